@@ -0,0 +1,153 @@
+//! Benchmarks for the read API.
+//!
+//! Run with `cargo bench --bench read`. This does not use the unstable `test`
+//! crate, so it works on stable Rust.
+use object::{macho, read, write, Architecture, BinaryFormat, Endianness, Object, ObjectSection};
+use object::{SectionKind, SymbolFlags, SymbolKind, SymbolScope};
+use std::time::{Duration, Instant};
+
+/// Run `f` repeatedly and print the average time per iteration.
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    // Warm up, and determine the number of iterations to run.
+    let mut iterations = 1;
+    loop {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            f();
+        }
+        if start.elapsed() > Duration::from_millis(100) {
+            break;
+        }
+        iterations *= 2;
+    }
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<40} {:>12} ns/iter",
+        name,
+        elapsed.as_nanos() / iterations as u128
+    );
+}
+
+/// The data of each section.
+///
+/// This is null terminated so that it can also be read with `cstr_at`.
+const SECTION_DATA: [u8; 16] = [0xc3, 0xc3, 0xc3, 0xc3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// Create a relocatable file with the given number of sections and symbols.
+fn object_file(format: BinaryFormat, sections: usize, symbols: usize) -> Vec<u8> {
+    let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+    let mut section_ids = Vec::new();
+    for i in 0..sections {
+        let section = object.add_section(
+            Vec::new(),
+            format!(".text.f{}", i).into_bytes(),
+            SectionKind::Text,
+        );
+        object.append_section_data(section, &SECTION_DATA, 16);
+        section_ids.push(section);
+    }
+    for i in 0..symbols {
+        object.add_symbol(write::Symbol {
+            name: format!("f{}", i).into_bytes(),
+            value: 0,
+            size: 16,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(section_ids[i % sections]),
+            flags: SymbolFlags::None,
+        });
+    }
+    object.write().unwrap()
+}
+
+/// Parsing only reads the headers, so its time should not depend on the number of symbols.
+fn parse() {
+    for &symbols in &[100, 10_000, 100_000] {
+        let data = object_file(BinaryFormat::Elf, 1, symbols);
+        bench(&format!("parse, {} symbols", symbols), || {
+            let file = read::File::parse(&*data).unwrap();
+            assert!(file.section_by_name(".text.f0").is_some());
+        });
+    }
+}
+
+/// Look up many sections by name.
+///
+/// COFF and PE files use a cached name index for this, instead of a linear search.
+fn section_by_name() {
+    let sections = 500;
+    let data = object_file(BinaryFormat::Coff, sections, 0);
+    let names = (0..sections)
+        .rev()
+        .map(|i| format!(".text.f{}", i))
+        .collect::<Vec<_>>();
+    let file = read::File::parse(&*data).unwrap();
+    bench("section_by_name, linear search", || {
+        for name in &names {
+            let section = file.sections().find(|section| section.name() == Ok(name));
+            assert!(section.is_some());
+        }
+    });
+    bench("section_by_name, cached index", || {
+        for name in &names {
+            assert!(file.section_by_name(name).is_some());
+        }
+    });
+    bench("section_by_name, parse and build index", || {
+        let file = read::File::parse(&*data).unwrap();
+        for name in &names {
+            assert!(file.section_by_name(name).is_some());
+        }
+    });
+}
+
+/// Read strings at many addresses.
+///
+/// Files that are not relocatable use a cached index of the section addresses for this,
+/// instead of a linear search.
+fn cstr_at() {
+    let sections = 1000;
+    let mut data = object_file(BinaryFormat::MachO, sections, 0);
+    // Change the file type to `MH_EXECUTE`, so that the file is not relocatable.
+    data[12..16].copy_from_slice(&macho::MH_EXECUTE.to_le_bytes());
+    let file = read::File::parse(&*data).unwrap();
+    let addresses = file
+        .sections()
+        .map(|section| section.address() + 1)
+        .collect::<Vec<_>>();
+    let expected = Some(&SECTION_DATA[1..4]);
+    bench("cstr_at, linear search", || {
+        for &address in &addresses {
+            let section = file
+                .sections()
+                .find(|section| address.wrapping_sub(section.address()) < section.size())
+                .unwrap();
+            let offset = (address - section.address()) as usize;
+            let cstr = object::Bytes(section.data().unwrap()).read_string_at(offset);
+            assert_eq!(cstr.ok(), expected);
+        }
+    });
+    bench("cstr_at, cached index", || {
+        for &address in &addresses {
+            assert_eq!(file.cstr_at(address), expected);
+        }
+    });
+    bench("cstr_at, parse and build index", || {
+        let file = read::File::parse(&*data).unwrap();
+        for &address in &addresses {
+            assert_eq!(file.cstr_at(address), expected);
+        }
+    });
+}
+
+fn main() {
+    parse();
+    section_by_name();
+    cstr_at();
+}
@@ -8,11 +8,14 @@ pub enum Architecture {
     Arm,
     Avr,
     Bpf,
+    Csky,
     I386,
     X86_64,
     #[allow(non_camel_case_types)]
     X86_64_X32,
     Hexagon,
+    LoongArch32,
+    LoongArch64,
     Mips,
     Mips64,
     Msp430,
@@ -21,6 +24,7 @@ pub enum Architecture {
     Riscv32,
     Riscv64,
     S390x,
+    Sparc,
     Sparc64,
     Wasm32,
 }
@@ -36,10 +40,13 @@ impl Architecture {
             Architecture::Arm => Some(AddressSize::U32),
             Architecture::Avr => Some(AddressSize::U8),
             Architecture::Bpf => Some(AddressSize::U64),
+            Architecture::Csky => Some(AddressSize::U32),
             Architecture::I386 => Some(AddressSize::U32),
             Architecture::X86_64 => Some(AddressSize::U64),
             Architecture::X86_64_X32 => Some(AddressSize::U32),
             Architecture::Hexagon => Some(AddressSize::U32),
+            Architecture::LoongArch32 => Some(AddressSize::U32),
+            Architecture::LoongArch64 => Some(AddressSize::U64),
             Architecture::Mips => Some(AddressSize::U32),
             Architecture::Mips64 => Some(AddressSize::U64),
             Architecture::Msp430 => Some(AddressSize::U16),
@@ -48,6 +55,7 @@ impl Architecture {
             Architecture::Riscv32 => Some(AddressSize::U32),
             Architecture::Riscv64 => Some(AddressSize::U64),
             Architecture::S390x => Some(AddressSize::U64),
+            Architecture::Sparc => Some(AddressSize::U32),
             Architecture::Sparc64 => Some(AddressSize::U64),
             Architecture::Wasm32 => Some(AddressSize::U32),
         }
@@ -236,6 +244,8 @@ pub enum SymbolKind {
     /// The symbol is the name of a file. It precedes symbols within that file.
     File,
     /// The symbol is for a code label.
+    ///
+    /// For ELF, this is used for `STT_NOTYPE` symbols that are defined in a section.
     Label,
     /// The symbol is for a thread local storage entity.
     Tls,
@@ -292,6 +302,26 @@ pub enum RelocationKind {
     SectionOffset,
     /// The index of the section containing the symbol.
     SectionIndex,
+    /// A thread-local storage reference using the general dynamic model.
+    ///
+    /// This refers to the GOT entries for the module and offset of the symbol.
+    /// The exact operation depends on the architecture.
+    TlsGd,
+    /// A thread-local storage reference using the local dynamic model.
+    ///
+    /// This refers to the GOT entry for the module containing the relocation.
+    /// The exact operation depends on the architecture.
+    TlsLd,
+    /// A thread-local storage reference using the initial exec model.
+    ///
+    /// This refers to the GOT entry for the offset of the symbol from the thread pointer.
+    /// The exact operation depends on the architecture.
+    TlsIe,
+    /// A thread-local storage reference using the local exec model.
+    ///
+    /// This is the offset of the symbol from the thread pointer.
+    /// The exact operation depends on the architecture.
+    TlsLe,
     /// Some other ELF relocation. The value is dependent on the architecture.
     Elf(u32),
     /// Some other Mach-O relocation. The value is dependent on the architecture.
@@ -305,6 +335,33 @@ pub enum RelocationKind {
     Coff(u16),
 }
 
+/// Relocation fields that are specific to each file format and architecture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RelocationFlags {
+    /// ELF relocation fields.
+    Elf {
+        /// The relocation type from `r_info`.
+        r_type: u32,
+    },
+    /// Mach-O relocation fields.
+    MachO {
+        /// `r_type` field in the relocation.
+        r_type: u8,
+        /// `r_pcrel` field in the relocation.
+        r_pcrel: bool,
+        /// `r_length` field in the relocation.
+        r_length: u8,
+    },
+    /// COFF relocation fields.
+    Coff {
+        /// `Type` field in the relocation.
+        ///
+        /// For PE base relocations, this is one of the `IMAGE_REL_BASED_*` values.
+        typ: u16,
+    },
+}
+
 /// Information about how the result of the relocation operation is encoded in the place.
 ///
 /// This is usually architecture specific, such as specifying an addressing mode or
@@ -389,6 +446,33 @@ pub enum SectionFlags {
     },
 }
 
+/// Segment flags that are specific to each file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SegmentFlags {
+    /// No segment flags.
+    None,
+    /// ELF segment flags.
+    Elf {
+        /// `p_flags` field in the segment header.
+        p_flags: u32,
+    },
+    /// Mach-O segment flags.
+    MachO {
+        /// `flags` field in the segment header.
+        flags: u32,
+        /// `maxprot` field in the segment header.
+        maxprot: u32,
+        /// `initprot` field in the segment header.
+        initprot: u32,
+    },
+    /// COFF segment flags.
+    Coff {
+        /// `Characteristics` field in the segment header.
+        characteristics: u32,
+    },
+}
+
 /// Symbol flags that are specific to each file format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
@@ -562,6 +562,8 @@ pub const EM_RISCV: u16 = 243;
 pub const EM_BPF: u16 = 247;
 /// C-SKY
 pub const EM_CSKY: u16 = 252;
+/// LoongArch
+pub const EM_LOONGARCH: u16 = 258;
 /// Digital Alpha
 pub const EM_ALPHA: u16 = 0x9026;
 
@@ -693,8 +695,16 @@ pub const SHT_PREINIT_ARRAY: u32 = 16;
 pub const SHT_GROUP: u32 = 17;
 /// Extended section indices for a symbol table.
 pub const SHT_SYMTAB_SHNDX: u32 = 18;
+/// Relative relocation entries in a compact format.
+pub const SHT_RELR: u32 = 19;
 /// Start of OS-specific section types.
 pub const SHT_LOOS: u32 = 0x6000_0000;
+/// Version definition section.
+pub const SHT_GNU_VERDEF: u32 = 0x6fff_fffd;
+/// Version needs section.
+pub const SHT_GNU_VERNEED: u32 = 0x6fff_fffe;
+/// Version symbol table.
+pub const SHT_GNU_VERSYM: u32 = 0x6fff_ffff;
 /// End of OS-specific section types.
 pub const SHT_HIOS: u32 = 0x6fff_ffff;
 /// Start of processor-specific section types.
@@ -1506,6 +1516,12 @@ pub const DT_PREINIT_ARRAY: u32 = 32;
 pub const DT_PREINIT_ARRAYSZ: u32 = 33;
 /// Address of SYMTAB_SHNDX section
 pub const DT_SYMTAB_SHNDX: u32 = 34;
+/// Total size of RELR relative relocations
+pub const DT_RELRSZ: u32 = 35;
+/// Address of RELR relative relocations
+pub const DT_RELR: u32 = 36;
+/// Size of one RELR relative relocation
+pub const DT_RELRENT: u32 = 37;
 /// Start of OS-specific
 pub const DT_LOOS: u32 = 0x6000_000d;
 /// End of OS-specific
@@ -1671,8 +1687,51 @@ pub const DF_1_PIE: u32 = 0x0800_0000;
 
 // TODO: ELF*_Verdef, VER_DEF_*, VER_FLG_*, VER_NDX_*
 // TODO: Elf*_Verdaux
-// TODO: Elf*_Verneed, VER_NEED_*
-// TODO: Elf*_Vernaux, VER_FLG_*
+
+/// Version dependency.
+///
+/// An entry in the `SHT_GNU_VERNEED` section.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Verneed<E: Endian> {
+    /// Version of structure. Must be `VER_NEED_CURRENT`.
+    pub vn_version: U16<E>,
+    /// Number of associated aux entries.
+    pub vn_cnt: U16<E>,
+    /// Offset of filename for this dependency.
+    pub vn_file: U32<E>,
+    /// Offset in bytes to vernaux array.
+    pub vn_aux: U32<E>,
+    /// Offset in bytes to next verneed entry.
+    pub vn_next: U32<E>,
+}
+
+// Values for `Verneed::vn_version`.
+/// No version
+pub const VER_NEED_NONE: u16 = 0;
+/// Current version
+pub const VER_NEED_CURRENT: u16 = 1;
+
+/// Auxiliary needed version information.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Vernaux<E: Endian> {
+    /// Hash value of dependency name.
+    pub vna_hash: U32<E>,
+    /// Dependency specific information.
+    pub vna_flags: U16<E>,
+    /// Version index as used in the symbol version table.
+    pub vna_other: U16<E>,
+    /// Dependency name string offset.
+    pub vna_name: U32<E>,
+    /// Offset in bytes to next vernaux entry.
+    pub vna_next: U32<E>,
+}
+
+// Values for `Vernaux::vna_flags`.
+/// Weak version identifier
+pub const VER_FLG_WEAK: u16 = 0x2;
+
 // TODO: Elf*_auxv_t, AT_*
 
 /// Note section entry header.
@@ -1766,7 +1825,36 @@ pub const NT_GNU_GOLD_VERSION: u32 = 4;
 /// Program property.
 pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
 
-// TODO: GNU_PROPERTY_*
+// Values for `GnuProperty::pr_type`.
+/// Stack size.
+pub const GNU_PROPERTY_STACK_SIZE: u32 = 1;
+/// No copy relocation on protected data symbol.
+pub const GNU_PROPERTY_NO_COPY_ON_PROTECTED: u32 = 2;
+/// Start of processor-specific range.
+pub const GNU_PROPERTY_LOPROC: u32 = 0xc000_0000;
+/// End of processor-specific range.
+pub const GNU_PROPERTY_HIPROC: u32 = 0xdfff_ffff;
+/// Start of application-specific range.
+pub const GNU_PROPERTY_LOUSER: u32 = 0xe000_0000;
+/// End of application-specific range.
+pub const GNU_PROPERTY_HIUSER: u32 = 0xffff_ffff;
+
+/// AArch64 specific GNU properties.
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc000_0000;
+
+/// Branch target identification.
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_BTI: u32 = 1 << 0;
+/// Pointer authentication.
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_PAC: u32 = 1 << 1;
+
+/// x86 specific GNU properties.
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc000_0002;
+
+/// Indirect branch tracking.
+pub const GNU_PROPERTY_X86_FEATURE_1_IBT: u32 = 1 << 0;
+/// Shadow stack.
+pub const GNU_PROPERTY_X86_FEATURE_1_SHSTK: u32 = 1 << 1;
+
 // TODO: Elf*_Move
 
 // Motorola 68k specific definitions.
@@ -6068,6 +6156,8 @@ unsafe_impl_endian_pod!(
     ProgramHeader64,
     Dyn32,
     Dyn64,
+    Verneed,
+    Vernaux,
     NoteHeader32,
     NoteHeader64,
 );
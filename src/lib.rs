@@ -16,6 +16,47 @@
 //! for each file format: [ELF](read::elf::ElfFile), [Mach-O](read::macho::MachOFile),
 //! [COFF](read::coff::CoffFile), [PE](read::pe::PeFile), [Wasm](read::wasm::WasmFile).
 //!
+//! The segments, sections and symbols of a file are each accessed using an iterator
+//! returned by [read::Object::segments], [read::Object::sections] and
+//! [read::Object::symbols]. A [read::File] reference can also be iterated over directly
+//! to access its sections, and likewise a symbol table returned by
+//! [read::Object::symbol_table] for its symbols. There is no such shorthand for segments,
+//! since a file reference can only iterate over one kind of item.
+//!
+//! ```
+//! use object::{Object, ObjectSection, ObjectSegment, ObjectSymbol};
+//!
+//! fn dump(data: &[u8]) -> object::Result<()> {
+//!     let file = object::File::parse(data)?;
+//!     for segment in file.segments() {
+//!         println!("{:?} {:#x}", segment.name()?, segment.address());
+//!     }
+//!     for section in &file {
+//!         println!("{} {:#x}", section.name()?, section.address());
+//!     }
+//!     for symbol in file.symbols() {
+//!         println!("{} {:#x}", symbol.name()?, symbol.address());
+//!     }
+//!     if let Some(symbol_table) = file.symbol_table() {
+//!         for symbol in &symbol_table {
+//!             println!("{}", symbol.name()?);
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The traits can also be imported with `use object::prelude::*`.
+//!
+//! The data to parse can be any type that implements [read::ReadRef], such as a
+//! `&[u8]` for data that has been read or memory-mapped, or a [read::ReadCache]
+//! for reading directly from a `std::fs::File`.
+//!
+//! The parsed file and the segments, sections and symbols that borrow from it are
+//! `Send` and `Sync` when the data is a `&[u8]`, so they can be shared between threads.
+//! This is not the case when using [read::ReadCache], because it mutates its cache
+//! when reading.
+//!
 //! ## Low level read API
 //!
 //! In addition to the unified read API, the various `read` modules define helpers that
@@ -70,6 +111,9 @@ pub mod read;
 #[cfg(feature = "read_core")]
 pub use read::*;
 
+#[cfg(feature = "read_core")]
+pub mod prelude;
+
 #[cfg(feature = "write_core")]
 pub mod write;
 
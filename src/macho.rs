@@ -7,7 +7,7 @@
 
 #![allow(missing_docs)]
 
-use crate::endian::{BigEndian, Endian, U64Bytes, U16, U32, U64};
+use crate::endian::{BigEndian, Endian, U64Bytes, I32, U16, U32, U64};
 use crate::pod::Pod;
 
 // Definitions from "/usr/include/mach/machine.h".
@@ -2248,6 +2248,133 @@ pub const DICE_KIND_JUMP_TABLE16: u32 = 0x0003;
 pub const DICE_KIND_JUMP_TABLE32: u32 = 0x0004;
 pub const DICE_KIND_ABS_JUMP_TABLE32: u32 = 0x0005;
 
+/*
+ * The LC_DYLD_CHAINED_FIXUPS load command uses a LinkeditDataCommand
+ * to point to a DyldChainedFixupsHeader.
+ */
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct DyldChainedFixupsHeader<E: Endian> {
+    /// 0
+    pub fixups_version: U32<E>,
+    /// offset of DyldChainedStartsInImage in chain_data
+    pub starts_offset: U32<E>,
+    /// offset of imports table in chain_data
+    pub imports_offset: U32<E>,
+    /// offset of symbol strings in chain_data
+    pub symbols_offset: U32<E>,
+    /// number of imported symbol names
+    pub imports_count: U32<E>,
+    /// DYLD_CHAINED_IMPORT*
+    pub imports_format: U32<E>,
+    /// 0 => uncompressed, 1 => zlib compressed
+    pub symbols_format: U32<E>,
+}
+
+/*
+ * This struct is embedded in LC_DYLD_CHAINED_FIXUPS payload.
+ * It is followed by `seg_count` `U32` offsets (seg_info_offset) to
+ * DyldChainedStartsInSegment, relative to the start of this struct.
+ * An offset of 0 means the segment has no fixups.
+ */
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct DyldChainedStartsInImage<E: Endian> {
+    pub seg_count: U32<E>,
+}
+
+/*
+ * This struct is embedded in DyldChainedStartsInImage
+ * and passed down to the kernel for page-in linking.
+ * It is followed by a `U16` page_count and `page_count` `U16` page_start values.
+ */
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct DyldChainedStartsInSegment<E: Endian> {
+    /// size of this (amount kernel needs to copy)
+    pub size: U32<E>,
+    /// 0x1000 or 0x4000
+    pub page_size: U16<E>,
+    /// DYLD_CHAINED_PTR_*
+    pub pointer_format: U16<E>,
+    /// offset in memory to start of segment
+    pub segment_offset: U64Bytes<E>,
+    /// for 32-bit OS, any value beyond this is not a pointer
+    pub max_valid_pointer: U32<E>,
+}
+
+// Values for DyldChainedStartsInSegment::page_start.
+/// used in page_start[] to denote a page with no fixups
+pub const DYLD_CHAINED_PTR_START_NONE: u16 = 0xFFFF;
+/// used in page_start[] to denote a page which has multiple starts
+pub const DYLD_CHAINED_PTR_START_MULTI: u16 = 0x8000;
+/// used in chain_starts[] to denote last start in list for page
+pub const DYLD_CHAINED_PTR_START_LAST: u16 = 0x8000;
+
+// Values for DyldChainedStartsInSegment::pointer_format.
+/// stride 8, unauth target is vmaddr
+pub const DYLD_CHAINED_PTR_ARM64E: u16 = 1;
+/// target is vmaddr
+pub const DYLD_CHAINED_PTR_64: u16 = 2;
+pub const DYLD_CHAINED_PTR_32: u16 = 3;
+pub const DYLD_CHAINED_PTR_32_CACHE: u16 = 4;
+pub const DYLD_CHAINED_PTR_32_FIRMWARE: u16 = 5;
+/// target is vm offset
+pub const DYLD_CHAINED_PTR_64_OFFSET: u16 = 6;
+/// old name
+pub const DYLD_CHAINED_PTR_ARM64E_OFFSET: u16 = 7;
+/// stride 4, unauth target is vm offset
+pub const DYLD_CHAINED_PTR_ARM64E_KERNEL: u16 = 7;
+pub const DYLD_CHAINED_PTR_64_KERNEL_CACHE: u16 = 8;
+/// stride 8, unauth target is vm offset
+pub const DYLD_CHAINED_PTR_ARM64E_USERLAND: u16 = 9;
+/// stride 4, unauth target is vmaddr
+pub const DYLD_CHAINED_PTR_ARM64E_FIRMWARE: u16 = 10;
+/// stride 1, x86_64 kernel caches
+pub const DYLD_CHAINED_PTR_X86_64_KERNEL_CACHE: u16 = 11;
+/// stride 8, unauth target is vm offset, 24-bit bind
+pub const DYLD_CHAINED_PTR_ARM64E_USERLAND24: u16 = 12;
+
+// Values for DyldChainedFixupsHeader::imports_format.
+pub const DYLD_CHAINED_IMPORT: u32 = 1;
+pub const DYLD_CHAINED_IMPORT_ADDEND: u32 = 2;
+pub const DYLD_CHAINED_IMPORT_ADDEND64: u32 = 3;
+
+/*
+ * DYLD_CHAINED_IMPORT
+ *
+ * The import is a bitfield: lib_ordinal:8, weak_import:1, name_offset:23.
+ */
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct DyldChainedImport<E: Endian> {
+    pub import: U32<E>,
+}
+
+/*
+ * DYLD_CHAINED_IMPORT_ADDEND
+ *
+ * The import is a bitfield: lib_ordinal:8, weak_import:1, name_offset:23.
+ */
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct DyldChainedImportAddend<E: Endian> {
+    pub import: U32<E>,
+    pub addend: I32<E>,
+}
+
+/*
+ * DYLD_CHAINED_IMPORT_ADDEND64
+ *
+ * The import is a bitfield: lib_ordinal:16, weak_import:1, reserved:15, name_offset:32.
+ */
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct DyldChainedImportAddend64<E: Endian> {
+    pub import: U64Bytes<E>,
+    pub addend: U64Bytes<E>,
+}
+
 /*
  * Sections of type S_THREAD_LOCAL_VARIABLES contain an array
  * of TlvDescriptor structures.
@@ -3246,6 +3373,12 @@ unsafe_impl_endian_pod!(
     EntryPointCommand,
     SourceVersionCommand,
     DataInCodeEntry,
+    DyldChainedFixupsHeader,
+    DyldChainedStartsInImage,
+    DyldChainedStartsInSegment,
+    DyldChainedImport,
+    DyldChainedImportAddend,
+    DyldChainedImportAddend64,
     //TlvDescriptor,
     NoteCommand,
     Nlist32,
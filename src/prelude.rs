@@ -0,0 +1,23 @@
+//! The traits and types that are needed for most uses of the unified read API.
+//!
+//! The methods of the unified read API are defined on traits, which must be in
+//! scope before the methods can be called. This module allows importing all of
+//! them at once.
+//!
+//! ```
+//! use object::prelude::*;
+//!
+//! fn text_size(data: &[u8]) -> object::Result<u64> {
+//!     let file = object::File::parse(data)?;
+//!     Ok(file
+//!         .sections()
+//!         .filter(|section| section.kind() == SectionKind::Text)
+//!         .map(|section| section.size())
+//!         .sum())
+//! }
+//! ```
+
+pub use crate::read::{
+    Object, ObjectComdat, ObjectSection, ObjectSegment, ObjectSymbol, ObjectSymbolTable,
+};
+pub use crate::{Architecture, SectionKind, SymbolKind};
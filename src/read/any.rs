@@ -1,6 +1,10 @@
+use alloc::borrow::Cow;
 use alloc::fmt;
+use alloc::string::String;
 use alloc::vec::Vec;
+use core::convert::TryFrom;
 use core::marker::PhantomData;
+use core::result;
 
 #[cfg(feature = "coff")]
 use crate::read::coff;
@@ -13,11 +17,12 @@ use crate::read::pe;
 #[cfg(feature = "wasm")]
 use crate::read::wasm;
 use crate::read::{
-    self, Architecture, BinaryFormat, CodeView, ComdatKind, CompressedData, CompressedFileRange,
-    Error, Export, FileFlags, FileKind, Import, Object, ObjectComdat, ObjectMap, ObjectSection,
-    ObjectSegment, ObjectSymbol, ObjectSymbolTable, ReadRef, Relocation, Result, SectionFlags,
-    SectionIndex, SectionKind, SymbolFlags, SymbolIndex, SymbolKind, SymbolMap, SymbolMapName,
-    SymbolScope, SymbolSection,
+    self, AddressMap, Architecture, BinaryFormat, CodeView, ComdatKind, CompressedData,
+    CompressedFileRange, Error, Export, ExportedSymbol, FileFlags, FileKind, Import, Object,
+    ObjectComdat, ObjectMap, ObjectSection, ObjectSegment, ObjectSymbol, ObjectSymbolTable,
+    ParseError, ReadError, ReadRef, Relocation, Result, SectionFlags, SectionIndex, SectionKind,
+    SegmentFlags, SymbolFlags, SymbolIndex, SymbolKind, SymbolMap, SymbolMapName, SymbolScope,
+    SymbolSection,
 };
 #[allow(unused_imports)]
 use crate::Endianness;
@@ -119,6 +124,29 @@ macro_rules! map_inner_option {
     };
 }
 
+macro_rules! map_inner_vec {
+    ($inner:expr, $from:ident, $to:ident, | $var:ident | $body:expr) => {
+        match $inner {
+            #[cfg(feature = "coff")]
+            $from::Coff(ref $var) => $body.map($to::Coff).collect(),
+            #[cfg(feature = "elf")]
+            $from::Elf32(ref $var) => $body.map($to::Elf32).collect(),
+            #[cfg(feature = "elf")]
+            $from::Elf64(ref $var) => $body.map($to::Elf64).collect(),
+            #[cfg(feature = "macho")]
+            $from::MachO32(ref $var) => $body.map($to::MachO32).collect(),
+            #[cfg(feature = "macho")]
+            $from::MachO64(ref $var) => $body.map($to::MachO64).collect(),
+            #[cfg(feature = "pe")]
+            $from::Pe32(ref $var) => $body.map($to::Pe32).collect(),
+            #[cfg(feature = "pe")]
+            $from::Pe64(ref $var) => $body.map($to::Pe64).collect(),
+            #[cfg(feature = "wasm")]
+            $from::Wasm(ref $var) => $body.map($to::Wasm).collect(),
+        }
+    };
+}
+
 macro_rules! map_inner_option_mut {
     ($inner:expr, $from:ident, $to:ident, | $var:ident | $body:expr) => {
         match $inner {
@@ -196,6 +224,11 @@ enum FileInternal<'data, R: ReadRef<'data>> {
 
 impl<'data, R: ReadRef<'data>> File<'data, R> {
     /// Parse the raw file data.
+    ///
+    /// For ELF, Mach-O, COFF and PE, this only reads the file headers and the
+    /// section and segment tables. The symbol table data is located, but the
+    /// symbols are not read until they are requested, so the time taken does
+    /// not depend on the number of symbols. Wasm files are fully parsed.
     pub fn parse(data: R) -> Result<Self> {
         let inner = match FileKind::parse(data)? {
             #[cfg(feature = "elf")]
@@ -220,6 +253,58 @@ impl<'data, R: ReadRef<'data>> File<'data, R> {
         Ok(File { inner })
     }
 
+    /// Parse the raw file data, and check that it is not truncated.
+    ///
+    /// This is the same as `File::parse`, but also returns an error if the file
+    /// range of any section or segment extends past the end of the data, as
+    /// determined by `Object::required_file_size`. Without this check, the error
+    /// is only returned when the data of the section is read.
+    ///
+    /// Returns `ParseError::Truncated` with the required and actual sizes if the
+    /// file is truncated.
+    pub fn parse_complete(data: R) -> result::Result<Self, ParseError> {
+        let file = Self::parse(data)?;
+        let actual = data.len().read_error("Unknown file data length")?;
+        let expected = file.required_file_size();
+        if expected > actual {
+            return Err(ParseError::Truncated { expected, actual });
+        }
+        Ok(file)
+    }
+
+    /// Parse the raw file data as the given file format.
+    ///
+    /// This does not use the file magic to determine the file format, which is useful
+    /// for formats that can not be reliably detected, such as COFF. The file must still
+    /// be valid for the given format, and for formats that have both 32-bit and
+    /// 64-bit variants, the variant is determined from the file.
+    pub fn parse_as(data: R, format: BinaryFormat) -> Result<Self> {
+        let inner = match format {
+            #[cfg(feature = "elf")]
+            BinaryFormat::Elf => match elf::ElfFile64::parse(data) {
+                Ok(file) => FileInternal::Elf64(file),
+                Err(_) => FileInternal::Elf32(elf::ElfFile32::parse(data)?),
+            },
+            #[cfg(feature = "macho")]
+            BinaryFormat::MachO => match macho::MachOFile64::parse(data) {
+                Ok(file) => FileInternal::MachO64(file),
+                Err(_) => FileInternal::MachO32(macho::MachOFile32::parse(data)?),
+            },
+            #[cfg(feature = "pe")]
+            BinaryFormat::Pe => match pe::PeFile64::parse(data) {
+                Ok(file) => FileInternal::Pe64(file),
+                Err(_) => FileInternal::Pe32(pe::PeFile32::parse(data)?),
+            },
+            #[cfg(feature = "coff")]
+            BinaryFormat::Coff => FileInternal::Coff(coff::CoffFile::parse(data)?),
+            #[cfg(feature = "wasm")]
+            BinaryFormat::Wasm => FileInternal::Wasm(wasm::WasmFile::parse(data)?),
+            #[allow(unreachable_patterns)]
+            _ => return Err(Error("Unsupported file format")),
+        };
+        Ok(File { inner })
+    }
+
     /// Parse the raw file data at an arbitrary offset inside the input data.
     ///
     /// Currently, this is only supported for Mach-O images.
@@ -254,6 +339,313 @@ impl<'data, R: ReadRef<'data>> File<'data, R> {
             FileInternal::Wasm(_) => BinaryFormat::Wasm,
         }
     }
+
+    /// Return true if `debug_data` is a Mach-O file with the same UUID as this file.
+    ///
+    /// This can be used to check that the Mach-O file in the `Contents/Resources/DWARF`
+    /// directory of a `.dSYM` bundle contains the debug information for this file.
+    /// If `debug_data` is a fat Mach-O file, then each architecture is checked.
+    ///
+    /// Returns `Ok(false)` if either file does not have a UUID.
+    #[cfg(feature = "macho")]
+    pub fn mach_uuid_matches(&self, debug_data: &[u8]) -> Result<bool> {
+        use macho::FatArch;
+
+        let uuid = match self.mach_uuid()? {
+            Some(uuid) => uuid,
+            None => return Ok(false),
+        };
+        let matches =
+            |data: &[u8]| -> Result<bool> { Ok(File::parse(data)?.mach_uuid()? == Some(uuid)) };
+        match FileKind::parse(debug_data)? {
+            FileKind::MachOFat32 => {
+                for arch in crate::macho::FatHeader::parse_arch32(debug_data)? {
+                    if matches(arch.data(debug_data)?)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            FileKind::MachOFat64 => {
+                for arch in crate::macho::FatHeader::parse_arch64(debug_data)? {
+                    if matches(arch.data(debug_data)?)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            _ => matches(debug_data),
+        }
+    }
+}
+
+#[cfg(feature = "write_core")]
+impl<'data, R: ReadRef<'data>> File<'data, R> {
+    /// Write the file to `w`.
+    ///
+    /// This is only supported for relocatable object files in the formats that
+    /// are supported by [`write::Object`](crate::write::Object).
+    ///
+    /// The file is rebuilt from its sections, symbols, relocations and comdats,
+    /// so the output is equivalent to the input but is not necessarily identical.
+    /// In particular:
+    /// - metadata sections, such as symbol tables, string tables and relocation
+    ///   sections, are regenerated instead of being copied
+    /// - the layout of the headers and section data is determined by the writer
+    /// - the writer may add symbols, such as section symbols
+    ///
+    /// Files that were themselves produced by this method are typically reproduced
+    /// byte-for-byte.
+    pub fn write_to<W: std::io::Write>(&self, w: &mut W) -> crate::write::Result<()> {
+        let data = self.to_write_object()?.write()?;
+        w.write_all(&data)
+            .map_err(|err| crate::write::Error(format!("{}", err)))
+    }
+
+    fn to_write_object(&self) -> crate::write::Result<crate::write::Object> {
+        use crate::write;
+        use std::collections::HashMap;
+
+        fn error<T: fmt::Display>(err: T) -> write::Error {
+            write::Error(format!("{}", err))
+        }
+
+        if !self.is_relocatable() {
+            return Err(write::Error(String::from(
+                "Unsupported file kind for writing",
+            )));
+        }
+        match self.format() {
+            #[cfg(feature = "coff")]
+            BinaryFormat::Coff => {}
+            #[cfg(feature = "elf")]
+            BinaryFormat::Elf => {}
+            #[cfg(feature = "macho")]
+            BinaryFormat::MachO => {}
+            _ => {
+                return Err(write::Error(String::from(
+                    "Unsupported file format for writing",
+                )))
+            }
+        }
+
+        let mut object = write::Object::new(self.format(), self.architecture(), self.endianness());
+        object.mangling = write::Mangling::None;
+        object.flags = self.flags();
+
+        let mut sections = HashMap::new();
+        for section in self.sections() {
+            if section.kind() == SectionKind::Metadata {
+                continue;
+            }
+            let segment_name = section.segment_name().map_err(error)?.unwrap_or("");
+            let name = section.name().map_err(error)?;
+            let section_id = object.add_section(
+                segment_name.as_bytes().to_vec(),
+                name.as_bytes().to_vec(),
+                section.kind(),
+            );
+            let out_section = object.section_mut(section_id);
+            if out_section.is_bss() {
+                out_section.append_bss(section.size(), section.align());
+            } else {
+                let data = section.data().map_err(error)?;
+                out_section.set_data(data.to_vec(), section.align());
+            }
+            out_section.flags = section.flags();
+            sections.insert(section.index(), section_id);
+        }
+        let section_id = |index: SectionIndex| {
+            sections
+                .get(&index)
+                .cloned()
+                .ok_or_else(|| write::Error(String::from("Invalid section index for writing")))
+        };
+
+        let mut symbols = HashMap::new();
+        for symbol in self.symbols() {
+            if symbol.kind() == SymbolKind::Null {
+                continue;
+            }
+            let (section, value) = match symbol.section() {
+                SymbolSection::None => (write::SymbolSection::None, symbol.address()),
+                SymbolSection::Undefined => (write::SymbolSection::Undefined, symbol.address()),
+                SymbolSection::Absolute => (write::SymbolSection::Absolute, symbol.address()),
+                SymbolSection::Common => (write::SymbolSection::Common, symbol.address()),
+                SymbolSection::Section(index) => {
+                    if symbol.kind() == SymbolKind::Section && !sections.contains_key(&index) {
+                        // Section symbols for metadata sections are not needed.
+                        continue;
+                    }
+                    let address = self.section_by_index(index).map_err(error)?.address();
+                    (
+                        write::SymbolSection::Section(section_id(index)?),
+                        symbol.address().wrapping_sub(address),
+                    )
+                }
+                _ => {
+                    return Err(write::Error(String::from(
+                        "Unsupported symbol section for writing",
+                    )))
+                }
+            };
+            let flags = match symbol.flags() {
+                SymbolFlags::None => SymbolFlags::None,
+                SymbolFlags::Elf { st_info, st_other } => SymbolFlags::Elf { st_info, st_other },
+                SymbolFlags::MachO { n_desc } => SymbolFlags::MachO { n_desc },
+                SymbolFlags::CoffSection {
+                    selection,
+                    associative_section,
+                } => SymbolFlags::CoffSection {
+                    selection,
+                    associative_section: associative_section.map(section_id).transpose()?,
+                },
+            };
+            let symbol_id = object.add_symbol(write::Symbol {
+                name: symbol.name().map_err(error)?.as_bytes().to_vec(),
+                value,
+                size: symbol.size(),
+                kind: symbol.kind(),
+                scope: symbol.scope(),
+                weak: symbol.is_weak(),
+                section,
+                flags,
+            });
+            symbols.insert(symbol.index(), symbol_id);
+        }
+
+        for section in self.sections() {
+            if section.kind() == SectionKind::Metadata {
+                continue;
+            }
+            let out_section = section_id(section.index())?;
+            for (offset, relocation) in section.relocations() {
+                let symbol = match relocation.target() {
+                    read::RelocationTarget::Symbol(index) => {
+                        *symbols.get(&index).ok_or_else(|| {
+                            write::Error(String::from("Invalid relocation symbol for writing"))
+                        })?
+                    }
+                    read::RelocationTarget::Section(index) => {
+                        object.section_symbol(section_id(index)?)
+                    }
+                    _ => {
+                        return Err(write::Error(String::from(
+                            "Unsupported relocation target for writing",
+                        )))
+                    }
+                };
+                object.add_relocation(
+                    out_section,
+                    write::Relocation {
+                        offset,
+                        size: relocation.size(),
+                        kind: relocation.kind(),
+                        encoding: relocation.encoding(),
+                        symbol,
+                        addend: relocation.addend(),
+                    },
+                )?;
+            }
+        }
+
+        for comdat in self.comdats() {
+            let symbol = *symbols
+                .get(&comdat.symbol())
+                .ok_or_else(|| write::Error(String::from("Invalid comdat symbol for writing")))?;
+            let sections = comdat
+                .sections()
+                .map(section_id)
+                .collect::<write::Result<Vec<_>>>()?;
+            object.add_comdat(write::Comdat {
+                kind: comdat.kind(),
+                symbol,
+                sections,
+            });
+        }
+
+        Ok(object)
+    }
+}
+
+/// An object file that owns its data.
+///
+/// This allows a parsed file to be stored without also storing the buffer
+/// that the file data is borrowed from. Use `OwnedFile::file` to access the
+/// `Object` trait implementation.
+///
+/// ```no_run
+/// use object::{Object, OwnedFile};
+///
+/// fn load(path: &str) -> Result<OwnedFile, Box<dyn std::error::Error>> {
+///     Ok(OwnedFile::parse(std::fs::read(path)?)?)
+/// }
+///
+/// fn entry(file: &OwnedFile) -> u64 {
+///     file.file().entry()
+/// }
+/// ```
+pub struct OwnedFile {
+    // This must be declared before `data` so that it is dropped first.
+    file: File<'static>,
+    data: Vec<u8>,
+}
+
+impl OwnedFile {
+    /// Parse the raw file data, taking ownership of it.
+    ///
+    /// See `File::parse`.
+    pub fn parse(data: Vec<u8>) -> Result<Self> {
+        // Extend the lifetime of the data to 'static.
+        // This is OK because the heap allocation of the vector does not move
+        // or change while `self` exists, `file` is dropped before `data`, and
+        // `file` is only accessible with a lifetime that is limited to that of `self`.
+        let slice = unsafe { core::mem::transmute::<&[u8], &'static [u8]>(&data) };
+        let file = File::parse(slice)?;
+        Ok(OwnedFile { file, data })
+    }
+
+    /// Return the parsed file.
+    ///
+    /// All data returned by the file borrows from `self`.
+    #[inline]
+    pub fn file<'data>(&'data self) -> &'data File<'data> {
+        &self.file
+    }
+
+    /// Return the raw file data.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return the raw file data, consuming `self`.
+    pub fn into_data(self) -> Vec<u8> {
+        let OwnedFile { file, data } = self;
+        drop(file);
+        data
+    }
+}
+
+impl fmt::Debug for OwnedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedFile")
+            .field("file", &self.file)
+            .finish()
+    }
+}
+
+/// Parse the raw file data.
+///
+/// This is equivalent to `File::parse`, and allows files to be used with
+/// code that is generic over `TryFrom`.
+impl<'data> TryFrom<&'data [u8]> for File<'data> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(data: &'data [u8]) -> Result<Self> {
+        File::parse(data)
+    }
 }
 
 impl<'data, R: ReadRef<'data>> read::private::Sealed for File<'data, R> {}
@@ -286,6 +678,10 @@ where
         with_inner!(self.inner, FileInternal, |x| x.is_64())
     }
 
+    fn is_relocatable(&self) -> bool {
+        with_inner!(self.inner, FileInternal, |x| x.is_relocatable())
+    }
+
     fn segments(&'file self) -> SegmentIterator<'data, 'file, R> {
         SegmentIterator {
             inner: map_inner!(self.inner, FileInternal, SegmentIteratorInternal, |x| x
@@ -299,6 +695,16 @@ where
         .map(|inner| Section { inner })
     }
 
+    fn sections_by_name(&'file self, section_name: &str) -> Vec<Section<'data, 'file, R>> {
+        let sections: Vec<_> = map_inner_vec!(self.inner, FileInternal, SectionInternal, |x| x
+            .sections_by_name(section_name)
+            .into_iter());
+        sections
+            .into_iter()
+            .map(|inner| Section { inner })
+            .collect()
+    }
+
     fn section_by_index(&'file self, index: SectionIndex) -> Result<Section<'data, 'file, R>> {
         map_inner_option!(self.inner, FileInternal, SectionInternal, |x| x
             .section_by_index(index))
@@ -312,6 +718,14 @@ where
         }
     }
 
+    fn cstr_at(&'file self, address: u64) -> Option<&'data [u8]> {
+        with_inner!(self.inner, FileInternal, |x| x.cstr_at(address))
+    }
+
+    fn address_map(&'file self) -> AddressMap<'data> {
+        with_inner!(self.inner, FileInternal, |x| x.address_map())
+    }
+
     fn comdats(&'file self) -> ComdatIterator<'data, 'file, R> {
         ComdatIterator {
             inner: map_inner!(self.inner, FileInternal, ComdatIteratorInternal, |x| x
@@ -335,6 +749,22 @@ where
         }
     }
 
+    fn local_symbols(&'file self) -> Vec<Symbol<'data, 'file, R>> {
+        let symbols: Vec<_> = map_inner_vec!(self.inner, FileInternal, SymbolInternal, |x| x
+            .local_symbols()
+            .into_iter()
+            .map(|x| (x, PhantomData)));
+        symbols.into_iter().map(|inner| Symbol { inner }).collect()
+    }
+
+    fn global_symbols(&'file self) -> Vec<Symbol<'data, 'file, R>> {
+        let symbols: Vec<_> = map_inner_vec!(self.inner, FileInternal, SymbolInternal, |x| x
+            .global_symbols()
+            .into_iter()
+            .map(|x| (x, PhantomData)));
+        symbols.into_iter().map(|inner| Symbol { inner }).collect()
+    }
+
     fn symbol_table(&'file self) -> Option<SymbolTable<'data, 'file, R>> {
         map_inner_option!(self.inner, FileInternal, SymbolTableInternal, |x| x
             .symbol_table()
@@ -358,26 +788,40 @@ where
         .map(|inner| SymbolTable { inner })
     }
 
-    #[cfg(feature = "elf")]
+    #[cfg(any(feature = "elf", feature = "pe"))]
     fn dynamic_relocations(&'file self) -> Option<DynamicRelocationIterator<'data, 'file, R>> {
         let inner = match self.inner {
+            #[cfg(feature = "elf")]
             FileInternal::Elf32(ref elf) => {
                 DynamicRelocationIteratorInternal::Elf32(elf.dynamic_relocations()?)
             }
+            #[cfg(feature = "elf")]
             FileInternal::Elf64(ref elf) => {
                 DynamicRelocationIteratorInternal::Elf64(elf.dynamic_relocations()?)
             }
+            #[cfg(feature = "pe")]
+            FileInternal::Pe32(ref pe) => {
+                DynamicRelocationIteratorInternal::Pe(pe.dynamic_relocations()?)
+            }
+            #[cfg(feature = "pe")]
+            FileInternal::Pe64(ref pe) => {
+                DynamicRelocationIteratorInternal::Pe(pe.dynamic_relocations()?)
+            }
             #[allow(unreachable_patterns)]
             _ => return None,
         };
         Some(DynamicRelocationIterator { inner })
     }
 
-    #[cfg(not(feature = "elf"))]
+    #[cfg(not(any(feature = "elf", feature = "pe")))]
     fn dynamic_relocations(&'file self) -> Option<DynamicRelocationIterator<'data, 'file, R>> {
         None
     }
 
+    fn relocation_sections(&'file self) -> Vec<(SectionIndex, SectionIndex)> {
+        with_inner!(self.inner, FileInternal, |x| x.relocation_sections())
+    }
+
     fn symbol_map(&self) -> SymbolMap<SymbolMapName<'data>> {
         with_inner!(self.inner, FileInternal, |x| x.symbol_map())
     }
@@ -394,6 +838,14 @@ where
         with_inner!(self.inner, FileInternal, |x| x.exports())
     }
 
+    fn init_functions(&'file self) -> Result<Vec<u64>> {
+        with_inner!(self.inner, FileInternal, |x| x.init_functions())
+    }
+
+    fn exported_symbols(&self) -> Result<Vec<ExportedSymbol<'data>>> {
+        with_inner!(self.inner, FileInternal, |x| x.exported_symbols())
+    }
+
     fn has_debug_symbols(&self) -> bool {
         with_inner!(self.inner, FileInternal, |x| x.has_debug_symbols())
     }
@@ -423,10 +875,26 @@ where
         with_inner!(self.inner, FileInternal, |x| x.pdb_info())
     }
 
+    fn compiler_info(&self) -> Vec<String> {
+        with_inner!(self.inner, FileInternal, |x| x.compiler_info())
+    }
+
     fn relative_address_base(&self) -> u64 {
         with_inner!(self.inner, FileInternal, |x| x.relative_address_base())
     }
 
+    fn preferred_load_address(&self) -> u64 {
+        with_inner!(self.inner, FileInternal, |x| x.preferred_load_address())
+    }
+
+    fn address_range(&'file self) -> (u64, u64) {
+        with_inner!(self.inner, FileInternal, |x| x.address_range())
+    }
+
+    fn contains_address(&'file self, address: u64) -> bool {
+        with_inner!(self.inner, FileInternal, |x| x.contains_address(address))
+    }
+
     fn entry(&self) -> u64 {
         with_inner!(self.inner, FileInternal, |x| x.entry())
     }
@@ -436,6 +904,23 @@ where
     }
 }
 
+/// Iterating over a `File` reference returns the sections of the file.
+///
+/// This is the same as `Object::sections`.
+impl<'data, 'file, R> IntoIterator for &'file File<'data, R>
+where
+    'data: 'file,
+    R: 'file + ReadRef<'data>,
+{
+    type Item = Section<'data, 'file, R>;
+    type IntoIter = SectionIterator<'data, 'file, R>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.sections()
+    }
+}
+
 /// An iterator over the segments of a `File`.
 #[derive(Debug)]
 pub struct SegmentIterator<'data, 'file, R: ReadRef<'data> = &'data [u8]>
@@ -527,6 +1012,24 @@ impl<'data, 'file, R: ReadRef<'data>> fmt::Debug for Segment<'data, 'file, R> {
     }
 }
 
+impl<'data, 'file, R: ReadRef<'data>> Segment<'data, 'file, R> {
+    /// Return an iterator over the sections that are contained in this segment.
+    ///
+    /// For ELF, this is the allocated sections whose address range lies within the segment.
+    /// For Mach-O, this is the sections defined in the segment's load command.
+    /// For PE and COFF, segments are the same as sections, so this returns a single section.
+    pub fn sections(&self) -> SegmentSectionIterator<'data, 'file, R> {
+        SegmentSectionIterator {
+            inner: map_inner!(
+                self.inner,
+                SegmentInternal,
+                SegmentSectionIteratorInternal,
+                |x| x.sections()
+            ),
+        }
+    }
+}
+
 impl<'data, 'file, R: ReadRef<'data>> read::private::Sealed for Segment<'data, 'file, R> {}
 
 impl<'data, 'file, R: ReadRef<'data>> ObjectSegment<'data> for Segment<'data, 'file, R> {
@@ -534,6 +1037,10 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSegment<'data> for Segment<'data, 'f
         with_inner!(self.inner, SegmentInternal, |x| x.address())
     }
 
+    fn relative_address(&self) -> u64 {
+        with_inner!(self.inner, SegmentInternal, |x| x.relative_address())
+    }
+
     fn size(&self) -> u64 {
         with_inner!(self.inner, SegmentInternal, |x| x.size())
     }
@@ -557,6 +1064,51 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSegment<'data> for Segment<'data, 'f
     fn name(&self) -> Result<Option<&str>> {
         with_inner!(self.inner, SegmentInternal, |x| x.name())
     }
+
+    fn flags(&self) -> SegmentFlags {
+        with_inner!(self.inner, SegmentInternal, |x| x.flags())
+    }
+}
+
+/// An iterator over the sections of a `Segment`.
+#[derive(Debug)]
+pub struct SegmentSectionIterator<'data, 'file, R: ReadRef<'data> = &'data [u8]>
+where
+    'data: 'file,
+{
+    inner: SegmentSectionIteratorInternal<'data, 'file, R>,
+}
+
+#[derive(Debug)]
+enum SegmentSectionIteratorInternal<'data, 'file, R: ReadRef<'data>>
+where
+    'data: 'file,
+{
+    #[cfg(feature = "coff")]
+    Coff(core::iter::Once<coff::CoffSection<'data, 'file, R>>),
+    #[cfg(feature = "elf")]
+    Elf32(elf::ElfSegmentSectionIterator32<'data, 'file, Endianness, R>),
+    #[cfg(feature = "elf")]
+    Elf64(elf::ElfSegmentSectionIterator64<'data, 'file, Endianness, R>),
+    #[cfg(feature = "macho")]
+    MachO32(macho::MachOSectionIterator32<'data, 'file, Endianness, R>),
+    #[cfg(feature = "macho")]
+    MachO64(macho::MachOSectionIterator64<'data, 'file, Endianness, R>),
+    #[cfg(feature = "pe")]
+    Pe32(core::iter::Once<pe::PeSection32<'data, 'file, R>>),
+    #[cfg(feature = "pe")]
+    Pe64(core::iter::Once<pe::PeSection64<'data, 'file, R>>),
+    #[cfg(feature = "wasm")]
+    Wasm(wasm::WasmSectionIterator<'data, 'file, R>),
+}
+
+impl<'data, 'file, R: ReadRef<'data>> Iterator for SegmentSectionIterator<'data, 'file, R> {
+    type Item = Section<'data, 'file, R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_inner!(self.inner, SegmentSectionIteratorInternal, SectionInternal)
+            .map(|inner| Section { inner })
+    }
 }
 
 /// An iterator of the sections of a `File`.
@@ -667,6 +1219,10 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSection<'data> for Section<'data, 'f
         with_inner!(self.inner, SectionInternal, |x| x.address())
     }
 
+    fn relative_address(&self) -> u64 {
+        with_inner!(self.inner, SectionInternal, |x| x.relative_address())
+    }
+
     fn size(&self) -> u64 {
         with_inner!(self.inner, SectionInternal, |x| x.size())
     }
@@ -695,6 +1251,10 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSection<'data> for Section<'data, 'f
         with_inner!(self.inner, SectionInternal, |x| x.compressed_data())
     }
 
+    fn relocated_data(&self) -> Result<Cow<'data, [u8]>> {
+        with_inner!(self.inner, SectionInternal, |x| x.relocated_data())
+    }
+
     fn name(&self) -> Result<&str> {
         with_inner!(self.inner, SectionInternal, |x| x.name())
     }
@@ -707,6 +1267,18 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSection<'data> for Section<'data, 'f
         with_inner!(self.inner, SectionInternal, |x| x.kind())
     }
 
+    fn is_stub(&self) -> bool {
+        with_inner!(self.inner, SectionInternal, |x| x.is_stub())
+    }
+
+    fn endianness(&self) -> Endianness {
+        with_inner!(self.inner, SectionInternal, |x| x.endianness())
+    }
+
+    fn relocation_count(&self) -> usize {
+        with_inner!(self.inner, SectionInternal, |x| x.relocation_count())
+    }
+
     fn relocations(&self) -> SectionRelocationIterator<'data, 'file, R> {
         SectionRelocationIterator {
             inner: map_inner!(
@@ -943,6 +1515,16 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSymbolTable<'data> for SymbolTable<'
     }
 }
 
+impl<'data, 'file, R: ReadRef<'data>> IntoIterator for &SymbolTable<'data, 'file, R> {
+    type Item = Symbol<'data, 'file, R>;
+    type IntoIter = SymbolIterator<'data, 'file, R>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.symbols()
+    }
+}
+
 /// An iterator over symbol table entries.
 #[derive(Debug)]
 pub struct SymbolIterator<'data, 'file, R = &'data [u8]>
@@ -1066,10 +1648,26 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSymbol<'data> for Symbol<'data, 'fil
         with_inner!(self.inner, SymbolInternal, |x| x.0.name())
     }
 
+    fn name_bytes(&self) -> Result<&'data [u8]> {
+        with_inner!(self.inner, SymbolInternal, |x| x.0.name_bytes())
+    }
+
     fn address(&self) -> u64 {
         with_inner!(self.inner, SymbolInternal, |x| x.0.address())
     }
 
+    fn relative_address(&self) -> u64 {
+        with_inner!(self.inner, SymbolInternal, |x| x.0.relative_address())
+    }
+
+    fn tls_offset(&self) -> Option<u64> {
+        with_inner!(self.inner, SymbolInternal, |x| x.0.tls_offset())
+    }
+
+    fn common_alignment(&self) -> Option<u64> {
+        with_inner!(self.inner, SymbolInternal, |x| x.0.common_alignment())
+    }
+
     fn size(&self) -> u64 {
         with_inner!(self.inner, SymbolInternal, |x| x.0.size())
     }
@@ -1098,6 +1696,14 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSymbol<'data> for Symbol<'data, 'fil
         with_inner!(self.inner, SymbolInternal, |x| x.0.is_weak())
     }
 
+    fn is_ifunc(&self) -> bool {
+        with_inner!(self.inner, SymbolInternal, |x| x.0.is_ifunc())
+    }
+
+    fn is_thumb(&self) -> bool {
+        with_inner!(self.inner, SymbolInternal, |x| x.0.is_thumb())
+    }
+
     fn scope(&self) -> SymbolScope {
         with_inner!(self.inner, SymbolInternal, |x| x.0.scope())
     }
@@ -1135,6 +1741,8 @@ where
     Elf32(elf::ElfDynamicRelocationIterator32<'data, 'file, Endianness, R>),
     #[cfg(feature = "elf")]
     Elf64(elf::ElfDynamicRelocationIterator64<'data, 'file, Endianness, R>),
+    #[cfg(feature = "pe")]
+    Pe(pe::PeDynamicRelocationIterator<'data>),
     // We need to always use the lifetime parameters.
     #[allow(unused)]
     None(PhantomData<(&'data (), &'file (), R)>),
@@ -1149,6 +1757,8 @@ impl<'data, 'file, R: ReadRef<'data>> Iterator for DynamicRelocationIterator<'da
             DynamicRelocationIteratorInternal::Elf32(ref mut elf) => elf.next(),
             #[cfg(feature = "elf")]
             DynamicRelocationIteratorInternal::Elf64(ref mut elf) => elf.next(),
+            #[cfg(feature = "pe")]
+            DynamicRelocationIteratorInternal::Pe(ref mut pe) => pe.next(),
             DynamicRelocationIteratorInternal::None(_) => None,
         }
     }
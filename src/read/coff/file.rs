@@ -1,9 +1,10 @@
 use alloc::vec::Vec;
 use core::str;
 
+use crate::read::util::LazyBox;
 use crate::read::{
-    self, Architecture, Export, FileFlags, Import, NoDynamicRelocationIterator, Object,
-    ObjectSection, ReadError, ReadRef, Result, SectionIndex, SymbolIndex,
+    self, Architecture, Export, FileFlags, Import, NoDynamicRelocationIterator, Object, ReadError,
+    ReadRef, Result, SectionIndex, SymbolIndex,
 };
 use crate::{pe, LittleEndian as LE};
 
@@ -20,6 +21,58 @@ pub(crate) struct CoffCommon<'data> {
     // TODO: ImageSymbolExBytes
     pub(crate) symbols: SymbolTable<'data>,
     pub(crate) image_base: u64,
+    /// The indices of the sections with valid names, sorted by name.
+    ///
+    /// This is built on first use.
+    pub(crate) section_names: LazyBox<Vec<usize>>,
+}
+
+impl<'data> CoffCommon<'data> {
+    /// Find the index and header of the first section with the given name.
+    pub(crate) fn section_by_name(
+        &self,
+        name: &[u8],
+    ) -> Option<(usize, &'data pe::ImageSectionHeader)> {
+        let index = *self.section_indices_by_name(name).first()?;
+        Some((index, self.sections.section(index).ok()?))
+    }
+
+    /// Find the indices of all sections with the given name, in file order.
+    ///
+    /// This uses a binary search of a name index that is built the first time
+    /// it is called.
+    pub(crate) fn section_indices_by_name(&self, name: &[u8]) -> &[usize] {
+        let strings = self.symbols.strings();
+        let section_name = |index| {
+            self.sections
+                .section(index)
+                .and_then(|section| section.name(strings))
+                .ok()
+        };
+        let indices = self.section_names.get_or_init(|| {
+            let mut indices = (1..=self.sections.len())
+                .filter(|&index| section_name(index).is_some())
+                .collect::<Vec<_>>();
+            // This sort is stable, so sections with the same name stay in file order.
+            indices.sort_by_key(|&index| section_name(index));
+            indices
+        });
+        // Find the first section with a name that is not less than `name`.
+        let (mut low, mut high) = (0, indices.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if section_name(indices[mid]) < Some(name) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        let count = indices[low..]
+            .iter()
+            .take_while(|&&index| section_name(index) == Some(name))
+            .count();
+        &indices[low..low + count]
+    }
 }
 
 /// A COFF object file.
@@ -44,6 +97,7 @@ impl<'data, R: ReadRef<'data>> CoffFile<'data, R> {
                 sections,
                 symbols,
                 image_base: 0,
+                section_names: LazyBox::new(),
             },
             data,
         })
@@ -89,16 +143,34 @@ where
         false
     }
 
+    #[inline]
+    fn is_relocatable(&self) -> bool {
+        true
+    }
+
     fn segments(&'file self) -> CoffSegmentIterator<'data, 'file, R> {
         CoffSegmentIterator {
             file: self,
-            iter: self.common.sections.iter(),
+            iter: self.common.sections.iter().enumerate(),
         }
     }
 
     fn section_by_name(&'file self, section_name: &str) -> Option<CoffSection<'data, 'file, R>> {
-        self.sections()
-            .find(|section| section.name() == Ok(section_name))
+        self.common
+            .section_by_name(section_name.as_bytes())
+            .map(|(index, section)| CoffSection {
+                file: self,
+                index: SectionIndex(index),
+                section,
+            })
+    }
+
+    fn sections_by_name(&'file self, section_name: &str) -> Vec<CoffSection<'data, 'file, R>> {
+        self.common
+            .section_indices_by_name(section_name.as_bytes())
+            .iter()
+            .filter_map(|&index| self.section_by_index(SectionIndex(index)).ok())
+            .collect()
     }
 
     fn section_by_index(&'file self, index: SectionIndex) -> Result<CoffSection<'data, 'file, R>> {
@@ -183,6 +255,15 @@ where
         0
     }
 
+    fn preferred_load_address(&self) -> u64 {
+        0
+    }
+
+    fn address_range(&self) -> (u64, u64) {
+        // COFF files are always relocatable.
+        (0, 0)
+    }
+
     #[inline]
     fn entry(&self) -> u64 {
         0
@@ -4,7 +4,8 @@ use core::slice;
 use crate::endian::LittleEndian as LE;
 use crate::pe;
 use crate::read::{
-    ReadRef, Relocation, RelocationEncoding, RelocationKind, RelocationTarget, SymbolIndex,
+    ReadRef, Relocation, RelocationEncoding, RelocationFlags, RelocationKind, RelocationTarget,
+    SymbolIndex,
 };
 
 use super::CoffFile;
@@ -72,6 +73,9 @@ impl<'data, 'file, R: ReadRef<'data>> Iterator for CoffRelocationIterator<'data,
                     target,
                     addend,
                     implicit_addend: true,
+                    flags: RelocationFlags::Coff {
+                        typ: relocation.typ.get(LE),
+                    },
                 },
             )
         })
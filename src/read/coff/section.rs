@@ -1,11 +1,12 @@
-use core::{iter, result, slice, str};
+use alloc::borrow::Cow;
+use core::{iter, mem, result, slice, str};
 
-use crate::endian::LittleEndian as LE;
+use crate::endian::{Endianness, LittleEndian as LE};
 use crate::pe;
 use crate::read::util::StringTable;
 use crate::read::{
     self, CompressedData, CompressedFileRange, Error, ObjectSection, ObjectSegment, ReadError,
-    ReadRef, Result, SectionFlags, SectionIndex, SectionKind,
+    ReadRef, Result, SectionFlags, SectionIndex, SectionKind, SegmentFlags,
 };
 
 use super::{CoffFile, CoffRelocationIterator};
@@ -83,15 +84,16 @@ impl<'data> SectionTable<'data> {
 #[derive(Debug)]
 pub struct CoffSegmentIterator<'data, 'file, R: ReadRef<'data> = &'data [u8]> {
     pub(super) file: &'file CoffFile<'data, R>,
-    pub(super) iter: slice::Iter<'data, pe::ImageSectionHeader>,
+    pub(super) iter: iter::Enumerate<slice::Iter<'data, pe::ImageSectionHeader>>,
 }
 
 impl<'data, 'file, R: ReadRef<'data>> Iterator for CoffSegmentIterator<'data, 'file, R> {
     type Item = CoffSegment<'data, 'file, R>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|section| CoffSegment {
+        self.iter.next().map(|(index, section)| CoffSegment {
             file: self.file,
+            index: SectionIndex(index + 1),
             section,
         })
     }
@@ -101,6 +103,7 @@ impl<'data, 'file, R: ReadRef<'data>> Iterator for CoffSegmentIterator<'data, 'f
 #[derive(Debug)]
 pub struct CoffSegment<'data, 'file, R: ReadRef<'data> = &'data [u8]> {
     pub(super) file: &'file CoffFile<'data, R>,
+    pub(super) index: SectionIndex,
     pub(super) section: &'data pe::ImageSectionHeader,
 }
 
@@ -110,6 +113,17 @@ impl<'data, 'file, R: ReadRef<'data>> CoffSegment<'data, 'file, R> {
             .coff_data(self.file.data)
             .read_error("Invalid COFF section offset or size")
     }
+
+    /// Return an iterator over the sections in this segment.
+    ///
+    /// COFF segments are the same as sections, so this always returns a single section.
+    pub fn sections(&self) -> iter::Once<CoffSection<'data, 'file, R>> {
+        iter::once(CoffSection {
+            file: self.file,
+            index: self.index,
+            section: self.section,
+        })
+    }
 }
 
 impl<'data, 'file, R: ReadRef<'data>> read::private::Sealed for CoffSegment<'data, 'file, R> {}
@@ -158,6 +172,12 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSegment<'data> for CoffSegment<'data
                 .read_error("Non UTF-8 COFF section name")?,
         ))
     }
+
+    #[inline]
+    fn flags(&self) -> SegmentFlags {
+        let characteristics = self.section.characteristics.get(LE);
+        SegmentFlags::Coff { characteristics }
+    }
 }
 
 /// An iterator over the sections of a `CoffFile`.
@@ -250,6 +270,10 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSection<'data> for CoffSection<'data
         self.data().map(CompressedData::none)
     }
 
+    fn relocated_data(&self) -> Result<Cow<'data, [u8]>> {
+        read::util::relocated_data(self.file, self, false)
+    }
+
     #[inline]
     fn name(&self) -> Result<&str> {
         let name = self.section.name(self.file.common.symbols.strings())?;
@@ -268,6 +292,22 @@ impl<'data, 'file, R: ReadRef<'data>> ObjectSection<'data> for CoffSection<'data
         self.section.kind()
     }
 
+    #[inline]
+    fn is_stub(&self) -> bool {
+        false
+    }
+
+    fn endianness(&self) -> Endianness {
+        Endianness::Little
+    }
+
+    fn relocation_count(&self) -> usize {
+        self.section
+            .coff_relocations(self.file.data)
+            .map(|relocations| relocations.len())
+            .unwrap_or(0)
+    }
+
     fn relocations(&self) -> CoffRelocationIterator<'data, 'file, R> {
         let relocations = self.section.coff_relocations(self.file.data).unwrap_or(&[]);
         CoffRelocationIterator {
@@ -405,12 +445,25 @@ impl pe::ImageSectionHeader {
     /// Read the relocations in a COFF file.
     ///
     /// `data` must be the entire file data.
+    ///
+    /// If `IMAGE_SCN_LNK_NRELOC_OVFL` is set and the number of relocations is 0xffff,
+    /// then the number of relocations is read from the first relocation, and
+    /// the first relocation is not included in the returned slice.
     pub fn coff_relocations<'data, R: ReadRef<'data>>(
         &self,
         data: R,
     ) -> read::Result<&'data [pe::ImageRelocation]> {
-        let pointer = self.pointer_to_relocations.get(LE).into();
-        let number = self.number_of_relocations.get(LE).into();
+        let mut pointer = self.pointer_to_relocations.get(LE).into();
+        let mut number: usize = self.number_of_relocations.get(LE).into();
+        if number == 0xffff && self.characteristics.get(LE) & pe::IMAGE_SCN_LNK_NRELOC_OVFL != 0 {
+            let first = data
+                .read_at::<pe::ImageRelocation>(pointer)
+                .read_error("Invalid COFF relocation offset or number")?;
+            number = (first.virtual_address.get(LE) as usize)
+                .checked_sub(1)
+                .read_error("Invalid COFF relocation number")?;
+            pointer += mem::size_of::<pe::ImageRelocation>() as u64;
+        }
         data.read_slice_at(pointer, number)
             .read_error("Invalid COFF relocation offset or number")
     }
@@ -197,7 +197,10 @@ impl pe::ImageSymbol {
         let section = sections.section(section_number)?;
         let virtual_address = u64::from(section.virtual_address.get(LE));
         let value = u64::from(self.value.get(LE));
-        Ok(image_base + virtual_address + value)
+        image_base
+            .checked_add(virtual_address)
+            .and_then(|address| address.checked_add(value))
+            .read_error("Invalid COFF symbol address")
     }
 
     /// Return true if the symbol is a definition of a function or data object.
@@ -231,6 +234,7 @@ impl pe::ImageSymbol {
         self.number_of_aux_symbols > 0
             && self.storage_class == pe::IMAGE_SYM_CLASS_STATIC
             && self.value.get(LE) == 0
+            && self.derived_type() != pe::IMAGE_SYM_DTYPE_FUNCTION
     }
 }
 
@@ -306,14 +310,18 @@ impl<'data, 'file> ObjectSymbol<'data> for CoffSymbol<'data, 'file> {
         self.index
     }
 
-    fn name(&self) -> read::Result<&'data str> {
-        let name = if self.symbol.has_aux_file_name() {
+    fn name_bytes(&self) -> read::Result<&'data [u8]> {
+        if self.symbol.has_aux_file_name() {
             self.file
                 .symbols
-                .aux_file_name(self.index.0, self.symbol.number_of_aux_symbols)?
+                .aux_file_name(self.index.0, self.symbol.number_of_aux_symbols)
         } else {
-            self.symbol.name(self.file.symbols.strings())?
-        };
+            self.symbol.name(self.file.symbols.strings())
+        }
+    }
+
+    fn name(&self) -> read::Result<&'data str> {
+        let name = self.name_bytes()?;
         str::from_utf8(name)
             .ok()
             .read_error("Non UTF-8 COFF symbol name")
@@ -338,6 +346,14 @@ impl<'data, 'file> ObjectSymbol<'data> for CoffSymbol<'data, 'file> {
             .unwrap_or(0)
     }
 
+    fn relative_address(&self) -> u64 {
+        let address = self.address();
+        if address == 0 {
+            return 0;
+        }
+        address.wrapping_sub(self.file.image_base)
+    }
+
     fn size(&self) -> u64 {
         match self.symbol.storage_class {
             pe::IMAGE_SYM_CLASS_STATIC => {
@@ -381,7 +397,7 @@ impl<'data, 'file> ObjectSymbol<'data> for CoffSymbol<'data, 'file> {
         };
         match self.symbol.storage_class {
             pe::IMAGE_SYM_CLASS_STATIC => {
-                if self.symbol.value.get(LE) == 0 && self.symbol.number_of_aux_symbols > 0 {
+                if self.symbol.has_aux_section() {
                     SymbolKind::Section
                 } else {
                     derived_kind
@@ -443,6 +459,16 @@ impl<'data, 'file> ObjectSymbol<'data> for CoffSymbol<'data, 'file> {
         self.symbol.storage_class == pe::IMAGE_SYM_CLASS_WEAK_EXTERNAL
     }
 
+    #[inline]
+    fn is_ifunc(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn is_thumb(&self) -> bool {
+        false
+    }
+
     #[inline]
     fn scope(&self) -> SymbolScope {
         match self.symbol.storage_class {
@@ -43,3 +43,225 @@ impl<Endian: endian::Endian> Dyn for elf::Dyn64<Endian> {
         self.d_val.get(endian)
     }
 }
+
+/// The tag of an entry in the ELF dynamic section.
+///
+/// This only covers the common tags. Use `DynamicTag::Other` for the rest,
+/// and for tags that are specific to an OS or processor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DynamicTag {
+    /// `DT_NULL`: Marks the end of the dynamic array.
+    Null,
+    /// `DT_NEEDED`: The string table offset of the name of a needed library.
+    Needed,
+    /// `DT_PLTRELSZ`: The size in bytes of the PLT relocations.
+    PltRelSz,
+    /// `DT_PLTGOT`: The address of the PLT or GOT.
+    PltGot,
+    /// `DT_HASH`: The address of the symbol hash table.
+    Hash,
+    /// `DT_STRTAB`: The address of the string table.
+    StrTab,
+    /// `DT_SYMTAB`: The address of the symbol table.
+    SymTab,
+    /// `DT_RELA`: The address of the `Rela` relocations.
+    Rela,
+    /// `DT_RELASZ`: The total size of the `Rela` relocations.
+    RelaSz,
+    /// `DT_RELAENT`: The size of one `Rela` relocation.
+    RelaEnt,
+    /// `DT_STRSZ`: The size of the string table.
+    StrSz,
+    /// `DT_SYMENT`: The size of one symbol table entry.
+    SymEnt,
+    /// `DT_INIT`: The address of the init function.
+    Init,
+    /// `DT_FINI`: The address of the termination function.
+    Fini,
+    /// `DT_SONAME`: The string table offset of the name of the shared object.
+    SoName,
+    /// `DT_RPATH`: The string table offset of the library search path (deprecated).
+    RPath,
+    /// `DT_SYMBOLIC`: Start symbol search here.
+    Symbolic,
+    /// `DT_REL`: The address of the `Rel` relocations.
+    Rel,
+    /// `DT_RELSZ`: The total size of the `Rel` relocations.
+    RelSz,
+    /// `DT_RELENT`: The size of one `Rel` relocation.
+    RelEnt,
+    /// `DT_PLTREL`: The type of relocation used for the PLT.
+    PltRel,
+    /// `DT_DEBUG`: For debugging; unspecified.
+    Debug,
+    /// `DT_TEXTREL`: Relocations might modify the text segment.
+    TextRel,
+    /// `DT_JMPREL`: The address of the PLT relocations.
+    JmpRel,
+    /// `DT_BIND_NOW`: Process relocations of the object immediately.
+    BindNow,
+    /// `DT_INIT_ARRAY`: The address of the array of init functions.
+    InitArray,
+    /// `DT_FINI_ARRAY`: The address of the array of termination functions.
+    FiniArray,
+    /// `DT_INIT_ARRAYSZ`: The size in bytes of `DT_INIT_ARRAY`.
+    InitArraySz,
+    /// `DT_FINI_ARRAYSZ`: The size in bytes of `DT_FINI_ARRAY`.
+    FiniArraySz,
+    /// `DT_RUNPATH`: The string table offset of the library search path.
+    RunPath,
+    /// `DT_FLAGS`: Flags for the object being loaded.
+    Flags,
+    /// `DT_PREINIT_ARRAY`: The address of the array of preinit functions.
+    PreinitArray,
+    /// `DT_PREINIT_ARRAYSZ`: The size in bytes of `DT_PREINIT_ARRAY`.
+    PreinitArraySz,
+    /// `DT_RELRSZ`: The total size of the RELR relative relocations.
+    RelrSz,
+    /// `DT_RELR`: The address of the RELR relative relocations.
+    Relr,
+    /// `DT_RELRENT`: The size of one RELR relative relocation.
+    RelrEnt,
+    /// `DT_GNU_HASH`: The address of the GNU symbol hash table.
+    GnuHash,
+    /// `DT_VERSYM`: The address of the symbol version table.
+    VerSym,
+    /// `DT_RELACOUNT`: The number of relative `Rela` relocations.
+    RelaCount,
+    /// `DT_RELCOUNT`: The number of relative `Rel` relocations.
+    RelCount,
+    /// `DT_FLAGS_1`: State flags.
+    Flags1,
+    /// `DT_VERDEF`: The address of the version definition table.
+    VerDef,
+    /// `DT_VERDEFNUM`: The number of version definitions.
+    VerDefNum,
+    /// `DT_VERNEED`: The address of the table of needed versions.
+    VerNeed,
+    /// `DT_VERNEEDNUM`: The number of needed versions.
+    VerNeedNum,
+    /// Any other value.
+    Other(u64),
+}
+
+impl DynamicTag {
+    /// Convert a `d_tag` value.
+    pub fn from_raw(tag: u64) -> Self {
+        if tag >> 32 != 0 {
+            return DynamicTag::Other(tag);
+        }
+        match tag as u32 {
+            elf::DT_NULL => DynamicTag::Null,
+            elf::DT_NEEDED => DynamicTag::Needed,
+            elf::DT_PLTRELSZ => DynamicTag::PltRelSz,
+            elf::DT_PLTGOT => DynamicTag::PltGot,
+            elf::DT_HASH => DynamicTag::Hash,
+            elf::DT_STRTAB => DynamicTag::StrTab,
+            elf::DT_SYMTAB => DynamicTag::SymTab,
+            elf::DT_RELA => DynamicTag::Rela,
+            elf::DT_RELASZ => DynamicTag::RelaSz,
+            elf::DT_RELAENT => DynamicTag::RelaEnt,
+            elf::DT_STRSZ => DynamicTag::StrSz,
+            elf::DT_SYMENT => DynamicTag::SymEnt,
+            elf::DT_INIT => DynamicTag::Init,
+            elf::DT_FINI => DynamicTag::Fini,
+            elf::DT_SONAME => DynamicTag::SoName,
+            elf::DT_RPATH => DynamicTag::RPath,
+            elf::DT_SYMBOLIC => DynamicTag::Symbolic,
+            elf::DT_REL => DynamicTag::Rel,
+            elf::DT_RELSZ => DynamicTag::RelSz,
+            elf::DT_RELENT => DynamicTag::RelEnt,
+            elf::DT_PLTREL => DynamicTag::PltRel,
+            elf::DT_DEBUG => DynamicTag::Debug,
+            elf::DT_TEXTREL => DynamicTag::TextRel,
+            elf::DT_JMPREL => DynamicTag::JmpRel,
+            elf::DT_BIND_NOW => DynamicTag::BindNow,
+            elf::DT_INIT_ARRAY => DynamicTag::InitArray,
+            elf::DT_FINI_ARRAY => DynamicTag::FiniArray,
+            elf::DT_INIT_ARRAYSZ => DynamicTag::InitArraySz,
+            elf::DT_FINI_ARRAYSZ => DynamicTag::FiniArraySz,
+            elf::DT_RUNPATH => DynamicTag::RunPath,
+            elf::DT_FLAGS => DynamicTag::Flags,
+            elf::DT_PREINIT_ARRAY => DynamicTag::PreinitArray,
+            elf::DT_PREINIT_ARRAYSZ => DynamicTag::PreinitArraySz,
+            elf::DT_RELRSZ => DynamicTag::RelrSz,
+            elf::DT_RELR => DynamicTag::Relr,
+            elf::DT_RELRENT => DynamicTag::RelrEnt,
+            elf::DT_GNU_HASH => DynamicTag::GnuHash,
+            elf::DT_VERSYM => DynamicTag::VerSym,
+            elf::DT_RELACOUNT => DynamicTag::RelaCount,
+            elf::DT_RELCOUNT => DynamicTag::RelCount,
+            elf::DT_FLAGS_1 => DynamicTag::Flags1,
+            elf::DT_VERDEF => DynamicTag::VerDef,
+            elf::DT_VERDEFNUM => DynamicTag::VerDefNum,
+            elf::DT_VERNEED => DynamicTag::VerNeed,
+            elf::DT_VERNEEDNUM => DynamicTag::VerNeedNum,
+            _ => DynamicTag::Other(tag),
+        }
+    }
+
+    /// Return the `d_tag` value.
+    pub fn raw(self) -> u64 {
+        let tag = match self {
+            DynamicTag::Null => elf::DT_NULL,
+            DynamicTag::Needed => elf::DT_NEEDED,
+            DynamicTag::PltRelSz => elf::DT_PLTRELSZ,
+            DynamicTag::PltGot => elf::DT_PLTGOT,
+            DynamicTag::Hash => elf::DT_HASH,
+            DynamicTag::StrTab => elf::DT_STRTAB,
+            DynamicTag::SymTab => elf::DT_SYMTAB,
+            DynamicTag::Rela => elf::DT_RELA,
+            DynamicTag::RelaSz => elf::DT_RELASZ,
+            DynamicTag::RelaEnt => elf::DT_RELAENT,
+            DynamicTag::StrSz => elf::DT_STRSZ,
+            DynamicTag::SymEnt => elf::DT_SYMENT,
+            DynamicTag::Init => elf::DT_INIT,
+            DynamicTag::Fini => elf::DT_FINI,
+            DynamicTag::SoName => elf::DT_SONAME,
+            DynamicTag::RPath => elf::DT_RPATH,
+            DynamicTag::Symbolic => elf::DT_SYMBOLIC,
+            DynamicTag::Rel => elf::DT_REL,
+            DynamicTag::RelSz => elf::DT_RELSZ,
+            DynamicTag::RelEnt => elf::DT_RELENT,
+            DynamicTag::PltRel => elf::DT_PLTREL,
+            DynamicTag::Debug => elf::DT_DEBUG,
+            DynamicTag::TextRel => elf::DT_TEXTREL,
+            DynamicTag::JmpRel => elf::DT_JMPREL,
+            DynamicTag::BindNow => elf::DT_BIND_NOW,
+            DynamicTag::InitArray => elf::DT_INIT_ARRAY,
+            DynamicTag::FiniArray => elf::DT_FINI_ARRAY,
+            DynamicTag::InitArraySz => elf::DT_INIT_ARRAYSZ,
+            DynamicTag::FiniArraySz => elf::DT_FINI_ARRAYSZ,
+            DynamicTag::RunPath => elf::DT_RUNPATH,
+            DynamicTag::Flags => elf::DT_FLAGS,
+            DynamicTag::PreinitArray => elf::DT_PREINIT_ARRAY,
+            DynamicTag::PreinitArraySz => elf::DT_PREINIT_ARRAYSZ,
+            DynamicTag::RelrSz => elf::DT_RELRSZ,
+            DynamicTag::Relr => elf::DT_RELR,
+            DynamicTag::RelrEnt => elf::DT_RELRENT,
+            DynamicTag::GnuHash => elf::DT_GNU_HASH,
+            DynamicTag::VerSym => elf::DT_VERSYM,
+            DynamicTag::RelaCount => elf::DT_RELACOUNT,
+            DynamicTag::RelCount => elf::DT_RELCOUNT,
+            DynamicTag::Flags1 => elf::DT_FLAGS_1,
+            DynamicTag::VerDef => elf::DT_VERDEF,
+            DynamicTag::VerDefNum => elf::DT_VERDEFNUM,
+            DynamicTag::VerNeed => elf::DT_VERNEED,
+            DynamicTag::VerNeedNum => elf::DT_VERNEEDNUM,
+            DynamicTag::Other(tag) => return tag,
+        };
+        tag.into()
+    }
+}
+
+/// An entry in the ELF dynamic section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynamicEntry {
+    /// The tag of the entry.
+    pub tag: DynamicTag,
+    /// The value of the entry.
+    ///
+    /// This is either an integer value, an address, or a string table offset,
+    /// depending on the tag.
+    pub value: u64,
+}
@@ -1,19 +1,24 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::convert::TryInto;
 use core::fmt::Debug;
 use core::{mem, str};
 
+use crate::read::util::{AddressIndex, LazyBox};
 use crate::read::{
-    self, util, Architecture, Error, Export, FileFlags, Import, Object, ReadError, ReadRef,
-    SectionIndex, StringTable, SymbolIndex,
+    self, util, AddressMap, Architecture, CompressedData, CompressionFormat, Error, Export,
+    ExportedSymbol, FileFlags, Import, Object, ObjectSection, ReadError, ReadRef, SectionIndex,
+    StringTable, SymbolIndex,
 };
 use crate::{elf, endian, ByteString, Bytes, Endian, Endianness, Pod, U32};
 
 use super::{
-    CompressionHeader, Dyn, ElfComdat, ElfComdatIterator, ElfDynamicRelocationIterator, ElfSection,
-    ElfSectionIterator, ElfSegment, ElfSegmentIterator, ElfSymbol, ElfSymbolIterator,
-    ElfSymbolTable, NoteHeader, ProgramHeader, Rel, Rela, RelocationSections, SectionHeader,
-    SectionTable, Sym, SymbolTable,
+    CompressionHeader, Dyn, DynamicEntry, DynamicTag, ElfComdat, ElfComdatIterator,
+    ElfDynamicRelocationIterator, ElfSection, ElfSectionIterator, ElfSegment, ElfSegmentIterator,
+    ElfSymbol, ElfSymbolIterator, ElfSymbolTable, GnuProperty, Note, NoteHeader, NoteIterator,
+    ProgramHeader, Rel, Rela, RelocationSections, RelrIterator, SectionHeader, SectionTable, Sym,
+    SymbolTable,
 };
 
 /// A 32-bit ELF object file.
@@ -23,6 +28,122 @@ pub type ElfFile32<'data, Endian = Endianness, R = &'data [u8]> =
 pub type ElfFile64<'data, Endian = Endianness, R = &'data [u8]> =
     ElfFile<'data, elf::FileHeader64<Endian>, R>;
 
+/// The OS or ABI of an ELF file, from the `EI_OSABI` identification byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ElfOsAbi {
+    /// UNIX System V ABI.
+    ///
+    /// This is also used by many files that are not specific to an OS.
+    SystemV,
+    /// HP-UX.
+    HpUx,
+    /// NetBSD.
+    NetBsd,
+    /// GNU/Linux.
+    Gnu,
+    /// Sun Solaris.
+    Solaris,
+    /// IBM AIX.
+    Aix,
+    /// SGI Irix.
+    Irix,
+    /// FreeBSD.
+    FreeBsd,
+    /// Compaq TRU64 UNIX.
+    Tru64,
+    /// Novell Modesto.
+    Modesto,
+    /// OpenBSD.
+    OpenBsd,
+    /// ARM EABI.
+    ArmAeabi,
+    /// ARM.
+    Arm,
+    /// Standalone (embedded) application.
+    Standalone,
+    /// Any other value.
+    Other(u8),
+}
+
+impl ElfOsAbi {
+    /// Convert an `EI_OSABI` value.
+    pub fn from_raw(os_abi: u8) -> Self {
+        match os_abi {
+            elf::ELFOSABI_SYSV => ElfOsAbi::SystemV,
+            elf::ELFOSABI_HPUX => ElfOsAbi::HpUx,
+            elf::ELFOSABI_NETBSD => ElfOsAbi::NetBsd,
+            elf::ELFOSABI_GNU => ElfOsAbi::Gnu,
+            elf::ELFOSABI_SOLARIS => ElfOsAbi::Solaris,
+            elf::ELFOSABI_AIX => ElfOsAbi::Aix,
+            elf::ELFOSABI_IRIX => ElfOsAbi::Irix,
+            elf::ELFOSABI_FREEBSD => ElfOsAbi::FreeBsd,
+            elf::ELFOSABI_TRU64 => ElfOsAbi::Tru64,
+            elf::ELFOSABI_MODESTO => ElfOsAbi::Modesto,
+            elf::ELFOSABI_OPENBSD => ElfOsAbi::OpenBsd,
+            elf::ELFOSABI_ARM_AEABI => ElfOsAbi::ArmAeabi,
+            elf::ELFOSABI_ARM => ElfOsAbi::Arm,
+            elf::ELFOSABI_STANDALONE => ElfOsAbi::Standalone,
+            other => ElfOsAbi::Other(other),
+        }
+    }
+
+    /// Return the `EI_OSABI` value.
+    pub fn raw(self) -> u8 {
+        match self {
+            ElfOsAbi::SystemV => elf::ELFOSABI_SYSV,
+            ElfOsAbi::HpUx => elf::ELFOSABI_HPUX,
+            ElfOsAbi::NetBsd => elf::ELFOSABI_NETBSD,
+            ElfOsAbi::Gnu => elf::ELFOSABI_GNU,
+            ElfOsAbi::Solaris => elf::ELFOSABI_SOLARIS,
+            ElfOsAbi::Aix => elf::ELFOSABI_AIX,
+            ElfOsAbi::Irix => elf::ELFOSABI_IRIX,
+            ElfOsAbi::FreeBsd => elf::ELFOSABI_FREEBSD,
+            ElfOsAbi::Tru64 => elf::ELFOSABI_TRU64,
+            ElfOsAbi::Modesto => elf::ELFOSABI_MODESTO,
+            ElfOsAbi::OpenBsd => elf::ELFOSABI_OPENBSD,
+            ElfOsAbi::ArmAeabi => elf::ELFOSABI_ARM_AEABI,
+            ElfOsAbi::Arm => elf::ELFOSABI_ARM,
+            ElfOsAbi::Standalone => elf::ELFOSABI_STANDALONE,
+            ElfOsAbi::Other(other) => other,
+        }
+    }
+}
+
+/// The ARM EABI information from the `e_flags` field of an ELF file header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArmEabi {
+    /// The EABI version, or 0 if the file does not conform to a known EABI version.
+    pub version: u8,
+    /// The floating point calling convention.
+    pub float_abi: ArmFloatAbi,
+}
+
+/// The floating point calling convention of an ARM ELF file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ArmFloatAbi {
+    /// The calling convention is not specified.
+    ///
+    /// This is always the case for EABI versions before 5.
+    Unknown,
+    /// Floating point arguments are passed in integer registers (`EF_ARM_ABI_FLOAT_SOFT`).
+    Soft,
+    /// Floating point arguments are passed in VFP registers (`EF_ARM_ABI_FLOAT_HARD`).
+    Hard,
+}
+
+/// The symbol versions that are required from a needed library.
+///
+/// Returned by `ElfFile::version_requirements`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionNeed<'data> {
+    /// The file name of the library, such as `libc.so.6`.
+    pub library: &'data [u8],
+    /// The names of the required versions, such as `GLIBC_2.34`.
+    pub versions: Vec<&'data [u8]>,
+}
+
 /// A partially parsed ELF file.
 ///
 /// Most of the functionality of this type is provided by the `Object` trait implementation.
@@ -40,6 +161,7 @@ where
     pub(super) relocations: RelocationSections,
     pub(super) symbols: SymbolTable<'data, Elf>,
     pub(super) dynamic_symbols: SymbolTable<'data, Elf>,
+    pub(super) address_index: LazyBox<AddressIndex>,
 }
 
 impl<'data, Elf, R> ElfFile<'data, Elf, R>
@@ -68,6 +190,7 @@ where
             relocations,
             symbols,
             dynamic_symbols,
+            address_index: LazyBox::new(),
         })
     }
 
@@ -91,6 +214,552 @@ where
         self.segments
     }
 
+    /// Returns the OS or ABI that the file is for, from the `EI_OSABI` identification byte.
+    pub fn os_abi(&self) -> ElfOsAbi {
+        ElfOsAbi::from_raw(self.header.e_ident().os_abi)
+    }
+
+    /// Returns the ABI version from the `EI_ABIVERSION` identification byte.
+    ///
+    /// The meaning of this value depends on `ElfFile::os_abi`.
+    pub fn abi_version(&self) -> u8 {
+        self.header.e_ident().abi_version
+    }
+
+    /// Returns the ARM EABI version and floating point calling convention from `e_flags`.
+    ///
+    /// Returns `None` if the file is not for ARM.
+    pub fn arm_eabi(&self) -> Option<ArmEabi> {
+        if self.header.e_machine(self.endian) != elf::EM_ARM {
+            return None;
+        }
+        let e_flags = self.header.e_flags(self.endian);
+        let version = ((e_flags & elf::EF_ARM_EABIMASK) >> 24) as u8;
+        let float_abi = if version < 5 {
+            // These bits had other meanings in earlier versions.
+            ArmFloatAbi::Unknown
+        } else if e_flags & elf::EF_ARM_ABI_FLOAT_HARD != 0 {
+            ArmFloatAbi::Hard
+        } else if e_flags & elf::EF_ARM_ABI_FLOAT_SOFT != 0 {
+            ArmFloatAbi::Soft
+        } else {
+            ArmFloatAbi::Unknown
+        };
+        Some(ArmEabi { version, float_abi })
+    }
+
+    /// Return the notes in the `PT_NOTE` segments.
+    ///
+    /// For core files, these include the `NT_PRSTATUS`, `NT_PRPSINFO` and `NT_FILE`
+    /// notes. Use `Note::file_mappings` to parse `NT_FILE` notes.
+    ///
+    /// `NT_PRSTATUS` and `NT_PRPSINFO` notes are not decoded, because their layout
+    /// depends on the OS and architecture. Use `Note::desc` to access their raw contents.
+    pub fn note_segments(&self) -> read::Result<Vec<Note<'data, Elf>>> {
+        let mut notes = Vec::new();
+        for segment in self.segments {
+            if let Some(mut iter) = segment.notes(self.endian, self.data)? {
+                while let Some(note) = iter.next()? {
+                    notes.push(note);
+                }
+            }
+        }
+        Ok(notes)
+    }
+
+    /// Returns the `.eh_frame_hdr` section, if present.
+    ///
+    /// The `.eh_frame` section can be found using `Object::section_by_name`.
+    pub fn eh_frame_hdr<'file>(&'file self) -> Option<ElfSection<'data, 'file, Elf, R>> {
+        self.raw_section_by_name(".eh_frame_hdr")
+    }
+
+    /// Returns the `PT_GNU_EH_FRAME` segment, if present.
+    ///
+    /// This segment contains the `.eh_frame_hdr` data. Its address is the base address
+    /// used for `DW_EH_PE_datarel` pointers in the binary search table.
+    /// This is available even if the file has no section headers.
+    pub fn eh_frame_hdr_segment<'file>(&'file self) -> Option<ElfSegment<'data, 'file, Elf, R>> {
+        self.segments
+            .iter()
+            .find(|segment| segment.p_type(self.endian) == elf::PT_GNU_EH_FRAME)
+            .map(|segment| ElfSegment {
+                file: self,
+                segment,
+            })
+    }
+
+    /// Return the properties in the `NT_GNU_PROPERTY_TYPE_0` notes.
+    ///
+    /// The notes are read from the `SHT_NOTE` sections if there are section headers,
+    /// otherwise from the `PT_NOTE` segments.
+    pub fn gnu_properties(&self) -> read::Result<Vec<GnuProperty<'data>>> {
+        let endian = self.endian;
+        let mut properties = Vec::new();
+        let mut add_notes = |notes: Option<NoteIterator<'data, Elf>>| -> read::Result<()> {
+            if let Some(mut notes) = notes {
+                while let Some(note) = notes.next()? {
+                    if let Some(mut iter) = note.gnu_properties(endian) {
+                        while let Some(property) = iter.next()? {
+                            properties.push(property);
+                        }
+                    }
+                }
+            }
+            Ok(())
+        };
+        if !self.sections.is_empty() {
+            for section in self.sections.iter() {
+                add_notes(section.notes(endian, self.data)?)?;
+            }
+        } else {
+            for segment in self.segments {
+                add_notes(segment.notes(endian, self.data)?)?;
+            }
+        }
+        Ok(properties)
+    }
+
+    /// Return the minimum Linux kernel version that is required by the file.
+    ///
+    /// This is parsed from the `NT_GNU_ABI_TAG` note, which is normally stored in
+    /// the `.note.ABI-tag` section. The version is returned as the major, minor and
+    /// subminor version numbers.
+    ///
+    /// Returns `Ok(None)` if the file has no `NT_GNU_ABI_TAG` note, or if the note
+    /// is for an operating system other than Linux.
+    pub fn min_kernel_version(&self) -> read::Result<Option<(u32, u32, u32)>> {
+        let endian = self.endian;
+        let find = |notes: Option<NoteIterator<'data, Elf>>| -> read::Result<Option<&'data [u8]>> {
+            if let Some(mut notes) = notes {
+                while let Some(note) = notes.next()? {
+                    if note.name() == elf::ELF_NOTE_GNU
+                        && note.n_type(endian) == elf::NT_GNU_ABI_TAG
+                    {
+                        return Ok(Some(note.desc()));
+                    }
+                }
+            }
+            Ok(None)
+        };
+        let mut desc = None;
+        // Use section headers if present, otherwise use program headers.
+        if !self.sections.is_empty() {
+            for section in self.sections.iter() {
+                desc = find(section.notes(endian, self.data)?)?;
+                if desc.is_some() {
+                    break;
+                }
+            }
+        } else {
+            for segment in self.segments {
+                desc = find(segment.notes(endian, self.data)?)?;
+                if desc.is_some() {
+                    break;
+                }
+            }
+        }
+        let desc = match desc {
+            Some(desc) => desc,
+            None => return Ok(None),
+        };
+        let words = Bytes(desc)
+            .read_slice_at::<U32<Elf::Endian>>(0, 4)
+            .read_error("Invalid ELF ABI tag note size")?;
+        if words[0].get(endian) != elf::ELF_NOTE_OS_LINUX {
+            return Ok(None);
+        }
+        Ok(Some((
+            words[1].get(endian),
+            words[2].get(endian),
+            words[3].get(endian),
+        )))
+    }
+
+    /// Return true if the file is marked as supporting both Intel CET features:
+    /// indirect branch tracking (IBT) and shadow stacks (SHSTK).
+    ///
+    /// This uses the `GNU_PROPERTY_X86_FEATURE_1_AND` property.
+    pub fn supports_cet(&self) -> bool {
+        let features = elf::GNU_PROPERTY_X86_FEATURE_1_IBT | elf::GNU_PROPERTY_X86_FEATURE_1_SHSTK;
+        self.gnu_feature_1(elf::GNU_PROPERTY_X86_FEATURE_1_AND) & features == features
+    }
+
+    /// Return true if the file is marked as supporting ARM branch target identification (BTI).
+    ///
+    /// This uses the `GNU_PROPERTY_AARCH64_FEATURE_1_AND` property.
+    pub fn supports_bti(&self) -> bool {
+        self.gnu_feature_1(elf::GNU_PROPERTY_AARCH64_FEATURE_1_AND)
+            & elf::GNU_PROPERTY_AARCH64_FEATURE_1_BTI
+            != 0
+    }
+
+    /// Return true if the file is marked as supporting ARM pointer authentication (PAC).
+    ///
+    /// This uses the `GNU_PROPERTY_AARCH64_FEATURE_1_AND` property.
+    pub fn supports_pac(&self) -> bool {
+        self.gnu_feature_1(elf::GNU_PROPERTY_AARCH64_FEATURE_1_AND)
+            & elf::GNU_PROPERTY_AARCH64_FEATURE_1_PAC
+            != 0
+    }
+
+    /// Return the value of a `FEATURE_1_AND` GNU property, or 0 if it is missing or invalid.
+    ///
+    /// The processor-specific property types overlap, so this also checks the machine.
+    fn gnu_feature_1(&self, pr_type: u32) -> u32 {
+        let machine = self.header.e_machine(self.endian);
+        let valid = match pr_type {
+            elf::GNU_PROPERTY_X86_FEATURE_1_AND => {
+                machine == elf::EM_386 || machine == elf::EM_X86_64
+            }
+            elf::GNU_PROPERTY_AARCH64_FEATURE_1_AND => machine == elf::EM_AARCH64,
+            _ => false,
+        };
+        if !valid {
+            return 0;
+        }
+        self.gnu_properties()
+            .ok()
+            .and_then(|properties| {
+                properties
+                    .into_iter()
+                    .find(|property| property.pr_type() == pr_type)
+            })
+            .and_then(|property| property.data_u32(self.endian).ok())
+            .unwrap_or(0)
+    }
+
+    /// Return true if the file has been modified by the `prelink` tool.
+    ///
+    /// Prelinking assigns fixed load addresses to shared libraries and applies their
+    /// relocations in advance, so addresses in the file may differ from the addresses
+    /// that were chosen by the linker.
+    ///
+    /// This is detected using the `DT_GNU_PRELINKED` dynamic entry or the
+    /// `.gnu.prelink_undo` section.
+    pub fn is_prelinked(&self) -> bool {
+        self.prelink_timestamp().ok().flatten().is_some()
+            || self.raw_section_by_name(".gnu.prelink_undo").is_some()
+    }
+
+    /// Return the time at which the file was prelinked.
+    ///
+    /// This is the value of the `DT_GNU_PRELINKED` dynamic entry, which is the
+    /// number of seconds since the Unix epoch.
+    ///
+    /// Returns `Ok(None)` if there is no such entry.
+    pub fn prelink_timestamp(&self) -> read::Result<Option<u64>> {
+        let endian = self.endian;
+        for entry in self.dynamic()? {
+            let tag = entry.d_tag(endian).into();
+            if tag == u64::from(elf::DT_NULL) {
+                break;
+            }
+            if tag == u64::from(elf::DT_GNU_PRELINKED) {
+                return Ok(Some(entry.d_val(endian).into()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return the entries of the dynamic section.
+    ///
+    /// The entries are read from the `PT_DYNAMIC` segment, or from the `SHT_DYNAMIC`
+    /// section if there is no such segment. The entries end before the first `DT_NULL`.
+    ///
+    /// Returns an empty vector if there is no dynamic section.
+    pub fn dynamic_entries(&self) -> read::Result<Vec<DynamicEntry>> {
+        let endian = self.endian;
+        Ok(self
+            .dynamic()?
+            .iter()
+            .map(|entry| DynamicEntry {
+                tag: DynamicTag::from_raw(entry.d_tag(endian).into()),
+                value: entry.d_val(endian).into(),
+            })
+            .take_while(|entry| entry.tag != DynamicTag::Null)
+            .collect())
+    }
+
+    /// Return the library search paths in `DT_RPATH` entries.
+    ///
+    /// Each entry is split at ':' into separate paths.
+    ///
+    /// `DT_RPATH` is searched before `LD_LIBRARY_PATH`, and is ignored if there
+    /// are any `DT_RUNPATH` entries.
+    pub fn rpaths(&self) -> read::Result<Vec<&'data [u8]>> {
+        self.dynamic_paths(DynamicTag::RPath)
+    }
+
+    /// Return the library search paths in `DT_RUNPATH` entries.
+    ///
+    /// Each entry is split at ':' into separate paths.
+    ///
+    /// `DT_RUNPATH` is searched after `LD_LIBRARY_PATH`, and only applies when
+    /// searching for the immediate dependencies of this file.
+    pub fn runpaths(&self) -> read::Result<Vec<&'data [u8]>> {
+        self.dynamic_paths(DynamicTag::RunPath)
+    }
+
+    /// Return the symbol versions that are required from each needed library.
+    ///
+    /// These are read from the `Verneed` and `Vernaux` entries in the
+    /// `SHT_GNU_VERNEED` section, which is usually named `.gnu.version_r`.
+    ///
+    /// Returns an empty `Vec` if there is no such section.
+    pub fn version_requirements(&self) -> read::Result<Vec<VersionNeed<'data>>> {
+        let endian = self.endian;
+        let mut needs = Vec::new();
+        let section = match self
+            .sections
+            .iter()
+            .find(|section| section.sh_type(endian) == elf::SHT_GNU_VERNEED)
+        {
+            Some(section) => section,
+            None => return Ok(needs),
+        };
+        let data = section
+            .data(endian, self.data)
+            .read_error("Invalid ELF version requirements section offset or size")
+            .map(Bytes)?;
+        let strtab = self.sections.section(section.sh_link(endian) as usize)?;
+        let strings = strtab
+            .data(endian, self.data)
+            .read_error("Invalid ELF string table data")
+            .map(StringTable::new)?;
+
+        let mut offset = 0usize;
+        for _ in 0..section.sh_info(endian) {
+            let verneed = data
+                .read_at::<elf::Verneed<Elf::Endian>>(offset)
+                .read_error("Invalid ELF Verneed offset")?;
+            let library = strings
+                .get(verneed.vn_file.get(endian))
+                .read_error("Invalid ELF Verneed file offset")?;
+            let mut versions = Vec::new();
+            let mut aux_offset = offset.wrapping_add(verneed.vn_aux.get(endian) as usize);
+            for _ in 0..verneed.vn_cnt.get(endian) {
+                let vernaux = data
+                    .read_at::<elf::Vernaux<Elf::Endian>>(aux_offset)
+                    .read_error("Invalid ELF Vernaux offset")?;
+                let version = strings
+                    .get(vernaux.vna_name.get(endian))
+                    .read_error("Invalid ELF Vernaux name offset")?;
+                versions.push(version);
+                aux_offset = aux_offset.wrapping_add(vernaux.vna_next.get(endian) as usize);
+            }
+            needs.push(VersionNeed { library, versions });
+
+            let next = verneed.vn_next.get(endian) as usize;
+            if next == 0 {
+                break;
+            }
+            offset = offset.wrapping_add(next);
+        }
+        Ok(needs)
+    }
+
+    /// Return the ELF file contained in the `.gnu_debugdata` section.
+    ///
+    /// This section is also known as MiniDebugInfo. It contains an ELF file with
+    /// a symbol table that supplements the dynamic symbols of a stripped file.
+    /// The returned data can be parsed with `ElfFile::parse` to read these symbols.
+    ///
+    /// The ELF file is normally compressed with XZ. Decompressing it requires the
+    /// `xz` feature. Use `compressed_gnu_debugdata` to do your own decompression.
+    ///
+    /// Returns `Ok(None)` if there is no `.gnu_debugdata` section.
+    pub fn gnu_debugdata(&self) -> read::Result<Option<Cow<'data, [u8]>>> {
+        match self.compressed_gnu_debugdata()? {
+            Some(data) => data.decompress().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Return the contents of the `.gnu_debugdata` section without decompressing it.
+    ///
+    /// If the section is compressed with XZ, then the format of the returned data
+    /// is `CompressionFormat::Xz`.
+    ///
+    /// Returns `Ok(None)` if there is no `.gnu_debugdata` section.
+    pub fn compressed_gnu_debugdata(&self) -> read::Result<Option<CompressedData<'data>>> {
+        let section = match self.raw_section_by_name(".gnu_debugdata") {
+            Some(section) => section,
+            None => return Ok(None),
+        };
+        let data = section
+            .section
+            .data(self.endian, self.data)
+            .read_error("Invalid ELF .gnu_debugdata section offset or size")?;
+        let compressed = match xz_uncompressed_size(data) {
+            Some(uncompressed_size) => CompressedData {
+                format: CompressionFormat::Xz,
+                data,
+                uncompressed_size,
+            },
+            None if data.starts_with(&elf::ELFMAG) => CompressedData::none(data),
+            None => CompressedData {
+                format: CompressionFormat::Unknown,
+                data,
+                uncompressed_size: 0,
+            },
+        };
+        Ok(Some(compressed))
+    }
+
+    /// Return the link time warnings from `.gnu.warning.SYMBOL` sections.
+    ///
+    /// The linker prints the warning message when a reference to the symbol is linked.
+    ///
+    /// Returns the symbol name and the message for each section. The message
+    /// does not include any null terminator.
+    pub fn symbol_warnings(&self) -> read::Result<Vec<(&'data [u8], &'data [u8])>> {
+        let endian = self.endian;
+        let mut warnings = Vec::new();
+        for section in self.sections.iter() {
+            let name = self.sections.section_name(endian, section)?;
+            let symbol = match name.get(..13) {
+                Some(b".gnu.warning.") => &name[13..],
+                _ => continue,
+            };
+            let data = section
+                .data(endian, self.data)
+                .read_error("Invalid ELF .gnu.warning section offset or size")?;
+            let message = match memchr::memchr(b'\0', data) {
+                Some(end) => &data[..end],
+                None => data,
+            };
+            warnings.push((symbol, message));
+        }
+        Ok(warnings)
+    }
+
+    fn dynamic_paths(&self, tag: DynamicTag) -> read::Result<Vec<&'data [u8]>> {
+        let entries = self.dynamic_entries()?;
+        let mut paths = Vec::new();
+        if !entries.iter().any(|entry| entry.tag == tag) {
+            return Ok(paths);
+        }
+        let strings = self.dynamic_strings(&entries)?;
+        for entry in entries.iter().filter(|entry| entry.tag == tag) {
+            let value = entry
+                .value
+                .try_into()
+                .ok()
+                .read_error("Invalid ELF dynamic string offset")?;
+            let path = strings
+                .get(value)
+                .read_error("Invalid ELF dynamic string offset")?;
+            paths.extend(path.split(|&b| b == b':'));
+        }
+        Ok(paths)
+    }
+
+    /// Return the string table for the dynamic section.
+    ///
+    /// This uses the section linked from the `SHT_DYNAMIC` section if there is one,
+    /// otherwise uses the `DT_STRTAB` and `DT_STRSZ` entries.
+    fn dynamic_strings(&self, entries: &[DynamicEntry]) -> read::Result<StringTable<'data>> {
+        let endian = self.endian;
+        if let Some(section) = self
+            .sections
+            .iter()
+            .find(|section| section.sh_type(endian) == elf::SHT_DYNAMIC)
+        {
+            let strtab = self.sections.section(section.sh_link(endian) as usize)?;
+            let data = strtab
+                .data(endian, self.data)
+                .read_error("Invalid ELF string table data")?;
+            return Ok(StringTable::new(data));
+        }
+        let value = |tag| {
+            entries
+                .iter()
+                .find(|entry| entry.tag == tag)
+                .map(|entry| entry.value)
+                .read_error("Missing ELF dynamic string table")
+        };
+        let address = value(DynamicTag::StrTab)?;
+        let size = value(DynamicTag::StrSz)?;
+        let data = self
+            .dynamic_data(address, size)
+            .read_error("Invalid ELF dynamic string table")?;
+        Ok(StringTable::new(data))
+    }
+
+    /// Return the RELR relocations given by the `DT_RELR` and `DT_RELRSZ` entries.
+    ///
+    /// This is only used if there is no `SHT_RELR` section, such as when the
+    /// section headers have been stripped.
+    ///
+    /// Returns `Ok(None)` if there is no `DT_RELR` entry.
+    fn dynamic_relr(&self) -> read::Result<Option<RelrIterator<'data, Elf>>> {
+        let endian = self.endian;
+        if self
+            .sections
+            .iter()
+            .any(|section| section.sh_type(endian) == elf::SHT_RELR)
+        {
+            return Ok(None);
+        }
+        let entries = self.dynamic_entries()?;
+        let value = |tag| {
+            entries
+                .iter()
+                .find(|entry| entry.tag == tag)
+                .map(|entry| entry.value)
+        };
+        let address = match value(DynamicTag::Relr) {
+            Some(address) => address,
+            None => return Ok(None),
+        };
+        let size = value(DynamicTag::RelrSz).read_error("Missing ELF DT_RELRSZ")?;
+        let data = self
+            .dynamic_data(address, size)
+            .read_error("Invalid ELF DT_RELR address or size")?;
+        Ok(Some(RelrIterator::new(endian, data)))
+    }
+
+    /// Return the file data for an address range given by a dynamic entry.
+    ///
+    /// The address is mapped to a file offset using the `PT_LOAD` segments.
+    fn dynamic_data(&self, address: u64, size: u64) -> Option<&'data [u8]> {
+        let endian = self.endian;
+        for segment in self.segments {
+            if segment.p_type(endian) != elf::PT_LOAD {
+                continue;
+            }
+            let segment_address: u64 = segment.p_vaddr(endian).into();
+            let offset = match address.checked_sub(segment_address) {
+                Some(offset) if offset < segment.p_filesz(endian).into() => offset,
+                _ => continue,
+            };
+            return segment
+                .p_offset(endian)
+                .into()
+                .checked_add(offset)
+                .and_then(|offset| self.data.read_bytes_at(offset, size).ok());
+        }
+        None
+    }
+
+    /// Return the raw entries of the dynamic section.
+    fn dynamic(&self) -> read::Result<&'data [Elf::Dyn]> {
+        for segment in self.segments {
+            if let Some(dynamic) = segment.dynamic(self.endian, self.data)? {
+                return Ok(dynamic);
+            }
+        }
+        for section in self.sections.iter() {
+            if section.sh_type(self.endian) == elf::SHT_DYNAMIC {
+                return section
+                    .data_as_array(self.endian, self.data)
+                    .read_error("Invalid ELF dynamic section offset or size");
+            }
+        }
+        Ok(&[])
+    }
+
     fn raw_section_by_name<'file>(
         &'file self,
         section_name: &str,
@@ -104,23 +773,75 @@ where
             })
     }
 
-    #[cfg(feature = "compression")]
-    fn zdebug_section_by_name<'file>(
+    /// Return the cached index of the address ranges of the file.
+    ///
+    /// For core files and files without section headers, the ranges are the
+    /// `PT_LOAD` segments instead of the sections.
+    fn address_index(&self) -> &AddressIndex {
+        self.address_index.get_or_init(|| {
+            let endian = self.endian;
+            if self.uses_segment_addresses() {
+                AddressIndex::new(self.segments.iter().enumerate().filter_map(
+                    |(index, segment)| {
+                        if segment.p_type(endian) != elf::PT_LOAD {
+                            return None;
+                        }
+                        let size = segment.data(endian, self.data).ok()?.len() as u64;
+                        Some((segment.p_vaddr(endian).into(), size, index))
+                    },
+                ))
+            } else {
+                AddressIndex::new(self.sections.iter().enumerate().filter_map(
+                    |(index, section)| {
+                        let size = section.data(endian, self.data).ok()?.len() as u64;
+                        Some((section.sh_addr(endian).into(), size, index))
+                    },
+                ))
+            }
+        })
+    }
+
+    /// Return the data of a range in the index returned by `address_index`.
+    fn address_range_data(&self, index: usize) -> Option<&'data [u8]> {
+        let endian = self.endian;
+        if self.uses_segment_addresses() {
+            self.segments.get(index)?.data(endian, self.data).ok()
+        } else {
+            self.sections
+                .section(index)
+                .ok()?
+                .data(endian, self.data)
+                .ok()
+        }
+    }
+
+    fn uses_segment_addresses(&self) -> bool {
+        self.header.e_type(self.endian) == elf::ET_CORE || self.sections.is_empty()
+    }
+
+    fn raw_sections_by_name<'file>(
         &'file self,
         section_name: &str,
-    ) -> Option<ElfSection<'data, 'file, Elf, R>> {
-        if !section_name.starts_with(".debug_") {
-            return None;
-        }
-        self.raw_section_by_name(&format!(".zdebug_{}", &section_name[7..]))
+    ) -> Vec<ElfSection<'data, 'file, Elf, R>> {
+        self.sections
+            .iter()
+            .enumerate()
+            .filter(|(_, section)| {
+                self.sections.section_name(self.endian, section) == Ok(section_name.as_bytes())
+            })
+            .map(|(index, section)| ElfSection {
+                file: self,
+                index: SectionIndex(index),
+                section,
+            })
+            .collect()
     }
 
-    #[cfg(not(feature = "compression"))]
     fn zdebug_section_by_name<'file>(
         &'file self,
-        _section_name: &str,
+        section_name: &str,
     ) -> Option<ElfSection<'data, 'file, Elf, R>> {
-        None
+        self.raw_section_by_name(&zdebug_section_name(section_name)?)
     }
 }
 
@@ -157,10 +878,13 @@ where
             (elf::EM_ARM, _) => Architecture::Arm,
             (elf::EM_AVR, _) => Architecture::Avr,
             (elf::EM_BPF, _) => Architecture::Bpf,
+            (elf::EM_CSKY, _) => Architecture::Csky,
             (elf::EM_386, _) => Architecture::I386,
             (elf::EM_X86_64, false) => Architecture::X86_64_X32,
             (elf::EM_X86_64, true) => Architecture::X86_64,
             (elf::EM_HEXAGON, _) => Architecture::Hexagon,
+            (elf::EM_LOONGARCH, false) => Architecture::LoongArch32,
+            (elf::EM_LOONGARCH, true) => Architecture::LoongArch64,
             (elf::EM_MIPS, false) => Architecture::Mips,
             (elf::EM_MIPS, true) => Architecture::Mips64,
             (elf::EM_MSP430, _) => Architecture::Msp430,
@@ -171,6 +895,8 @@ where
             // This is either s390 or s390x, depending on the ELF class.
             // We only support the 64-bit variant s390x here.
             (elf::EM_S390, true) => Architecture::S390x,
+            (elf::EM_SPARC, false) => Architecture::Sparc,
+            (elf::EM_SPARC32PLUS, false) => Architecture::Sparc,
             (elf::EM_SPARCV9, true) => Architecture::Sparc64,
             _ => Architecture::Unknown,
         }
@@ -186,6 +912,11 @@ where
         self.header.is_class_64()
     }
 
+    #[inline]
+    fn is_relocatable(&self) -> bool {
+        self.header.e_type(self.endian) == elf::ET_REL
+    }
+
     fn segments(&'file self) -> ElfSegmentIterator<'data, 'file, Elf, R> {
         ElfSegmentIterator {
             file: self,
@@ -201,6 +932,17 @@ where
             .or_else(|| self.zdebug_section_by_name(section_name))
     }
 
+    fn sections_by_name(&'file self, section_name: &str) -> Vec<ElfSection<'data, 'file, Elf, R>> {
+        let sections = self.raw_sections_by_name(section_name);
+        if !sections.is_empty() {
+            return sections;
+        }
+        match zdebug_section_name(section_name) {
+            Some(name) => self.raw_sections_by_name(&name),
+            None => Vec::new(),
+        }
+    }
+
     fn section_by_index(
         &'file self,
         index: SectionIndex,
@@ -220,6 +962,33 @@ where
         }
     }
 
+    fn cstr_at(&'file self, address: u64) -> Option<&'data [u8]> {
+        if self.is_relocatable() {
+            return util::sections_cstr_at(self, address);
+        }
+        let (index, offset) = self.address_index().find(address)?;
+        let data = self.address_range_data(index)?;
+        Bytes(data).read_string_at(offset.try_into().ok()?).ok()
+    }
+
+    /// For core files and files without section headers, the map is constructed
+    /// from the `PT_LOAD` segments instead of the sections.
+    fn address_map(&'file self) -> AddressMap<'data> {
+        if self.is_relocatable() {
+            let ranges = self
+                .sections()
+                .filter_map(|section| Some((section.address(), section.data().ok()?)))
+                .collect();
+            return AddressMap::new(ranges);
+        }
+        let ranges = self
+            .address_index()
+            .iter()
+            .filter_map(|(address, index)| Some((address, self.address_range_data(index)?)))
+            .collect();
+        AddressMap::new(ranges)
+    }
+
     fn comdats(&'file self) -> ElfComdatIterator<'data, 'file, Elf, R> {
         ElfComdatIterator {
             file: self,
@@ -234,38 +1003,72 @@ where
         let symbol = self.symbols.symbol(index.0)?;
         Ok(ElfSymbol {
             endian: self.endian,
+            machine: self.header.e_machine(self.endian),
             symbols: &self.symbols,
             index,
             symbol,
+            file: None,
         })
     }
 
     fn symbols(&'file self) -> ElfSymbolIterator<'data, 'file, Elf> {
         ElfSymbolIterator {
             endian: self.endian,
+            machine: self.header.e_machine(self.endian),
             symbols: &self.symbols,
             index: 0,
+            end: self.symbols.len(),
+            file: 0,
         }
     }
 
     fn symbol_table(&'file self) -> Option<ElfSymbolTable<'data, 'file, Elf>> {
         Some(ElfSymbolTable {
             endian: self.endian,
+            machine: self.header.e_machine(self.endian),
             symbols: &self.symbols,
         })
     }
 
+    fn local_symbols(&'file self) -> Vec<ElfSymbol<'data, 'file, Elf>> {
+        ElfSymbolIterator {
+            endian: self.endian,
+            machine: self.header.e_machine(self.endian),
+            symbols: &self.symbols,
+            index: 0,
+            end: self.symbols.first_global(),
+            file: 0,
+        }
+        .collect()
+    }
+
+    fn global_symbols(&'file self) -> Vec<ElfSymbol<'data, 'file, Elf>> {
+        ElfSymbolIterator {
+            endian: self.endian,
+            machine: self.header.e_machine(self.endian),
+            symbols: &self.symbols,
+            index: self.symbols.first_global(),
+            end: self.symbols.len(),
+            file: 0,
+        }
+        .collect()
+    }
+
     fn dynamic_symbols(&'file self) -> ElfSymbolIterator<'data, 'file, Elf> {
         ElfSymbolIterator {
             endian: self.endian,
+            machine: self.header.e_machine(self.endian),
             symbols: &self.dynamic_symbols,
             index: 0,
+            end: self.dynamic_symbols.len(),
+            file: 0,
         }
     }
 
     fn dynamic_symbol_table(&'file self) -> Option<ElfSymbolTable<'data, 'file, Elf>> {
         Some(ElfSymbolTable {
             endian: self.endian,
+            machine: self.header.e_machine(self.endian),
             symbols: &self.dynamic_symbols,
         })
     }
@@ -277,9 +1080,22 @@ where
             section_index: 1,
             file: self,
             relocations: None,
+            relr: self.dynamic_relr().ok().flatten(),
         })
     }
 
+    fn relocation_sections(&'file self) -> Vec<(SectionIndex, SectionIndex)> {
+        let mut sections = Vec::new();
+        for target in 0..self.sections.len() {
+            let mut index = target;
+            while let Some(next) = self.relocations.get(index) {
+                sections.push((SectionIndex(next), SectionIndex(target)));
+                index = next;
+            }
+        }
+        sections
+    }
+
     /// Get the imported symbols.
     fn imports(&self) -> read::Result<Vec<Import<'data>>> {
         let mut imports = Vec::new();
@@ -314,6 +1130,31 @@ where
         Ok(exports)
     }
 
+    fn exported_symbols(&self) -> read::Result<Vec<ExportedSymbol<'data>>> {
+        let mut exports = Vec::new();
+        for symbol in self.dynamic_symbols.iter() {
+            if !symbol.is_definition(self.endian) {
+                continue;
+            }
+            let is_weak = match symbol.st_bind() {
+                elf::STB_GLOBAL | elf::STB_GNU_UNIQUE => false,
+                elf::STB_WEAK => true,
+                _ => continue,
+            };
+            match symbol.st_visibility() {
+                elf::STV_HIDDEN | elf::STV_INTERNAL => continue,
+                _ => {}
+            }
+            let name = symbol.name(self.endian, self.dynamic_symbols.strings())?;
+            exports.push(ExportedSymbol {
+                name: Cow::Borrowed(name),
+                address: symbol.st_value(self.endian).into(),
+                is_weak,
+            });
+        }
+        Ok(exports)
+    }
+
     fn has_debug_symbols(&self) -> bool {
         for section in self.sections.iter() {
             if let Ok(name) = self.sections.section_name(self.endian, section) {
@@ -377,6 +1218,20 @@ where
         Ok(Some((filename, crc)))
     }
 
+    fn compiler_info(&self) -> Vec<String> {
+        let data = self
+            .raw_section_by_name(".comment")
+            .and_then(|section| section.section.data(self.endian, self.data).ok());
+        match data {
+            Some(data) => data
+                .split(|&byte| byte == 0)
+                .filter(|comment| !comment.is_empty())
+                .map(|comment| String::from_utf8_lossy(comment).into_owned())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     fn gnu_debugaltlink(&self) -> read::Result<Option<(&'data [u8], &'data [u8])>> {
         let section = match self.raw_section_by_name(".gnu_debugaltlink") {
             Some(section) => section,
@@ -398,6 +1253,15 @@ where
         0
     }
 
+    fn preferred_load_address(&self) -> u64 {
+        self.segments
+            .iter()
+            .filter(|segment| segment.p_type(self.endian) == elf::PT_LOAD)
+            .map(|segment| segment.p_vaddr(self.endian).into())
+            .min()
+            .unwrap_or(0)
+    }
+
     fn entry(&self) -> u64 {
         self.header.e_entry(self.endian).into()
     }
@@ -675,6 +1539,66 @@ pub trait FileHeader: Debug + Pod {
     }
 }
 
+/// Return the name of the GNU compressed section that may be used instead of
+/// the given DWARF section.
+///
+/// For example, `.zdebug_info` may be used instead of `.debug_info`.
+/// Returns `None` if the `compression` feature is disabled.
+#[cfg(feature = "compression")]
+fn zdebug_section_name(section_name: &str) -> Option<String> {
+    if !section_name.starts_with(".debug_") {
+        return None;
+    }
+    Some(format!(".zdebug_{}", &section_name[7..]))
+}
+
+#[cfg(not(feature = "compression"))]
+fn zdebug_section_name(_section_name: &str) -> Option<String> {
+    None
+}
+
+/// Return the uncompressed size of XZ compressed data.
+///
+/// This reads the index at the end of the stream. Only a single stream is supported.
+///
+/// Returns `None` if the data is not valid XZ data.
+fn xz_uncompressed_size(data: &[u8]) -> Option<u64> {
+    const HEADER_MAGIC: [u8; 6] = [0xfd, b'7', b'z', b'X', b'Z', 0];
+    const FOOTER_MAGIC: [u8; 2] = [b'Y', b'Z'];
+    if !data.starts_with(&HEADER_MAGIC) {
+        return None;
+    }
+    // Skip any stream padding.
+    let mut end = data.len();
+    while end >= 4 && data[end - 4..end] == [0; 4] {
+        end -= 4;
+    }
+    let data = Bytes(&data[..end]);
+
+    // The stream footer contains the size of the index.
+    let footer_offset = end.checked_sub(12)?;
+    let footer = data.read_bytes_at(footer_offset, 12).ok()?.0;
+    if footer[10..] != FOOTER_MAGIC {
+        return None;
+    }
+    let backward_size = u32::from_le_bytes(footer[4..8].try_into().ok()?);
+    let index_size = (backward_size as usize).checked_add(1)?.checked_mul(4)?;
+    let index_offset = footer_offset.checked_sub(index_size)?;
+
+    let mut index = data.read_bytes_at(index_offset, index_size).ok()?;
+    if *index.read::<u8>().ok()? != 0 {
+        return None;
+    }
+    let count = index.read_uleb128().ok()?;
+    let mut size = 0u64;
+    for _ in 0..count {
+        // The unpadded size of the block.
+        index.read_uleb128().ok()?;
+        size = size.checked_add(index.read_uleb128().ok()?)?;
+    }
+    Some(size)
+}
+
 impl<Endian: endian::Endian> FileHeader for elf::FileHeader32<Endian> {
     type Word = u32;
     type Sword = i32;
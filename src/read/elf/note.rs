@@ -1,8 +1,9 @@
+use alloc::vec::Vec;
 use core::fmt::Debug;
 use core::mem;
 
 use crate::elf;
-use crate::endian;
+use crate::endian::{self, U32Bytes, U64Bytes};
 use crate::pod::{Bytes, Pod};
 use crate::read::util;
 use crate::read::{self, Error, ReadError};
@@ -134,6 +135,156 @@ impl<'data, Elf: FileHeader> Note<'data, Elf> {
     pub fn desc(&self) -> &'data [u8] {
         self.desc
     }
+
+    /// Return an iterator for the properties of a `NT_GNU_PROPERTY_TYPE_0` note.
+    ///
+    /// Returns `None` if the note is not a GNU property note.
+    pub fn gnu_properties(
+        &self,
+        endian: Elf::Endian,
+    ) -> Option<GnuPropertyIterator<'data, Elf::Endian>> {
+        if self.name() != elf::ELF_NOTE_GNU || self.n_type(endian) != elf::NT_GNU_PROPERTY_TYPE_0 {
+            return None;
+        }
+        // Use the ELF class instead of the section alignment.
+        // This matches what other parsers do.
+        let align = mem::size_of::<Elf::Word>();
+        Some(GnuPropertyIterator {
+            endian,
+            align,
+            data: Bytes(self.desc),
+        })
+    }
+
+    /// Parse the file mappings in a `NT_FILE` note of a core file.
+    ///
+    /// Each mapping gives the address range of a file that was mapped into
+    /// the memory of the process.
+    ///
+    /// Returns `Ok(None)` if the note is not a `NT_FILE` note.
+    pub fn file_mappings(
+        &self,
+        endian: Elf::Endian,
+    ) -> read::Result<Option<Vec<CoreFileMapping<'data>>>> {
+        if self.name() != elf::ELF_NOTE_CORE || self.n_type(endian) != elf::NT_FILE {
+            return Ok(None);
+        }
+        let mut data = Bytes(self.desc);
+        let mut read_word = || -> read::Result<u64> {
+            if mem::size_of::<Elf::Word>() == 8 {
+                data.read::<U64Bytes<Elf::Endian>>()
+                    .map(|word| word.get(endian))
+            } else {
+                data.read::<U32Bytes<Elf::Endian>>()
+                    .map(|word| word.get(endian).into())
+            }
+            .read_error("Invalid ELF NT_FILE note size")
+        };
+        let count = read_word()?;
+        let page_size = read_word()?;
+        let mut mappings = Vec::new();
+        for _ in 0..count {
+            let start = read_word()?;
+            let end = read_word()?;
+            let page_offset = read_word()?;
+            mappings.push(CoreFileMapping {
+                start,
+                end,
+                file_offset: page_offset.wrapping_mul(page_size),
+                path: &[],
+            });
+        }
+        for mapping in &mut mappings {
+            mapping.path = data
+                .read_string()
+                .read_error("Invalid ELF NT_FILE note path")?;
+        }
+        Ok(Some(mappings))
+    }
+}
+
+/// A file mapping in a `NT_FILE` note of a core file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoreFileMapping<'data> {
+    /// The start address of the mapping.
+    pub start: u64,
+    /// The end address of the mapping.
+    pub end: u64,
+    /// The offset within the file of the start of the mapping.
+    pub file_offset: u64,
+    /// The path of the mapped file.
+    pub path: &'data [u8],
+}
+
+/// An iterator for the properties in a `NT_GNU_PROPERTY_TYPE_0` note.
+#[derive(Debug)]
+pub struct GnuPropertyIterator<'data, Endian: endian::Endian> {
+    endian: Endian,
+    align: usize,
+    data: Bytes<'data>,
+}
+
+impl<'data, Endian: endian::Endian> GnuPropertyIterator<'data, Endian> {
+    /// Returns the next property.
+    pub fn next(&mut self) -> read::Result<Option<GnuProperty<'data>>> {
+        let mut data = self.data;
+        if data.is_empty() {
+            return Ok(None);
+        }
+
+        let pr_type = data
+            .read_at::<U32Bytes<Endian>>(0)
+            .read_error("ELF GNU property is too short")?
+            .get(self.endian);
+        let pr_datasz = data
+            .read_at::<U32Bytes<Endian>>(4)
+            .read_error("ELF GNU property is too short")?
+            .get(self.endian) as usize;
+        let pr_data = data
+            .read_bytes_at(8, pr_datasz)
+            .read_error("Invalid ELF GNU property size")?
+            .0;
+
+        // The next property (if any) must be aligned.
+        let offset = util::align(8 + pr_datasz, self.align);
+        if data.skip(offset).is_err() {
+            data = Bytes(&[]);
+        }
+        self.data = data;
+
+        Ok(Some(GnuProperty { pr_type, pr_data }))
+    }
+}
+
+/// A property in a `NT_GNU_PROPERTY_TYPE_0` note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GnuProperty<'data> {
+    pr_type: u32,
+    pr_data: &'data [u8],
+}
+
+impl<'data> GnuProperty<'data> {
+    /// Return the property type.
+    ///
+    /// This is one of the `GNU_PROPERTY_*` constants.
+    pub fn pr_type(&self) -> u32 {
+        self.pr_type
+    }
+
+    /// Return the property data.
+    pub fn pr_data(&self) -> &'data [u8] {
+        self.pr_data
+    }
+
+    /// Parse the property data as an unsigned 32-bit integer.
+    ///
+    /// This is used for the `GNU_PROPERTY_*_FEATURE_1_AND` properties.
+    pub fn data_u32<E: endian::Endian>(&self, endian: E) -> read::Result<u32> {
+        Bytes(self.pr_data)
+            .read_at::<U32Bytes<E>>(0)
+            .read_error("Invalid ELF GNU property data")
+            .map(|val| val.get(endian))
+    }
 }
 
 /// A trait for generic access to `NoteHeader32` and `NoteHeader64`.
@@ -1,14 +1,14 @@
 use alloc::fmt;
 use alloc::vec::Vec;
 use core::fmt::Debug;
-use core::slice;
+use core::{mem, slice};
 
 use crate::elf;
-use crate::endian::{self, Endianness};
-use crate::pod::Pod;
+use crate::endian::{self, Endianness, U32Bytes, U64Bytes};
+use crate::pod::{Bytes, Pod};
 use crate::read::{
-    self, Error, ReadRef, Relocation, RelocationEncoding, RelocationKind, RelocationTarget,
-    SymbolIndex,
+    self, Error, ReadRef, Relocation, RelocationEncoding, RelocationFlags, RelocationKind,
+    RelocationTarget, SymbolIndex,
 };
 
 use super::{ElfFile, FileHeader, SectionHeader, SectionTable};
@@ -91,6 +91,94 @@ impl<'data, Elf: FileHeader> Iterator for ElfRelaIterator<'data, Elf> {
     }
 }
 
+/// An iterator over the addresses of the relocations in an `SHT_RELR` section.
+///
+/// Each address is the location of a word that must have the load bias added to it.
+/// This is equivalent to a `R_*_RELATIVE` relocation with an implicit addend.
+#[derive(Debug, Clone)]
+pub struct RelrIterator<'data, Elf: FileHeader> {
+    endian: Elf::Endian,
+    data: Bytes<'data>,
+    /// The address that the next bitmap entry starts at.
+    next: u64,
+    /// The bits of the current bitmap entry that have not been returned yet.
+    bits: u64,
+    /// The address corresponding to bit 0 of `bits`.
+    base: u64,
+}
+
+impl<'data, Elf: FileHeader> RelrIterator<'data, Elf> {
+    /// Create an iterator for the given section data.
+    pub fn new(endian: Elf::Endian, data: &'data [u8]) -> Self {
+        RelrIterator {
+            endian,
+            data: Bytes(data),
+            next: 0,
+            bits: 0,
+            base: 0,
+        }
+    }
+
+    fn word_size() -> u64 {
+        mem::size_of::<Elf::Word>() as u64
+    }
+
+    fn read_word(&mut self) -> Option<u64> {
+        if Self::word_size() == 8 {
+            let word = self.data.read::<U64Bytes<Elf::Endian>>().ok()?;
+            Some(word.get(self.endian))
+        } else {
+            let word = self.data.read::<U32Bytes<Elf::Endian>>().ok()?;
+            Some(word.get(self.endian).into())
+        }
+    }
+}
+
+impl<'data, Elf: FileHeader> Iterator for RelrIterator<'data, Elf> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let word_size = Self::word_size();
+        loop {
+            if self.bits != 0 {
+                let index = u64::from(self.bits.trailing_zeros());
+                self.bits &= self.bits - 1;
+                return Some(self.base.wrapping_add(index * word_size));
+            }
+            let entry = self.read_word()?;
+            if entry & 1 == 0 {
+                // An even entry is the address of a relocation.
+                self.next = entry.wrapping_add(word_size);
+                return Some(entry);
+            }
+            // An odd entry is a bitmap of the relocations in the words following
+            // the previous entry. Bit 0 is the marker, so each bitmap entry
+            // covers one less word than the number of bits in the entry.
+            self.bits = entry >> 1;
+            self.base = self.next;
+            self.next = self.next.wrapping_add((word_size * 8 - 1) * word_size);
+        }
+    }
+}
+
+/// Return the `R_*_RELATIVE` relocation type for the given machine.
+///
+/// Returns 0 (`R_*_NONE`) if the machine is not known to use relative relocations.
+fn relative_relocation_type(e_machine: u16) -> u32 {
+    match e_machine {
+        elf::EM_386 => elf::R_386_RELATIVE,
+        elf::EM_X86_64 => elf::R_X86_64_RELATIVE,
+        elf::EM_AARCH64 => elf::R_AARCH64_RELATIVE,
+        elf::EM_ARM => elf::R_ARM_RELATIVE,
+        elf::EM_PPC => elf::R_PPC_RELATIVE,
+        elf::EM_PPC64 => elf::R_PPC64_RELATIVE,
+        elf::EM_RISCV => elf::R_RISCV_RELATIVE,
+        elf::EM_S390 => elf::R_390_RELATIVE,
+        elf::EM_SPARC | elf::EM_SPARC32PLUS | elf::EM_SPARCV9 => elf::R_SPARC_RELATIVE,
+        _ => 0,
+    }
+}
+
 /// An iterator over the dynamic relocations for an `ElfFile32`.
 pub type ElfDynamicRelocationIterator32<'data, 'file, Endian = Endianness, R = &'data [u8]> =
     ElfDynamicRelocationIterator<'data, 'file, elf::FileHeader32<Endian>, R>;
@@ -108,6 +196,7 @@ where
     pub(super) section_index: usize,
     pub(super) file: &'file ElfFile<'data, Elf, R>,
     pub(super) relocations: Option<ElfRelaIterator<'data, Elf>>,
+    pub(super) relr: Option<RelrIterator<'data, Elf>>,
 }
 
 impl<'data, 'file, Elf, R> Iterator for ElfDynamicRelocationIterator<'data, 'file, Elf, R>
@@ -129,9 +218,32 @@ where
                 self.relocations = None;
             }
 
+            if let Some(ref mut relr) = self.relr {
+                if let Some(address) = relr.next() {
+                    let r_type = relative_relocation_type(self.file.header.e_machine(endian));
+                    let relocation = Relocation {
+                        kind: RelocationKind::Elf(r_type),
+                        encoding: RelocationEncoding::Generic,
+                        size: 0,
+                        target: RelocationTarget::Absolute,
+                        addend: 0,
+                        implicit_addend: true,
+                        flags: RelocationFlags::Elf { r_type },
+                    };
+                    return Some((address, relocation));
+                }
+                self.relr = None;
+            }
+
             let section = self.file.sections.section(self.section_index).ok()?;
             self.section_index += 1;
 
+            // RELR sections don't use a symbol table.
+            if let Ok(Some(relr)) = section.relr(endian, self.file.data) {
+                self.relr = Some(relr);
+                continue;
+            }
+
             let sh_link = section.sh_link(endian) as usize;
             if sh_link != self.file.dynamic_symbols.section() {
                 continue;
@@ -247,14 +359,41 @@ fn parse_relocation<Elf: FileHeader>(
             elf::R_AARCH64_PREL64 => (RelocationKind::Relative, 64),
             elf::R_AARCH64_PREL32 => (RelocationKind::Relative, 32),
             elf::R_AARCH64_PREL16 => (RelocationKind::Relative, 16),
-            elf::R_AARCH64_CALL26 => {
+            elf::R_AARCH64_CALL26 | elf::R_AARCH64_JUMP26 => {
                 encoding = RelocationEncoding::AArch64Call;
                 (RelocationKind::PltRelative, 26)
             }
+            elf::R_AARCH64_GOTREL64 => (RelocationKind::GotBaseOffset, 64),
+            elf::R_AARCH64_GOTREL32 => (RelocationKind::GotBaseOffset, 32),
+            // The remaining relocations are for instruction immediates, which have
+            // no `RelocationEncoding`, so their size is not given.
+            elf::R_AARCH64_LD_PREL_LO19
+            | elf::R_AARCH64_ADR_PREL_LO21
+            | elf::R_AARCH64_TSTBR14
+            | elf::R_AARCH64_CONDBR19 => (RelocationKind::Relative, 0),
+            elf::R_AARCH64_GOT_LD_PREL19 => (RelocationKind::GotRelative, 0),
+            elf::R_AARCH64_TLSGD_ADR_PREL21
+            | elf::R_AARCH64_TLSGD_ADR_PAGE21
+            | elf::R_AARCH64_TLSGD_ADD_LO12_NC => (RelocationKind::TlsGd, 0),
+            elf::R_AARCH64_TLSLD_ADR_PREL21 | elf::R_AARCH64_TLSLD_ADR_PAGE21 => {
+                (RelocationKind::TlsLd, 0)
+            }
+            elf::R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21
+            | elf::R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC
+            | elf::R_AARCH64_TLSIE_LD_GOTTPREL_PREL19 => (RelocationKind::TlsIe, 0),
+            elf::R_AARCH64_TLSLE_ADD_TPREL_HI12
+            | elf::R_AARCH64_TLSLE_ADD_TPREL_LO12
+            | elf::R_AARCH64_TLSLE_ADD_TPREL_LO12_NC => (RelocationKind::TlsLe, 0),
             r_type => (RelocationKind::Elf(r_type), 0),
         },
         elf::EM_ARM => match reloc.r_type(endian, false) {
             elf::R_ARM_ABS32 => (RelocationKind::Absolute, 32),
+            elf::R_ARM_REL32 => (RelocationKind::Relative, 32),
+            elf::R_ARM_GOT_PREL => (RelocationKind::GotRelative, 32),
+            elf::R_ARM_TLS_GD32 => (RelocationKind::TlsGd, 32),
+            elf::R_ARM_TLS_LDM32 => (RelocationKind::TlsLd, 32),
+            elf::R_ARM_TLS_IE32 => (RelocationKind::TlsIe, 32),
+            elf::R_ARM_TLS_LE32 => (RelocationKind::TlsLe, 32),
             r_type => (RelocationKind::Elf(r_type), 0),
         },
         elf::EM_AVR => match reloc.r_type(endian, false) {
@@ -278,6 +417,10 @@ fn parse_relocation<Elf: FileHeader>(
             elf::R_386_PC16 => (RelocationKind::Relative, 16),
             elf::R_386_8 => (RelocationKind::Absolute, 8),
             elf::R_386_PC8 => (RelocationKind::Relative, 8),
+            elf::R_386_TLS_GD => (RelocationKind::TlsGd, 32),
+            elf::R_386_TLS_LDM => (RelocationKind::TlsLd, 32),
+            elf::R_386_TLS_IE => (RelocationKind::TlsIe, 32),
+            elf::R_386_TLS_LE => (RelocationKind::TlsLe, 32),
             r_type => (RelocationKind::Elf(r_type), 0),
         },
         elf::EM_X86_64 => match reloc.r_type(endian, false) {
@@ -295,6 +438,11 @@ fn parse_relocation<Elf: FileHeader>(
             elf::R_X86_64_PC16 => (RelocationKind::Relative, 16),
             elf::R_X86_64_8 => (RelocationKind::Absolute, 8),
             elf::R_X86_64_PC8 => (RelocationKind::Relative, 8),
+            elf::R_X86_64_TLSGD => (RelocationKind::TlsGd, 32),
+            elf::R_X86_64_TLSLD => (RelocationKind::TlsLd, 32),
+            elf::R_X86_64_GOTTPOFF => (RelocationKind::TlsIe, 32),
+            elf::R_X86_64_TPOFF32 => (RelocationKind::TlsLe, 32),
+            elf::R_X86_64_TPOFF64 => (RelocationKind::TlsLe, 64),
             r_type => (RelocationKind::Elf(r_type), 0),
         },
         elf::EM_HEXAGON => match reloc.r_type(endian, false) {
@@ -389,6 +537,9 @@ fn parse_relocation<Elf: FileHeader>(
         target,
         addend: reloc.r_addend(endian).into(),
         implicit_addend,
+        flags: RelocationFlags::Elf {
+            r_type: reloc.r_type(endian, is_mips64el),
+        },
     }
 }
 
@@ -1,17 +1,18 @@
+use alloc::borrow::Cow;
 use core::fmt::Debug;
 use core::{iter, mem, slice, str};
 
 use crate::elf;
-use crate::endian::{self, Endianness, U32Bytes};
+use crate::endian::{self, Endian, Endianness, U32Bytes};
 use crate::pod::{Bytes, Pod};
 use crate::read::{
     self, CompressedData, CompressedFileRange, CompressionFormat, Error, ObjectSection, ReadError,
-    ReadRef, SectionFlags, SectionIndex, SectionKind, StringTable,
+    ReadRef, SectionFlags, SectionIndex, SectionKind, SegmentFlags, StringTable,
 };
 
 use super::{
     CompressionHeader, ElfFile, ElfSectionRelocationIterator, FileHeader, NoteIterator,
-    RelocationSections, SymbolTable,
+    ProgramHeader, RelocationSections, RelrIterator, SymbolTable,
 };
 
 /// The table of section headers in an ELF file.
@@ -192,6 +193,39 @@ impl<'data, 'file, Elf: FileHeader, R: ReadRef<'data>> ElfSection<'data, 'file,
             .read_error("Invalid ELF section size or offset")
     }
 
+    /// Return the flags of the `PT_LOAD` segment that contains this section.
+    ///
+    /// These are the permissions that will be applied to the section at runtime,
+    /// which may differ from the section flags.
+    ///
+    /// Returns `None` if the section is not allocated, or is not contained in
+    /// a loadable segment.
+    pub fn runtime_flags(&self) -> Option<SegmentFlags> {
+        let endian = self.file.endian;
+        if self.section.sh_flags(endian).into() & u64::from(elf::SHF_ALLOC) == 0 {
+            return None;
+        }
+        let address: u64 = self.section.sh_addr(endian).into();
+        let end = address.checked_add(self.section.sh_size(endian).into())?;
+        self.file
+            .segments
+            .iter()
+            .find(|segment| {
+                if segment.p_type(endian) != elf::PT_LOAD {
+                    return false;
+                }
+                let segment_address: u64 = segment.p_vaddr(endian).into();
+                let segment_size: u64 = segment.p_memsz(endian).into();
+                match segment_address.checked_add(segment_size) {
+                    Some(segment_end) => address >= segment_address && end <= segment_end,
+                    None => false,
+                }
+            })
+            .map(|segment| SegmentFlags::Elf {
+                p_flags: segment.p_flags(endian),
+            })
+    }
+
     fn maybe_compressed(&self) -> read::Result<Option<CompressedFileRange>> {
         let endian = self.file.endian;
         if (self.section.sh_flags(endian).into() & u64::from(elf::SHF_COMPRESSED)) == 0 {
@@ -332,6 +366,10 @@ where
         self.compressed_file_range()?.data(self.file.data)
     }
 
+    fn relocated_data(&self) -> read::Result<Cow<'data, [u8]>> {
+        read::util::relocated_data(self.file, self, false)
+    }
+
     fn name(&self) -> read::Result<&str> {
         let name = self
             .file
@@ -385,12 +423,59 @@ where
             | elf::SHT_HASH
             | elf::SHT_DYNAMIC
             | elf::SHT_REL
+            | elf::SHT_RELR
             | elf::SHT_DYNSYM
             | elf::SHT_GROUP => SectionKind::Metadata,
             _ => SectionKind::Elf(sh_type),
         }
     }
 
+    fn is_stub(&self) -> bool {
+        let flags: u64 = self.section.sh_flags(self.file.endian).into();
+        if flags & u64::from(elf::SHF_EXECINSTR) == 0 {
+            return false;
+        }
+        match self
+            .file
+            .sections
+            .section_name(self.file.endian, self.section)
+        {
+            Ok(name) => name.starts_with(b".plt"),
+            Err(_) => false,
+        }
+    }
+
+    fn endianness(&self) -> Endianness {
+        if self.file.endian.is_little_endian() {
+            Endianness::Little
+        } else {
+            Endianness::Big
+        }
+    }
+
+    fn relocation_count(&self) -> usize {
+        let endian = self.file.endian;
+        let mut count = 0;
+        let mut section_index = self.index.0;
+        while let Some(index) = self.file.relocations.get(section_index) {
+            section_index = index;
+            // The construction of RelocationSections ensures section_index is valid.
+            let section = self.file.sections.section(section_index).unwrap();
+            count += match section.sh_type(endian) {
+                elf::SHT_REL => section
+                    .data_as_array::<Elf::Rel, _>(endian, self.file.data)
+                    .map(|relocations| relocations.len())
+                    .unwrap_or(0),
+                elf::SHT_RELA => section
+                    .data_as_array::<Elf::Rela, _>(endian, self.file.data)
+                    .map(|relocations| relocations.len())
+                    .unwrap_or(0),
+                _ => 0,
+            };
+        }
+        count
+    }
+
     fn relocations(&self) -> ElfSectionRelocationIterator<'data, 'file, Elf, R> {
         ElfSectionRelocationIterator {
             section_index: self.index.0,
@@ -533,6 +618,24 @@ pub trait SectionHeader: Debug + Pod {
             .read_error("Invalid ELF relocation section offset or size")
     }
 
+    /// Return an iterator over the addresses of the relative relocations in the section.
+    ///
+    /// Returns `Ok(None)` if the section does not contain RELR relocations.
+    /// Returns `Err` for invalid values.
+    fn relr<'data, R: ReadRef<'data>>(
+        &self,
+        endian: Self::Endian,
+        data: R,
+    ) -> read::Result<Option<RelrIterator<'data, Self::Elf>>> {
+        if self.sh_type(endian) != elf::SHT_RELR {
+            return Ok(None);
+        }
+        let data = self
+            .data(endian, data)
+            .read_error("Invalid ELF relocation section offset or size")?;
+        Ok(Some(RelrIterator::new(endian, data)))
+    }
+
     /// Return the symbol table for a relocation section.
     ///
     /// Returns `Err` for invalid values, including if the section does not contain
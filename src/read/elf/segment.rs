@@ -4,9 +4,9 @@ use core::{mem, slice, str};
 use crate::elf;
 use crate::endian::{self, Endianness};
 use crate::pod::{Bytes, Pod};
-use crate::read::{self, ObjectSegment, ReadError, ReadRef};
+use crate::read::{self, ObjectSegment, ReadError, ReadRef, SegmentFlags};
 
-use super::{ElfFile, FileHeader, NoteIterator};
+use super::{ElfFile, ElfSection, ElfSectionIterator, FileHeader, NoteIterator, SectionHeader};
 
 /// An iterator over the segments of an `ElfFile32`.
 pub type ElfSegmentIterator32<'data, 'file, Endian = Endianness, R = &'data [u8]> =
@@ -71,6 +71,21 @@ impl<'data, 'file, Elf: FileHeader, R: ReadRef<'data>> ElfSegment<'data, 'file,
             .data(self.file.endian, self.file.data)
             .read_error("Invalid ELF segment size or offset")
     }
+
+    /// Return an iterator over the sections that are contained in this segment.
+    ///
+    /// A section is contained in the segment if it is allocated and its address range
+    /// lies within the address range of the segment. TLS sections without file data
+    /// (such as `.tbss`) are excluded since they don't occupy space in the segment.
+    pub fn sections(&self) -> ElfSegmentSectionIterator<'data, 'file, Elf, R> {
+        ElfSegmentSectionIterator {
+            segment: self.segment,
+            iter: ElfSectionIterator {
+                file: self.file,
+                iter: self.file.sections.iter().enumerate(),
+            },
+        }
+    }
 }
 
 impl<'data, 'file, Elf, R> read::private::Sealed for ElfSegment<'data, 'file, Elf, R>
@@ -123,6 +138,66 @@ where
     fn name(&self) -> read::Result<Option<&str>> {
         Ok(None)
     }
+
+    #[inline]
+    fn flags(&self) -> SegmentFlags {
+        let p_flags = self.segment.p_flags(self.file.endian);
+        SegmentFlags::Elf { p_flags }
+    }
+}
+
+/// An iterator over the sections of an `ElfSegment32`.
+pub type ElfSegmentSectionIterator32<'data, 'file, Endian = Endianness, R = &'data [u8]> =
+    ElfSegmentSectionIterator<'data, 'file, elf::FileHeader32<Endian>, R>;
+/// An iterator over the sections of an `ElfSegment64`.
+pub type ElfSegmentSectionIterator64<'data, 'file, Endian = Endianness, R = &'data [u8]> =
+    ElfSegmentSectionIterator<'data, 'file, elf::FileHeader64<Endian>, R>;
+
+/// An iterator over the sections of an `ElfSegment`.
+#[derive(Debug)]
+pub struct ElfSegmentSectionIterator<'data, 'file, Elf, R = &'data [u8]>
+where
+    'data: 'file,
+    Elf: FileHeader,
+    R: ReadRef<'data>,
+{
+    segment: &'data Elf::ProgramHeader,
+    iter: ElfSectionIterator<'data, 'file, Elf, R>,
+}
+
+impl<'data, 'file, Elf, R> Iterator for ElfSegmentSectionIterator<'data, 'file, Elf, R>
+where
+    Elf: FileHeader,
+    R: ReadRef<'data>,
+{
+    type Item = ElfSection<'data, 'file, Elf, R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let endian = self.iter.file.endian;
+        let segment_address: u64 = self.segment.p_vaddr(endian).into();
+        let segment_size: u64 = self.segment.p_memsz(endian).into();
+        let segment_end = segment_address.checked_add(segment_size)?;
+        for section in &mut self.iter {
+            let header = section.section;
+            let flags: u64 = header.sh_flags(endian).into();
+            if flags & u64::from(elf::SHF_ALLOC) == 0 {
+                continue;
+            }
+            if flags & u64::from(elf::SHF_TLS) != 0 && header.sh_type(endian) == elf::SHT_NOBITS {
+                continue;
+            }
+            let address: u64 = header.sh_addr(endian).into();
+            let size: u64 = header.sh_size(endian).into();
+            let end = match address.checked_add(size) {
+                Some(end) => end,
+                None => continue,
+            };
+            if address >= segment_address && end <= segment_end {
+                return Some(section);
+            }
+        }
+        None
+    }
 }
 
 /// A trait for generic access to `ProgramHeader32` and `ProgramHeader64`.
@@ -1,8 +1,8 @@
 use alloc::fmt;
 use alloc::vec::Vec;
 use core::fmt::Debug;
-use core::slice;
 use core::str;
+use core::{cmp, slice};
 
 use crate::elf;
 use crate::endian::{self, Endianness};
@@ -22,6 +22,7 @@ use super::{FileHeader, SectionHeader, SectionTable};
 pub struct SymbolTable<'data, Elf: FileHeader> {
     section: usize,
     symbols: &'data [Elf::Sym],
+    first_global: usize,
     strings: StringTable<'data>,
     shndx: &'data [u32],
 }
@@ -31,6 +32,7 @@ impl<'data, Elf: FileHeader> Default for SymbolTable<'data, Elf> {
         SymbolTable {
             section: 0,
             symbols: &[],
+            first_global: 0,
             strings: Default::default(),
             shndx: &[],
         }
@@ -51,9 +53,11 @@ impl<'data, Elf: FileHeader> SymbolTable<'data, Elf> {
                 || section.sh_type(endian) == elf::SHT_SYMTAB
         );
 
-        let symbols = section
+        let symbols: &'data [Elf::Sym] = section
             .data_as_array(endian, data)
             .read_error("Invalid ELF symbol table data")?;
+        // `sh_info` is one greater than the index of the last local symbol.
+        let first_global = cmp::min(section.sh_info(endian) as usize, symbols.len());
 
         let strtab = sections.section(section.sh_link(endian) as usize)?;
         let strtab_data = strtab
@@ -78,6 +82,7 @@ impl<'data, Elf: FileHeader> SymbolTable<'data, Elf> {
         Ok(SymbolTable {
             section: section_index,
             symbols,
+            first_global,
             strings,
             shndx,
         })
@@ -113,6 +118,15 @@ impl<'data, Elf: FileHeader> SymbolTable<'data, Elf> {
         self.symbols.len()
     }
 
+    /// The index of the first non-local symbol.
+    ///
+    /// ELF requires all local symbols to precede the global symbols in the table.
+    /// This is determined by the `sh_info` field of the symbol table section header.
+    #[inline]
+    pub fn first_global(&self) -> usize {
+        self.first_global
+    }
+
     /// Return the symbol at the given index.
     pub fn symbol(&self, index: usize) -> read::Result<&'data Elf::Sym> {
         self.symbols
@@ -135,6 +149,47 @@ impl<'data, Elf: FileHeader> SymbolTable<'data, Elf> {
         symbol.name(endian, self.strings)
     }
 
+    /// Return the name of the source file for the symbol at the given index.
+    ///
+    /// This is the name of the nearest preceding `STT_FILE` symbol. Only local
+    /// symbols are associated with a file. For `STT_FILE` symbols, this is the
+    /// name of the symbol itself.
+    ///
+    /// This searches backwards from the given index. When this is needed for many
+    /// symbols, use `ElfSymbol::file_name` for the symbols returned by a symbol
+    /// iterator instead, because the iterator tracks the most recent `STT_FILE` symbol.
+    ///
+    /// Returns `Ok(None)` if the symbol is not local, or if there is no preceding
+    /// `STT_FILE` symbol, or if the name of that symbol is empty.
+    pub fn symbol_file_name(
+        &self,
+        endian: Elf::Endian,
+        index: usize,
+    ) -> read::Result<Option<&'data [u8]>> {
+        let symbol = self.symbol(index)?;
+        let file = self.symbols[..=index]
+            .iter()
+            .rposition(|symbol| symbol.st_type() == elf::STT_FILE)
+            .unwrap_or(0);
+        self.file_name(endian, symbol, file)
+    }
+
+    /// Return the name of the given `STT_FILE` symbol, if `symbol` is local.
+    ///
+    /// A `file` index of 0 means that there is no `STT_FILE` symbol.
+    fn file_name(
+        &self,
+        endian: Elf::Endian,
+        symbol: &'data Elf::Sym,
+        file: usize,
+    ) -> read::Result<Option<&'data [u8]>> {
+        if symbol.st_bind() != elf::STB_LOCAL || file == 0 {
+            return Ok(None);
+        }
+        let name = self.symbol(file)?.name(endian, self.strings)?;
+        Ok(if name.is_empty() { None } else { Some(name) })
+    }
+
     /// Construct a map from addresses to a user-defined map entry.
     pub fn map<Entry: SymbolMapEntry, F: Fn(&'data Elf::Sym) -> Option<Entry>>(
         &self,
@@ -169,9 +224,40 @@ where
     Elf: FileHeader,
 {
     pub(super) endian: Elf::Endian,
+    pub(super) machine: u16,
     pub(super) symbols: &'file SymbolTable<'data, Elf>,
 }
 
+impl<'data, 'file, Elf: FileHeader> ElfSymbolTable<'data, 'file, Elf> {
+    /// Get an iterator over the local symbols in the table.
+    ///
+    /// This includes the null symbol at index 0.
+    pub fn local_symbols(&self) -> ElfSymbolIterator<'data, 'file, Elf> {
+        ElfSymbolIterator {
+            endian: self.endian,
+            machine: self.machine,
+            symbols: self.symbols,
+            index: 0,
+            end: self.symbols.first_global(),
+            file: 0,
+        }
+    }
+
+    /// Get an iterator over the global and weak symbols in the table.
+    ///
+    /// This does not need to read the local symbols.
+    pub fn global_symbols(&self) -> ElfSymbolIterator<'data, 'file, Elf> {
+        ElfSymbolIterator {
+            endian: self.endian,
+            machine: self.machine,
+            symbols: self.symbols,
+            index: self.symbols.first_global(),
+            end: self.symbols.len(),
+            file: 0,
+        }
+    }
+}
+
 impl<'data, 'file, Elf: FileHeader> read::private::Sealed for ElfSymbolTable<'data, 'file, Elf> {}
 
 impl<'data, 'file, Elf: FileHeader> ObjectSymbolTable<'data> for ElfSymbolTable<'data, 'file, Elf> {
@@ -181,8 +267,11 @@ impl<'data, 'file, Elf: FileHeader> ObjectSymbolTable<'data> for ElfSymbolTable<
     fn symbols(&self) -> Self::SymbolIterator {
         ElfSymbolIterator {
             endian: self.endian,
+            machine: self.machine,
             symbols: self.symbols,
             index: 0,
+            end: self.symbols.len(),
+            file: 0,
         }
     }
 
@@ -190,9 +279,11 @@ impl<'data, 'file, Elf: FileHeader> ObjectSymbolTable<'data> for ElfSymbolTable<
         let symbol = self.symbols.symbol(index.0)?;
         Ok(ElfSymbol {
             endian: self.endian,
+            machine: self.machine,
             symbols: self.symbols,
             index,
             symbol,
+            file: None,
         })
     }
 }
@@ -211,8 +302,12 @@ where
     Elf: FileHeader,
 {
     pub(super) endian: Elf::Endian,
+    pub(super) machine: u16,
     pub(super) symbols: &'file SymbolTable<'data, Elf>,
     pub(super) index: usize,
+    pub(super) end: usize,
+    /// The index of the most recent `STT_FILE` symbol, or 0 if there is none.
+    pub(super) file: usize,
 }
 
 impl<'data, 'file, Elf: FileHeader> fmt::Debug for ElfSymbolIterator<'data, 'file, Elf> {
@@ -226,13 +321,21 @@ impl<'data, 'file, Elf: FileHeader> Iterator for ElfSymbolIterator<'data, 'file,
 
     fn next(&mut self) -> Option<Self::Item> {
         let index = self.index;
+        if index >= self.end {
+            return None;
+        }
         let symbol = self.symbols.symbols.get(index)?;
         self.index += 1;
+        if symbol.st_type() == elf::STT_FILE {
+            self.file = index;
+        }
         Some(ElfSymbol {
             endian: self.endian,
+            machine: self.machine,
             symbols: self.symbols,
             index: SymbolIndex(index),
             symbol,
+            file: Some(self.file),
         })
     }
 }
@@ -252,21 +355,53 @@ where
     Elf: FileHeader,
 {
     pub(super) endian: Elf::Endian,
+    pub(super) machine: u16,
     pub(super) symbols: &'file SymbolTable<'data, Elf>,
     pub(super) index: SymbolIndex,
     pub(super) symbol: &'data Elf::Sym,
+    /// The index of the `STT_FILE` symbol for this symbol, or 0 if there is none.
+    ///
+    /// This is `None` if the symbol was not returned by an iterator, in which case
+    /// the symbol table is searched when the file name is needed.
+    pub(super) file: Option<usize>,
 }
 
 impl<'data, 'file, Elf: FileHeader> read::private::Sealed for ElfSymbol<'data, 'file, Elf> {}
 
+impl<'data, 'file, Elf: FileHeader> ElfSymbol<'data, 'file, Elf> {
+    /// Return the value of the symbol.
+    ///
+    /// Unlike `address`, this does not clear the Thumb bit for ARM functions.
+    #[inline]
+    pub fn raw_address(&self) -> u64 {
+        self.symbol.st_value(self.endian).into()
+    }
+
+    /// Return the name of the source file for this symbol.
+    ///
+    /// See `SymbolTable::symbol_file_name`. For symbols returned by a symbol iterator,
+    /// this does not need to search the symbol table.
+    pub fn file_name(&self) -> read::Result<Option<&'data [u8]>> {
+        match self.file {
+            Some(file) => self.symbols.file_name(self.endian, self.symbol, file),
+            None => self.symbols.symbol_file_name(self.endian, self.index.0),
+        }
+    }
+}
+
 impl<'data, 'file, Elf: FileHeader> ObjectSymbol<'data> for ElfSymbol<'data, 'file, Elf> {
     #[inline]
     fn index(&self) -> SymbolIndex {
         self.index
     }
 
+    #[inline]
+    fn name_bytes(&self) -> read::Result<&'data [u8]> {
+        self.symbol.name(self.endian, self.symbols.strings())
+    }
+
     fn name(&self) -> read::Result<&'data str> {
-        let name = self.symbol.name(self.endian, self.symbols.strings())?;
+        let name = self.name_bytes()?;
         str::from_utf8(name)
             .ok()
             .read_error("Non UTF-8 ELF symbol name")
@@ -274,7 +409,30 @@ impl<'data, 'file, Elf: FileHeader> ObjectSymbol<'data> for ElfSymbol<'data, 'fi
 
     #[inline]
     fn address(&self) -> u64 {
-        self.symbol.st_value(self.endian).into()
+        let address = self.raw_address();
+        if self.is_thumb() {
+            address & !1
+        } else {
+            address
+        }
+    }
+
+    #[inline]
+    fn tls_offset(&self) -> Option<u64> {
+        if self.symbol.st_type() == elf::STT_TLS {
+            Some(self.symbol.st_value(self.endian).into())
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn common_alignment(&self) -> Option<u64> {
+        if self.symbol.st_shndx(self.endian) == elf::SHN_COMMON {
+            Some(self.symbol.st_value(self.endian).into())
+        } else {
+            None
+        }
     }
 
     #[inline]
@@ -285,6 +443,7 @@ impl<'data, 'file, Elf: FileHeader> ObjectSymbol<'data> for ElfSymbol<'data, 'fi
     fn kind(&self) -> SymbolKind {
         match self.symbol.st_type() {
             elf::STT_NOTYPE if self.index.0 == 0 => SymbolKind::Null,
+            elf::STT_NOTYPE if self.section().index().is_some() => SymbolKind::Label,
             elf::STT_OBJECT | elf::STT_COMMON => SymbolKind::Data,
             elf::STT_FUNC => SymbolKind::Text,
             elf::STT_SECTION => SymbolKind::Section,
@@ -336,6 +495,18 @@ impl<'data, 'file, Elf: FileHeader> ObjectSymbol<'data> for ElfSymbol<'data, 'fi
         self.symbol.st_bind() == elf::STB_WEAK
     }
 
+    #[inline]
+    fn is_ifunc(&self) -> bool {
+        self.symbol.st_type() == elf::STT_GNU_IFUNC
+    }
+
+    #[inline]
+    fn is_thumb(&self) -> bool {
+        self.machine == elf::EM_ARM
+            && self.symbol.st_type() == elf::STT_FUNC
+            && self.symbol.st_value(self.endian).into() & 1 != 0
+    }
+
     fn scope(&self) -> SymbolScope {
         if self.symbol.st_shndx(self.endian) == elf::SHN_UNDEF {
             SymbolScope::Unknown
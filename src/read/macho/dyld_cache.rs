@@ -208,9 +208,38 @@ impl<E: Endian> macho::DyldCacheImageInfo<E> {
             if address >= mapping_address
                 && address < mapping_address.wrapping_add(mapping.size.get(endian))
             {
-                return Ok(address - mapping_address + mapping.file_offset.get(endian));
+                return (address - mapping_address)
+                    .checked_add(mapping.file_offset.get(endian))
+                    .read_error("Invalid dyld cache mapping file offset");
             }
         }
         Err(Error("Invalid dyld cache image address"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endian::{LittleEndian as LE, U32, U64};
+
+    #[test]
+    fn image_file_offset_overflow() {
+        let mapping = |file_offset| macho::DyldCacheMappingInfo {
+            address: U64::new(LE, 0x1000),
+            size: U64::new(LE, 0x1000),
+            file_offset: U64::new(LE, file_offset),
+            max_prot: U32::new(LE, 0),
+            init_prot: U32::new(LE, 0),
+        };
+        let image = macho::DyldCacheImageInfo {
+            address: U64::new(LE, 0x1010),
+            mod_time: U64::new(LE, 0),
+            inode: U64::new(LE, 0),
+            path_file_offset: U32::new(LE, 0),
+            pad: U32::new(LE, 0),
+        };
+        assert_eq!(image.file_offset(LE, &[mapping(0x2000)]), Ok(0x2010));
+        // This used to overflow.
+        assert!(image.file_offset(LE, &[mapping(u64::max_value())]).is_err());
+    }
+}
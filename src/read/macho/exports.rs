@@ -0,0 +1,124 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::macho;
+use crate::pod::Bytes;
+use crate::read::{Error, ExportedSymbol, ReadError, Result};
+
+/// Parse the export trie from an `LC_DYLD_INFO` or `LC_DYLD_EXPORTS_TRIE` command.
+///
+/// `base` is the address of the Mach-O header in memory. The addresses
+/// in the trie are relative to this, except for absolute symbols.
+///
+/// Re-exported symbols are skipped, because they are not defined in this file.
+pub(super) fn export_trie<'data>(
+    trie: &'data [u8],
+    base: u64,
+) -> Result<Vec<ExportedSymbol<'data>>> {
+    let trie = Bytes(trie);
+    let mut exports = Vec::new();
+    if trie.is_empty() {
+        return Ok(exports);
+    }
+
+    // Each node occupies at least one byte, so a valid trie can't have more
+    // nodes than this. This also prevents infinite loops for cyclic tries.
+    let mut remaining_nodes = trie.len();
+    let mut stack = vec![(0, Vec::new())];
+    while let Some((offset, name)) = stack.pop() {
+        remaining_nodes = remaining_nodes
+            .checked_sub(1)
+            .read_error("Invalid Mach-O export trie node count")?;
+        let mut node = trie;
+        node.skip(offset)
+            .read_error("Invalid Mach-O export trie node offset")?;
+        let terminal_size = node
+            .read_uleb128()
+            .read_error("Invalid Mach-O export trie terminal size")?;
+        let mut children = node;
+        children
+            .skip(terminal_size as usize)
+            .read_error("Invalid Mach-O export trie terminal size")?;
+
+        if terminal_size != 0 {
+            let flags = node
+                .read_uleb128()
+                .read_error("Invalid Mach-O export trie flags")?;
+            if flags & u64::from(macho::EXPORT_SYMBOL_FLAGS_REEXPORT) == 0 {
+                let offset = node
+                    .read_uleb128()
+                    .read_error("Invalid Mach-O export trie symbol offset")?;
+                let address = if flags & u64::from(macho::EXPORT_SYMBOL_FLAGS_KIND_MASK)
+                    == u64::from(macho::EXPORT_SYMBOL_FLAGS_KIND_ABSOLUTE)
+                {
+                    offset
+                } else {
+                    base.wrapping_add(offset)
+                };
+                exports.push(ExportedSymbol {
+                    name: Cow::Owned(name.clone()),
+                    address,
+                    is_weak: flags & u64::from(macho::EXPORT_SYMBOL_FLAGS_WEAK_DEFINITION) != 0,
+                });
+            }
+        }
+
+        let child_count = *children
+            .read::<u8>()
+            .read_error("Invalid Mach-O export trie child count")?;
+        for _ in 0..child_count {
+            let edge = children
+                .read_string()
+                .read_error("Invalid Mach-O export trie edge")?;
+            let child = children
+                .read_uleb128()
+                .read_error("Invalid Mach-O export trie child offset")?;
+            if child == 0 {
+                return Err(Error("Invalid Mach-O export trie child offset"));
+            }
+            let mut child_name = name.clone();
+            child_name.extend_from_slice(edge);
+            stack.push((child as usize, child_name));
+        }
+    }
+    Ok(exports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trie() {
+        let trie = [
+            // Root node: not terminal, one child.
+            0x00, 0x01, b'_', 0x00, 0x06, //
+            // Padding.
+            0x00, //
+            // "_": not terminal, two children.
+            0x00, 0x02, b'f', b'o', b'o', 0x00, 0x14, b'b', b'a', b'r', 0x00, 0x19, //
+            // Padding.
+            0x00, 0x00, //
+            // "_foo": regular symbol at offset 0x1000, no children.
+            0x03, 0x00, 0x80, 0x20, 0x00, //
+            // "_bar": weak symbol at offset 0x10, no children.
+            0x02, 0x04, 0x10, 0x00,
+        ];
+        let mut exports = export_trie(&trie, 0x1_0000_0000).unwrap();
+        exports.sort_by_key(|export| export.address());
+        assert_eq!(exports.len(), 2);
+        assert_eq!(exports[0].name(), b"_bar");
+        assert_eq!(exports[0].address(), 0x1_0000_0010);
+        assert!(exports[0].is_weak());
+        assert_eq!(exports[1].name(), b"_foo");
+        assert_eq!(exports[1].address(), 0x1_0000_1000);
+        assert!(!exports[1].is_weak());
+
+        // A child that points back to the root.
+        let cyclic = [0x00, 0x01, b'_', 0x00, 0x05, 0x00, 0x01, b'_', 0x00, 0x00];
+        assert!(export_trie(&cyclic, 0).is_err());
+        // A child that points to itself.
+        let cyclic = [0x00, 0x01, b'_', 0x00, 0x05, 0x00, 0x01, b'_', 0x00, 0x05];
+        assert!(export_trie(&cyclic, 0).is_err());
+    }
+}
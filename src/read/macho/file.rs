@@ -1,18 +1,21 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::Debug;
 use core::{mem, str};
 
+use crate::read::util::{AddressIndex, LazyBox};
 use crate::read::{
-    self, Architecture, ComdatKind, Error, Export, FileFlags, Import, NoDynamicRelocationIterator,
-    Object, ObjectComdat, ObjectMap, ObjectSection, ReadError, ReadRef, Result, SectionIndex,
-    SymbolIndex,
+    self, util, Architecture, ComdatKind, Error, Export, ExportedSymbol, FileFlags, Import,
+    NoDynamicRelocationIterator, Object, ObjectComdat, ObjectMap, ObjectSection, ObjectSegment,
+    ReadError, ReadRef, Result, SectionIndex, SymbolIndex,
 };
-use crate::{endian, macho, BigEndian, ByteString, Endian, Endianness, Pod};
+use crate::{endian, macho, BigEndian, ByteString, Bytes, Endian, Endianness, Pod};
 
 use super::{
-    LoadCommandIterator, MachOSection, MachOSectionInternal, MachOSectionIterator, MachOSegment,
-    MachOSegmentIterator, MachOSymbol, MachOSymbolIterator, MachOSymbolTable, Nlist, Section,
-    Segment, SymbolTable,
+    is_objc_section, LoadCommandIterator, MachOPointer, MachOSection, MachOSectionInternal,
+    MachOSectionIterator, MachOSegment, MachOSegmentIterator, MachOStab, MachOSymbol,
+    MachOSymbolIterator, MachOSymbolTable, Nlist, Section, Segment, SymbolTable,
 };
 
 /// A 32-bit Mach-O object file.
@@ -37,6 +40,7 @@ where
     pub(super) header: &'data Mach,
     pub(super) sections: Vec<MachOSectionInternal<'data, Mach>>,
     pub(super) symbols: SymbolTable<'data, Mach>,
+    pub(super) address_index: LazyBox<AddressIndex>,
 }
 
 impl<'data, Mach, R> MachOFile<'data, Mach, R>
@@ -65,7 +69,7 @@ where
                 if let Some((segment, section_data)) = Mach::Segment::from_command(command)? {
                     for section in segment.sections(endian, section_data)? {
                         let index = SectionIndex(sections.len() + 1);
-                        sections.push(MachOSectionInternal::parse(index, section));
+                        sections.push(MachOSectionInternal::parse(endian, index, section));
                     }
                 } else if let Some(symtab) = command.symtab()? {
                     symbols = symtab.symbols(endian, data)?;
@@ -80,9 +84,169 @@ where
             header,
             sections,
             symbols,
+            address_index: LazyBox::new(),
         })
     }
 
+    /// Return the sections containing Objective-C runtime metadata.
+    ///
+    /// These are the `__objc_*` sections in the `__DATA`, `__DATA_CONST` and `__DATA_DIRTY`
+    /// segments, such as `__objc_classlist` and `__objc_selrefs`, and the sections in the
+    /// legacy `__OBJC` segment. These sections have a kind of `SectionKind::Metadata`.
+    pub fn objc_sections<'file>(&'file self) -> Vec<MachOSection<'data, 'file, Mach, R>> {
+        self.sections
+            .iter()
+            .filter(|internal| {
+                is_objc_section(internal.section.segment_name(), internal.section.name())
+            })
+            .map(|&internal| MachOSection {
+                file: self,
+                internal,
+            })
+            .collect()
+    }
+
+    /// Return the entries of a `MH_FILESET` file, such as a kernel collection.
+    ///
+    /// Each entry is an embedded Mach-O image that is described by a
+    /// `LC_FILESET_ENTRY` load command. Returns an empty vector for other files.
+    pub fn fileset_entries(&self) -> Result<Vec<MachOFilesetEntry<'data, R>>> {
+        let mut entries = Vec::new();
+        let mut commands = self
+            .header
+            .load_commands(self.endian, self.data, self.header_offset)?;
+        while let Some(command) = commands.next()? {
+            if let Some((entry, name)) = command.fileset_entry(self.endian)? {
+                entries.push(MachOFilesetEntry {
+                    data: self.data,
+                    name,
+                    address: entry.vmaddr.get(self.endian),
+                    file_offset: entry.fileoff.get(self.endian),
+                });
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Return the STABS debugging entries in the symbol table.
+    ///
+    /// These entries are interleaved with the normal symbols in the symbol table,
+    /// but are omitted from `Object::symbols` and the other symbol accessors.
+    /// They are used by debuggers, for example to locate the object files that
+    /// were linked into an executable.
+    pub fn stabs(&self) -> Result<Vec<MachOStab<'data>>> {
+        self.symbols.stabs(self.endian)
+    }
+
+    /// Return the pointers that are fixed up by the `LC_DYLD_CHAINED_FIXUPS` load command.
+    ///
+    /// Returns the address and value of each pointer, in segment and chain order.
+    /// Returns an empty vector if the file does not use chained fixups.
+    ///
+    /// Only the `DYLD_CHAINED_PTR_64` and `DYLD_CHAINED_PTR_64_OFFSET` pointer formats
+    /// are supported. An error is returned for other formats, such as those used for arm64e.
+    pub fn chained_fixups(&self) -> Result<Vec<(u64, MachOPointer<'data>)>> {
+        self.chained_fixups_in_range(None)
+    }
+
+    /// Return the pointers that are fixed up by chained fixups, optionally
+    /// limited to the addresses in `start..end`.
+    ///
+    /// Only the chains in the pages that overlap the range are walked.
+    pub(super) fn chained_fixups_in_range(
+        &self,
+        range: Option<(u64, u64)>,
+    ) -> Result<Vec<(u64, MachOPointer<'data>)>> {
+        let fixups = match self.linkedit_data(macho::LC_DYLD_CHAINED_FIXUPS)? {
+            Some(fixups) => fixups,
+            None => return Ok(Vec::new()),
+        };
+        let mut segments = Vec::new();
+        let mut commands = self
+            .header
+            .load_commands(self.endian, self.data, self.header_offset)?;
+        while let Some(command) = commands.next()? {
+            if let Some((segment, _)) = Mach::Segment::from_command(command)? {
+                segments.push((
+                    segment.vmaddr(self.endian).into(),
+                    segment.fileoff(self.endian).into(),
+                ));
+            }
+        }
+        super::chained_fixups(
+            self.endian,
+            self.data,
+            fixups,
+            &segments,
+            self.preferred_load_address(),
+            range,
+        )
+    }
+
+    /// Return the entries of the `LC_DATA_IN_CODE` load command.
+    ///
+    /// Each entry describes a range of data, such as a jump table, that is
+    /// embedded in a code section. The offset of each entry is relative to
+    /// the Mach-O header.
+    ///
+    /// Returns an empty slice if the file has no `LC_DATA_IN_CODE` load command.
+    pub fn data_in_code(&self) -> Result<&'data [macho::DataInCodeEntry<Mach::Endian>]> {
+        let data = match self.linkedit_data(macho::LC_DATA_IN_CODE)? {
+            Some(data) => data,
+            None => return Ok(&[]),
+        };
+        let count = data.len() / mem::size_of::<macho::DataInCodeEntry<Mach::Endian>>();
+        Bytes(data)
+            .read_slice_at(0, count)
+            .read_error("Invalid Mach-O data in code alignment")
+    }
+
+    /// Return true if this image is part of a dyld shared cache.
+    ///
+    /// This is determined by the `MH_DYLIB_IN_CACHE` header flag, which is set for
+    /// images in the cache, and for images that have been extracted from it.
+    ///
+    /// The linkedit data of these images, such as the symbol table, is shared with
+    /// other images in the cache, and its file offsets are relative to the start of
+    /// the cache. Use `MachOFile::parse_at` with the data of the entire cache to read it.
+    /// Images that have been extracted from the cache can only be read if the extraction
+    /// tool has rewritten these offsets.
+    pub fn is_dyld_cache_image(&self) -> bool {
+        self.header.flags(self.endian) & macho::MH_DYLIB_IN_CACHE != 0
+    }
+
+    /// Return the data of the `LC_SEGMENT_SPLIT_INFO` load command.
+    ///
+    /// This data is used when building the dyld shared cache, so that the segments of
+    /// a dylib can be relocated independently of each other. The contents of the data
+    /// are not parsed.
+    ///
+    /// Returns `None` if the file has no `LC_SEGMENT_SPLIT_INFO` load command.
+    pub fn segment_split_info(&self) -> Result<Option<&'data [u8]>> {
+        self.linkedit_data(macho::LC_SEGMENT_SPLIT_INFO)
+    }
+
+    /// Return the data referenced by the first `LinkeditDataCommand` with the given `cmd`.
+    fn linkedit_data(&self, cmd: u32) -> Result<Option<&'data [u8]>> {
+        let mut commands = self
+            .header
+            .load_commands(self.endian, self.data, self.header_offset)?;
+        while let Some(command) = commands.next()? {
+            if command.cmd() == cmd {
+                let command = command.data::<macho::LinkeditDataCommand<Mach::Endian>>()?;
+                return self
+                    .data
+                    .read_bytes_at(
+                        command.dataoff.get(self.endian).into(),
+                        command.datasize.get(self.endian).into(),
+                    )
+                    .read_error("Invalid Mach-O linkedit data offset or size")
+                    .map(Some);
+            }
+        }
+        Ok(None)
+    }
+
     /// Return the section at the given index.
     #[inline]
     pub(super) fn section_internal(
@@ -142,6 +306,11 @@ where
         self.header.is_type_64()
     }
 
+    #[inline]
+    fn is_relocatable(&self) -> bool {
+        self.header.filetype(self.endian) == macho::MH_OBJECT
+    }
+
     fn segments(&'file self) -> MachOSegmentIterator<'data, 'file, Mach, R> {
         MachOSegmentIterator {
             file: self,
@@ -150,6 +319,7 @@ where
                 .load_commands(self.endian, self.data, self.header_offset)
                 .ok()
                 .unwrap_or_else(Default::default),
+            sections: &self.sections,
         }
     }
 
@@ -157,32 +327,17 @@ where
         &'file self,
         section_name: &str,
     ) -> Option<MachOSection<'data, 'file, Mach, R>> {
-        // Translate the "." prefix to the "__" prefix used by OSX/Mach-O, eg
-        // ".debug_info" to "__debug_info", and limit to 16 bytes total.
-        let system_name = if section_name.starts_with('.') {
-            if section_name.len() > 15 {
-                Some(&section_name[1..15])
-            } else {
-                Some(&section_name[1..])
-            }
-        } else {
-            None
-        };
-        let cmp_section_name = |section: &MachOSection<'data, 'file, Mach, R>| {
-            section
-                .name()
-                .map(|name| {
-                    section_name == name
-                        || system_name
-                            .filter(|system_name| {
-                                name.starts_with("__") && name[2..] == **system_name
-                            })
-                            .is_some()
-                })
-                .unwrap_or(false)
-        };
+        self.sections()
+            .find(|section| section_name_matches(section, section_name))
+    }
 
-        self.sections().find(cmp_section_name)
+    fn sections_by_name(
+        &'file self,
+        section_name: &str,
+    ) -> Vec<MachOSection<'data, 'file, Mach, R>> {
+        self.sections()
+            .filter(|section| section_name_matches(section, section_name))
+            .collect()
     }
 
     fn section_by_index(
@@ -203,6 +358,10 @@ where
         }
     }
 
+    fn cstr_at(&'file self, address: u64) -> Option<&'data [u8]> {
+        util::indexed_cstr_at(self, &self.address_index, address)
+    }
+
     fn comdats(&'file self) -> MachOComdatIterator<'data, 'file, Mach, R> {
         MachOComdatIterator { file: self }
     }
@@ -317,6 +476,78 @@ where
         Ok(exports)
     }
 
+    fn exported_symbols(&self) -> Result<Vec<ExportedSymbol<'data>>> {
+        let mut trie = self.linkedit_data(macho::LC_DYLD_EXPORTS_TRIE)?;
+        if trie.is_none() {
+            let mut commands =
+                self.header
+                    .load_commands(self.endian, self.data, self.header_offset)?;
+            while let Some(command) = commands.next()? {
+                if let Some(command) = command.dyld_info()? {
+                    let size = command.export_size.get(self.endian);
+                    if size != 0 {
+                        trie = Some(
+                            self.data
+                                .read_bytes_at(
+                                    command.export_off.get(self.endian).into(),
+                                    size.into(),
+                                )
+                                .read_error("Invalid Mach-O export trie offset or size")?,
+                        );
+                    }
+                    break;
+                }
+            }
+        }
+        if let Some(trie) = trie {
+            return super::exports::export_trie(trie, self.preferred_load_address());
+        }
+
+        // Fall back to the external symbols in the symbol table.
+        let mut exports = Vec::new();
+        for symbol in self.symbols.iter() {
+            if symbol.is_stab() || symbol.is_undefined() {
+                continue;
+            }
+            let n_type = symbol.n_type();
+            if n_type & macho::N_EXT == 0 || n_type & macho::N_PEXT != 0 {
+                continue;
+            }
+            let name = symbol.name(self.endian, self.symbols.strings())?;
+            exports.push(ExportedSymbol {
+                name: Cow::Borrowed(name),
+                address: symbol.n_value(self.endian).into(),
+                is_weak: symbol.n_desc(self.endian) & macho::N_WEAK_DEF != 0,
+            });
+        }
+        Ok(exports)
+    }
+
+    fn init_functions(&'file self) -> Result<Vec<u64>> {
+        let mut functions = read::init_function_pointers(self)?;
+        if !functions.is_empty() && !self.is_relocatable() {
+            // The pointers may be encoded as chained fixups. If the fixups can't
+            // be parsed, such as for unsupported pointer formats, then fall back
+            // to the raw section values.
+            if let Ok(mut pointers) = self.chained_fixups() {
+                pointers.sort_by_key(|&(address, _)| address);
+                for function in &mut functions {
+                    if let Ok(index) =
+                        pointers.binary_search_by_key(&function.0, |&(address, _)| address)
+                    {
+                        if let MachOPointer::Rebase(target) = pointers[index].1 {
+                            function.1 = target;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(functions
+            .into_iter()
+            .map(|(_, function)| function)
+            .collect())
+    }
+
     #[inline]
     fn dynamic_relocations(&'file self) -> Option<NoDynamicRelocationIterator> {
         None
@@ -330,10 +561,66 @@ where
         self.header.uuid(self.endian, self.data, self.header_offset)
     }
 
+    fn compiler_info(&self) -> Vec<String> {
+        let mut tools = Vec::new();
+        if let Ok(mut commands) =
+            self.header
+                .load_commands(self.endian, self.data, self.header_offset)
+        {
+            while let Ok(Some(command)) = commands.next() {
+                if let Ok(Some((_, versions))) = command.build_version(self.endian) {
+                    for version in versions {
+                        let name = match version.tool.get(self.endian) {
+                            macho::TOOL_CLANG => "clang".into(),
+                            macho::TOOL_SWIFT => "swift".into(),
+                            macho::TOOL_LD => "ld".into(),
+                            tool => format!("tool {}", tool),
+                        };
+                        let version = version.version.get(self.endian);
+                        tools.push(format!(
+                            "{} {}.{}.{}",
+                            name,
+                            version >> 16,
+                            (version >> 8) & 0xff,
+                            version & 0xff
+                        ));
+                    }
+                }
+            }
+        }
+        tools
+    }
+
     fn relative_address_base(&self) -> u64 {
         0
     }
 
+    fn address_range(&'file self) -> (u64, u64) {
+        if self.header.filetype(self.endian) == macho::MH_OBJECT {
+            return (0, 0);
+        }
+        read::util::segments_address_range(
+            self.segments().filter(|segment| segment.is_accessible()),
+        )
+    }
+
+    fn contains_address(&'file self, address: u64) -> bool {
+        if self.is_relocatable() {
+            return false;
+        }
+        self.segments().any(|segment| {
+            segment.is_accessible()
+                && address >= segment.address()
+                && address - segment.address() < segment.size()
+        })
+    }
+
+    fn preferred_load_address(&'file self) -> u64 {
+        self.segment_by_name("__TEXT")
+            .map(|segment| segment.address())
+            .unwrap_or(0)
+    }
+
     fn entry(&self) -> u64 {
         if let Ok(mut commands) =
             self.header
@@ -355,6 +642,79 @@ where
     }
 }
 
+/// An embedded Mach-O image in a `MH_FILESET` file.
+///
+/// Returned by `MachOFile::fileset_entries`.
+#[derive(Debug, Clone, Copy)]
+pub struct MachOFilesetEntry<'data, R = &'data [u8]>
+where
+    R: ReadRef<'data>,
+{
+    data: R,
+    name: &'data [u8],
+    address: u64,
+    file_offset: u64,
+}
+
+impl<'data, R: ReadRef<'data>> MachOFilesetEntry<'data, R> {
+    /// The identifier of the entry, such as the bundle identifier of a kernel extension.
+    #[inline]
+    pub fn name(&self) -> &'data [u8] {
+        self.name
+    }
+
+    /// The virtual address of the entry.
+    #[inline]
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// The offset in the fileset where the Mach-O header of the entry starts.
+    #[inline]
+    pub fn file_offset(&self) -> u64 {
+        self.file_offset
+    }
+
+    /// Parse this entry into an Object.
+    pub fn parse_object(&self) -> Result<read::File<'data, R>> {
+        read::File::parse_at(self.data, self.file_offset)
+    }
+}
+
+/// Return true if the section matches a name given to `Object::section_by_name`.
+///
+/// Names starting with "." are translated to the "__" prefix used by Mach-O,
+/// for example ".debug_info" to "__debug_info", and limited to 16 bytes total.
+fn section_name_matches<'data, 'file, Mach, R>(
+    section: &MachOSection<'data, 'file, Mach, R>,
+    section_name: &str,
+) -> bool
+where
+    Mach: MachHeader,
+    R: ReadRef<'data>,
+{
+    let name = match section.name() {
+        Ok(name) => name,
+        Err(_) => return false,
+    };
+    // `.rodata` has a different name, and `__const` is also used in other segments.
+    if section_name == ".rodata" {
+        return name == "__const" && section.segment_name() == Ok(Some("__TEXT"));
+    }
+    if section_name == name {
+        return true;
+    }
+    if section_name.starts_with('.') && name.starts_with("__") {
+        let system_name = if section_name.len() > 15 {
+            &section_name[1..15]
+        } else {
+            &section_name[1..]
+        };
+        return name[2..] == *system_name;
+    }
+    false
+}
+
 /// An iterator over the COMDAT section groups of a `MachOFile64`.
 pub type MachOComdatIterator32<'data, 'file, Endian = Endianness, R = &'data [u8]> =
     MachOComdatIterator<'data, 'file, macho::MachHeader32<Endian>, R>;
@@ -0,0 +1,293 @@
+use alloc::vec::Vec;
+
+use crate::endian::{Endian, U64Bytes, U16, U32};
+use crate::macho;
+use crate::pod::Bytes;
+use crate::read::{Error, ReadError, ReadRef, Result};
+
+/// A pointer in a Mach-O file that is fixed up by the dynamic linker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MachOPointer<'data> {
+    /// The pointer is set to the given address, adjusted by the slide of the image.
+    Rebase(u64),
+    /// The pointer is set to the address of an imported symbol plus an addend.
+    Bind {
+        /// The name of the imported symbol.
+        name: &'data [u8],
+        /// The addend to add to the address of the symbol.
+        addend: i64,
+    },
+}
+
+/// Parse the chain data from an `LC_DYLD_CHAINED_FIXUPS` command and
+/// walk the chains of pointers in each segment.
+///
+/// `segments` contains the address and file offset of each segment, in the order
+/// of the load commands. `base` is the address of the Mach-O header in memory.
+/// If `range` is given, then only the pages that overlap the address range
+/// `start..end` are walked, and only the pointers within that range are returned.
+///
+/// Returns the address and value of each fixed up pointer.
+pub(super) fn chained_fixups<'data, E: Endian, R: ReadRef<'data>>(
+    endian: E,
+    data: R,
+    fixups: &'data [u8],
+    segments: &[(u64, u64)],
+    base: u64,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<(u64, MachOPointer<'data>)>> {
+    let fixups = Bytes(fixups);
+    let header = fixups
+        .read_at::<macho::DyldChainedFixupsHeader<E>>(0)
+        .read_error("Invalid Mach-O chained fixups header size or alignment")?;
+    if header.symbols_format.get(endian) != 0 {
+        return Err(Error("Unsupported Mach-O chained fixups symbol format"));
+    }
+    let imports = chained_imports(endian, fixups, header)?;
+
+    let starts_offset = header.starts_offset.get(endian) as usize;
+    let starts_data = Bytes(fixups.0.get(starts_offset..).unwrap_or(&[]));
+    let mut seg_offsets = starts_data;
+    let seg_count = seg_offsets
+        .read::<macho::DyldChainedStartsInImage<E>>()
+        .read_error("Invalid Mach-O chained starts offset")?
+        .seg_count
+        .get(endian);
+    let seg_offsets = seg_offsets
+        .read_slice::<U32<E>>(seg_count as usize)
+        .read_error("Invalid Mach-O chained starts segment count")?;
+
+    let mut pointers = Vec::new();
+    for (seg_offset, &(address, file_offset)) in seg_offsets.iter().zip(segments.iter()) {
+        let seg_offset = seg_offset.get(endian) as usize;
+        if seg_offset == 0 {
+            continue;
+        }
+        let mut starts_data = starts_data;
+        starts_data
+            .skip(seg_offset)
+            .read_error("Invalid Mach-O chained starts segment offset")?;
+        let starts = starts_data
+            .read::<macho::DyldChainedStartsInSegment<E>>()
+            .read_error("Invalid Mach-O chained starts segment offset")?;
+        let page_count = starts_data
+            .read::<U16<E>>()
+            .read_error("Invalid Mach-O chained starts segment size")?
+            .get(endian);
+        let page_starts = starts_data
+            .read_slice::<U16<E>>(page_count as usize)
+            .read_error("Invalid Mach-O chained starts page count")?;
+        let page_size = u64::from(starts.page_size.get(endian));
+        let overlaps = |start: u64, size: u64| match range {
+            Some((range_start, range_end)) => {
+                start < range_end && start.wrapping_add(size) > range_start
+            }
+            None => true,
+        };
+        if !overlaps(address, page_count as u64 * page_size) {
+            continue;
+        }
+
+        let pointer_format = starts.pointer_format.get(endian);
+        match pointer_format {
+            macho::DYLD_CHAINED_PTR_64 | macho::DYLD_CHAINED_PTR_64_OFFSET => {}
+            _ => return Err(Error("Unsupported Mach-O chained pointer format")),
+        }
+
+        for (page_index, page_start) in page_starts.iter().enumerate() {
+            let page_start = page_start.get(endian);
+            if page_start == macho::DYLD_CHAINED_PTR_START_NONE
+                || !overlaps(
+                    address.wrapping_add(page_index as u64 * page_size),
+                    page_size,
+                )
+            {
+                continue;
+            }
+            if page_start & macho::DYLD_CHAINED_PTR_START_MULTI != 0 {
+                return Err(Error("Unsupported Mach-O chained page start"));
+            }
+            let mut offset = page_index as u64 * page_size + u64::from(page_start);
+            loop {
+                let raw = data
+                    .read_at::<U64Bytes<E>>(file_offset.wrapping_add(offset))
+                    .read_error("Invalid Mach-O chained pointer offset")?
+                    .get(endian);
+                let pointer = if raw >> 63 != 0 {
+                    let ordinal = (raw & 0xff_ffff) as usize;
+                    let (name, addend) = *imports
+                        .get(ordinal)
+                        .read_error("Invalid Mach-O chained import ordinal")?;
+                    MachOPointer::Bind {
+                        name,
+                        addend: addend.wrapping_add(((raw >> 24) & 0xff) as i64),
+                    }
+                } else {
+                    let target = (raw & 0xf_ffff_ffff) | ((raw >> 36) & 0xff) << 56;
+                    if pointer_format == macho::DYLD_CHAINED_PTR_64_OFFSET {
+                        MachOPointer::Rebase(base.wrapping_add(target))
+                    } else {
+                        MachOPointer::Rebase(target)
+                    }
+                };
+                let pointer_address = address.wrapping_add(offset);
+                if overlaps(pointer_address, 1) {
+                    pointers.push((pointer_address, pointer));
+                }
+
+                // The stride is 4 bytes for both of the supported formats.
+                let next = (raw >> 51) & 0xfff;
+                if next == 0 {
+                    break;
+                }
+                offset += next * 4;
+            }
+        }
+    }
+    Ok(pointers)
+}
+
+/// Parse the imports table of the chained fixups.
+///
+/// Returns the name and addend of each import.
+fn chained_imports<'data, E: Endian>(
+    endian: E,
+    fixups: Bytes<'data>,
+    header: &macho::DyldChainedFixupsHeader<E>,
+) -> Result<Vec<(&'data [u8], i64)>> {
+    let symbols_offset = header.symbols_offset.get(endian) as usize;
+    let name = |name_offset: u64| -> Result<&'data [u8]> {
+        fixups
+            .read_string_at(symbols_offset.wrapping_add(name_offset as usize))
+            .read_error("Invalid Mach-O chained import name offset")
+    };
+
+    let mut data = fixups;
+    data.skip(header.imports_offset.get(endian) as usize)
+        .read_error("Invalid Mach-O chained imports offset")?;
+    let count = header.imports_count.get(endian) as usize;
+    let mut imports = Vec::new();
+    match header.imports_format.get(endian) {
+        macho::DYLD_CHAINED_IMPORT => {
+            for import in data
+                .read_slice::<macho::DyldChainedImport<E>>(count)
+                .read_error("Invalid Mach-O chained imports count")?
+            {
+                let import = import.import.get(endian);
+                imports.push((name(u64::from(import >> 9))?, 0));
+            }
+        }
+        macho::DYLD_CHAINED_IMPORT_ADDEND => {
+            for import in data
+                .read_slice::<macho::DyldChainedImportAddend<E>>(count)
+                .read_error("Invalid Mach-O chained imports count")?
+            {
+                let addend = i64::from(import.addend.get(endian));
+                let import = import.import.get(endian);
+                imports.push((name(u64::from(import >> 9))?, addend));
+            }
+        }
+        macho::DYLD_CHAINED_IMPORT_ADDEND64 => {
+            for import in data
+                .read_slice::<macho::DyldChainedImportAddend64<E>>(count)
+                .read_error("Invalid Mach-O chained imports count")?
+            {
+                let addend = import.addend.get(endian) as i64;
+                let import = import.import.get(endian);
+                imports.push((name(import >> 32)?, addend));
+            }
+        }
+        _ => return Err(Error("Unsupported Mach-O chained imports format")),
+    }
+    Ok(imports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endian::LittleEndian as LE;
+
+    #[test]
+    fn chained_pointers() {
+        // Header.
+        let mut fixups = Vec::new();
+        for value in &[0u32, 28, 64, 72, 2, macho::DYLD_CHAINED_IMPORT, 0] {
+            fixups.extend_from_slice(&value.to_le_bytes());
+        }
+        // Starts in image: two segments, only the second has fixups.
+        for value in &[2u32, 0, 12] {
+            fixups.extend_from_slice(&value.to_le_bytes());
+        }
+        // Starts in segment: one page with a chain starting at offset 8.
+        fixups.extend_from_slice(&24u32.to_le_bytes());
+        fixups.extend_from_slice(&0x1000u16.to_le_bytes());
+        fixups.extend_from_slice(&macho::DYLD_CHAINED_PTR_64_OFFSET.to_le_bytes());
+        fixups.extend_from_slice(&0x4000u64.to_le_bytes());
+        fixups.extend_from_slice(&0u32.to_le_bytes());
+        fixups.extend_from_slice(&1u16.to_le_bytes());
+        fixups.extend_from_slice(&8u16.to_le_bytes());
+        assert_eq!(fixups.len(), 64);
+        // Imports.
+        fixups.extend_from_slice(&(1u32).to_le_bytes());
+        fixups.extend_from_slice(&(2u32 | 5 << 9).to_le_bytes());
+        assert_eq!(fixups.len(), 72);
+        fixups.extend_from_slice(b"_foo\0_bar\0");
+
+        let mut data = vec![0u8; 0x20];
+        // Rebase to offset 0x1234, next is 2 strides.
+        let rebase = 0x1234u64 | 2 << 51;
+        data[0x08..0x10].copy_from_slice(&rebase.to_le_bytes());
+        // Bind to import 1 with addend 3, next is 2 strides.
+        let bind = 1u64 << 63 | 2 << 51 | 3 << 24 | 1;
+        data[0x10..0x18].copy_from_slice(&bind.to_le_bytes());
+        // Bind to import 0 at the end of the chain.
+        let bind = 1u64 << 63;
+        data[0x18..].copy_from_slice(&bind.to_le_bytes());
+
+        let segments = [(0x1_0000_0000, 0x100), (0x1_0000_4000, 0)];
+        let pointers = chained_fixups(LE, &*data, &fixups, &segments, 0x1_0000_0000, None).unwrap();
+        assert_eq!(
+            pointers,
+            [
+                (0x1_0000_4008, MachOPointer::Rebase(0x1_0000_1234)),
+                (
+                    0x1_0000_4010,
+                    MachOPointer::Bind {
+                        name: b"_bar",
+                        addend: 3
+                    }
+                ),
+                (
+                    0x1_0000_4018,
+                    MachOPointer::Bind {
+                        name: b"_foo",
+                        addend: 0
+                    }
+                ),
+            ]
+        );
+
+        // Only the pointers in the range are returned.
+        let range = Some((0x1_0000_4010, 0x1_0000_4018));
+        let pointers = chained_fixups(LE, &*data, &fixups, &segments, 0x1_0000_0000, range);
+        assert_eq!(
+            pointers.unwrap(),
+            [(
+                0x1_0000_4010,
+                MachOPointer::Bind {
+                    name: b"_bar",
+                    addend: 3
+                }
+            )]
+        );
+
+        // An import ordinal that is out of range.
+        data[0x10] = 2;
+        assert!(chained_fixups(LE, &*data, &fixups, &segments, 0x1_0000_0000, None).is_err());
+
+        // Chains in pages outside of the range are not walked.
+        let range = Some((0x1_0000_0000, 0x1_0000_4000));
+        let pointers = chained_fixups(LE, &*data, &fixups, &segments, 0x1_0000_0000, range);
+        assert_eq!(pointers.unwrap(), []);
+    }
+}
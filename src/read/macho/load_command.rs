@@ -221,6 +221,47 @@ impl<'data, E: Endian> LoadCommandData<'data, E> {
         }
     }
 
+    /// Try to parse this command as a `BuildVersionCommand`.
+    ///
+    /// Returns the build version command and the tool versions that follow it.
+    pub fn build_version(
+        self,
+        endian: E,
+    ) -> Result<
+        Option<(
+            &'data macho::BuildVersionCommand<E>,
+            &'data [macho::BuildToolVersion<E>],
+        )>,
+    > {
+        if self.cmd == macho::LC_BUILD_VERSION {
+            let mut data = self.data;
+            let command: &macho::BuildVersionCommand<E> =
+                data.read().read_error("Invalid Mach-O command size")?;
+            let tools = data
+                .read_slice(command.ntools.get(endian) as usize)
+                .read_error("Invalid Mach-O build version tool count")?;
+            Ok(Some((command, tools)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Try to parse this command as a `FilesetEntryCommand`.
+    ///
+    /// Returns the fileset entry command and the entry id.
+    pub fn fileset_entry(
+        self,
+        endian: E,
+    ) -> Result<Option<(&'data macho::FilesetEntryCommand<E>, &'data [u8])>> {
+        if self.cmd == macho::LC_FILESET_ENTRY {
+            let command: &macho::FilesetEntryCommand<E> = self.data()?;
+            let entry_id = self.string(endian, command.entry_id)?;
+            Ok(Some((command, entry_id)))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Try to parse this command as a `DyldInfoCommand`.
     pub fn dyld_info(self) -> Result<Option<&'data macho::DyldInfoCommand<E>>> {
         if self.cmd == macho::LC_DYLD_INFO || self.cmd == macho::LC_DYLD_INFO_ONLY {
@@ -8,12 +8,17 @@
 mod dyld_cache;
 pub use dyld_cache::*;
 
+mod exports;
+
 mod fat;
 pub use fat::*;
 
 mod file;
 pub use file::*;
 
+mod fixups;
+pub use fixups::*;
+
 mod load_command;
 pub use load_command::*;
 
@@ -3,8 +3,8 @@ use core::{fmt, slice};
 use crate::endian::Endianness;
 use crate::macho;
 use crate::read::{
-    ReadRef, Relocation, RelocationEncoding, RelocationKind, RelocationTarget, SectionIndex,
-    SymbolIndex,
+    ReadRef, Relocation, RelocationEncoding, RelocationFlags, RelocationKind, RelocationTarget,
+    SectionIndex, SymbolIndex,
 };
 
 use super::{MachHeader, MachOFile};
@@ -109,6 +109,11 @@ where
                     target,
                     addend,
                     implicit_addend: true,
+                    flags: RelocationFlags::MachO {
+                        r_type: reloc.r_type,
+                        r_pcrel: reloc.r_pcrel,
+                        r_length: reloc.r_length,
+                    },
                 },
             ));
         }
@@ -1,7 +1,9 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
 use core::fmt::Debug;
 use core::{fmt, result, slice, str};
 
-use crate::endian::{self, Endianness};
+use crate::endian::{self, Endian, Endianness};
 use crate::macho;
 use crate::pod::Pod;
 use crate::read::{
@@ -9,7 +11,7 @@ use crate::read::{
     SectionFlags, SectionIndex, SectionKind,
 };
 
-use super::{MachHeader, MachOFile, MachORelocationIterator};
+use super::{MachHeader, MachOFile, MachOPointer, MachORelocationIterator, Nlist};
 
 /// An iterator over the sections of a `MachOFile32`.
 pub type MachOSectionIterator32<'data, 'file, Endian = Endianness, R = &'data [u8]> =
@@ -85,6 +87,90 @@ where
             .data(self.file.endian, self.file.data)
             .read_error("Invalid Mach-O section size or offset")
     }
+
+    /// Return the pointers in this section that are fixed up by chained fixups.
+    ///
+    /// This is useful for sections such as `__DATA_CONST,__const` and `__DATA,__got`,
+    /// whose file contents are encoded chains instead of the final pointer values.
+    ///
+    /// See `MachOFile::chained_fixups` for the supported formats.
+    pub fn pointers(&self) -> Result<Vec<(u64, MachOPointer<'data>)>> {
+        let start = self.address();
+        let end = start.wrapping_add(self.size());
+        self.file.chained_fixups_in_range(Some((start, end)))
+    }
+
+    /// Split the section data at the addresses of the symbols defined in it.
+    ///
+    /// This models how the linker treats sections in files that have the
+    /// `MH_SUBSECTIONS_VIA_SYMBOLS` header flag set, which allows each atom to be
+    /// dead stripped separately.
+    ///
+    /// The atoms are returned in address order. If multiple symbols have the same
+    /// address, then only the first is used. Any data before the first symbol is
+    /// returned as an atom with an empty symbol name. Atoms in zerofill sections
+    /// have empty data.
+    pub fn atoms(&self) -> Result<Vec<MachOAtom<'data>>> {
+        let endian = self.file.endian;
+        let data = self.bytes()?;
+        let start = self.address();
+        let end = start.wrapping_add(self.size());
+
+        let mut symbols = Vec::new();
+        for nlist in self.file.symbols.iter() {
+            if nlist.is_stab()
+                || nlist.n_type() & macho::N_TYPE != macho::N_SECT
+                || usize::from(nlist.n_sect()) != self.internal.index.0
+            {
+                continue;
+            }
+            let address = nlist.n_value(endian).into();
+            if address < start || address >= end {
+                continue;
+            }
+            let name = nlist.name(endian, self.file.symbols.strings())?;
+            let name = str::from_utf8(name)
+                .ok()
+                .read_error("Non UTF-8 Mach-O symbol name")?;
+            symbols.push((address, name));
+        }
+        symbols.sort_by_key(|&(address, _)| address);
+        symbols.dedup_by_key(|&mut (address, _)| address);
+        if symbols
+            .first()
+            .map_or(start != end, |&(address, _)| address != start)
+        {
+            symbols.insert(0, (start, ""));
+        }
+
+        let mut atoms = Vec::with_capacity(symbols.len());
+        for (i, &(address, symbol)) in symbols.iter().enumerate() {
+            let next = symbols.get(i + 1).map_or(end, |&(address, _)| address);
+            let offset = (address - start) as usize;
+            let size = (next - address) as usize;
+            let data = data.get(offset..).unwrap_or(&[]);
+            let data = &data[..size.min(data.len())];
+            atoms.push(MachOAtom {
+                symbol,
+                address,
+                data,
+            });
+        }
+        Ok(atoms)
+    }
+}
+
+/// A range of a Mach-O section that starts at a symbol.
+///
+/// Returned by `MachOSection::atoms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MachOAtom<'data> {
+    /// The name of the symbol at the start of the atom.
+    pub symbol: &'data str,
+    /// The address of the atom.
+    pub address: u64,
+    /// The data of the atom.
+    pub data: &'data [u8],
 }
 
 impl<'data, 'file, Mach, R> read::private::Sealed for MachOSection<'data, 'file, Mach, R>
@@ -150,6 +236,10 @@ where
         self.data().map(CompressedData::none)
     }
 
+    fn relocated_data(&self) -> Result<Cow<'data, [u8]>> {
+        read::util::relocated_data(self.file, self, true)
+    }
+
     #[inline]
     fn name(&self) -> Result<&str> {
         str::from_utf8(self.internal.section.name())
@@ -170,6 +260,26 @@ where
         self.internal.kind
     }
 
+    fn is_stub(&self) -> bool {
+        self.internal.section.flags(self.file.endian) & macho::SECTION_TYPE == macho::S_SYMBOL_STUBS
+    }
+
+    fn endianness(&self) -> Endianness {
+        if self.file.endian.is_little_endian() {
+            Endianness::Little
+        } else {
+            Endianness::Big
+        }
+    }
+
+    fn relocation_count(&self) -> usize {
+        self.internal
+            .section
+            .relocations(self.file.endian, self.file.data)
+            .map(|relocations| relocations.len())
+            .unwrap_or(0)
+    }
+
     fn relocations(&self) -> MachORelocationIterator<'data, 'file, Mach, R> {
         MachORelocationIterator {
             file: self.file,
@@ -197,7 +307,11 @@ pub(super) struct MachOSectionInternal<'data, Mach: MachHeader> {
 }
 
 impl<'data, Mach: MachHeader> MachOSectionInternal<'data, Mach> {
-    pub(super) fn parse(index: SectionIndex, section: &'data Mach::Section) -> Self {
+    pub(super) fn parse(
+        endian: Mach::Endian,
+        index: SectionIndex,
+        section: &'data Mach::Section,
+    ) -> Self {
         // TODO: we don't validate flags, should we?
         let kind = match (section.segment_name(), section.name()) {
             (b"__TEXT", b"__text") => SectionKind::Text,
@@ -208,6 +322,8 @@ impl<'data, Mach: MachHeader> MachOSectionInternal<'data, Mach> {
             (b"__TEXT", b"__literal16") => SectionKind::ReadOnlyData,
             (b"__TEXT", b"__eh_frame") => SectionKind::ReadOnlyData,
             (b"__TEXT", b"__gcc_except_tab") => SectionKind::ReadOnlyData,
+            (b"__TEXT", b"__stubs") => SectionKind::Text,
+            (b"__TEXT", b"__stub_helper") => SectionKind::Text,
             (b"__DATA", b"__data") => SectionKind::Data,
             (b"__DATA", b"__const") => SectionKind::ReadOnlyData,
             (b"__DATA", b"__bss") => SectionKind::UninitializedData,
@@ -215,8 +331,21 @@ impl<'data, Mach: MachHeader> MachOSectionInternal<'data, Mach> {
             (b"__DATA", b"__thread_data") => SectionKind::Tls,
             (b"__DATA", b"__thread_bss") => SectionKind::UninitializedTls,
             (b"__DATA", b"__thread_vars") => SectionKind::TlsVariables,
+            (b"__DATA", b"__la_symbol_ptr") => SectionKind::Data,
+            (b"__DATA", b"__nl_symbol_ptr") => SectionKind::Data,
+            (b"__DATA", b"__got") => SectionKind::Data,
+            (b"__DATA_CONST", b"__got") => SectionKind::Data,
             (b"__DWARF", _) => SectionKind::Debug,
-            _ => SectionKind::Unknown,
+            (segname, sectname) if is_objc_section(segname, sectname) => SectionKind::Metadata,
+            // Classify the remaining sections using the section type, which
+            // handles stub and pointer sections with nonstandard names.
+            _ => match section.flags(endian) & macho::SECTION_TYPE {
+                macho::S_SYMBOL_STUBS => SectionKind::Text,
+                macho::S_NON_LAZY_SYMBOL_POINTERS
+                | macho::S_LAZY_SYMBOL_POINTERS
+                | macho::S_LAZY_DYLIB_SYMBOL_POINTERS => SectionKind::Data,
+                _ => SectionKind::Unknown,
+            },
         };
         MachOSectionInternal {
             index,
@@ -226,6 +355,18 @@ impl<'data, Mach: MachHeader> MachOSectionInternal<'data, Mach> {
     }
 }
 
+/// Return true if the section contains Objective-C runtime metadata.
+///
+/// This matches the `__objc_*` sections in the data segments, and all sections
+/// in the legacy `__OBJC` segment.
+pub(super) fn is_objc_section(segname: &[u8], sectname: &[u8]) -> bool {
+    match segname {
+        b"__DATA" | b"__DATA_CONST" | b"__DATA_DIRTY" => sectname.starts_with(b"__objc_"),
+        b"__OBJC" => true,
+        _ => false,
+    }
+}
+
 /// A trait for generic access to `Section32` and `Section64`.
 #[allow(missing_docs)]
 pub trait Section: Debug + Pod {
@@ -1,12 +1,15 @@
 use core::fmt::Debug;
-use core::{result, str};
+use core::{cmp, result, str};
 
 use crate::endian::{self, Endianness};
 use crate::macho;
 use crate::pod::Pod;
-use crate::read::{self, ObjectSegment, ReadError, ReadRef, Result};
+use crate::read::{self, ObjectSegment, ReadError, ReadRef, Result, SegmentFlags};
 
-use super::{LoadCommandData, LoadCommandIterator, MachHeader, MachOFile, Section};
+use super::{
+    LoadCommandData, LoadCommandIterator, MachHeader, MachOFile, MachOSectionInternal,
+    MachOSectionIterator, Section,
+};
 
 /// An iterator over the segments of a `MachOFile32`.
 pub type MachOSegmentIterator32<'data, 'file, Endian = Endianness, R = &'data [u8]> =
@@ -25,6 +28,8 @@ where
 {
     pub(super) file: &'file MachOFile<'data, Mach, R>,
     pub(super) commands: LoadCommandIterator<'data, Mach::Endian>,
+    /// The sections that have not yet been assigned to a segment.
+    pub(super) sections: &'file [MachOSectionInternal<'data, Mach>],
 }
 
 impl<'data, 'file, Mach, R> Iterator for MachOSegmentIterator<'data, 'file, Mach, R>
@@ -38,9 +43,14 @@ where
         loop {
             let command = self.commands.next().ok()??;
             if let Ok(Some((segment, _))) = Mach::Segment::from_command(command) {
+                let nsects = segment.nsects(self.file.endian) as usize;
+                let count = cmp::min(nsects, self.sections.len());
+                let (sections, rest) = self.sections.split_at(count);
+                self.sections = rest;
                 return Some(MachOSegment {
                     file: self.file,
                     segment,
+                    sections,
                 });
             }
         }
@@ -64,6 +74,7 @@ where
 {
     file: &'file MachOFile<'data, Mach, R>,
     segment: &'data Mach::Segment,
+    sections: &'file [MachOSectionInternal<'data, Mach>],
 }
 
 impl<'data, 'file, Mach, R> MachOSegment<'data, 'file, Mach, R>
@@ -76,6 +87,26 @@ where
             .data(self.file.endian, self.file.data)
             .read_error("Invalid Mach-O segment size or offset")
     }
+
+    /// Return true if the segment maps memory that can be accessed.
+    ///
+    /// This is false for segments with no initial access permissions, such as the
+    /// `__PAGEZERO` segment of an executable, which reserves the low addresses so
+    /// that null pointer dereferences fault.
+    pub(super) fn is_accessible(&self) -> bool {
+        let endian = self.file.endian;
+        let vmaddr: u64 = self.segment.vmaddr(endian).into();
+        let filesize: u64 = self.segment.filesize(endian).into();
+        self.segment.initprot(endian) != 0 && (vmaddr != 0 || filesize != 0)
+    }
+
+    /// Return an iterator over the sections that are defined in this segment's load command.
+    pub fn sections(&self) -> MachOSectionIterator<'data, 'file, Mach, R> {
+        MachOSectionIterator {
+            file: self.file,
+            iter: self.sections.iter(),
+        }
+    }
 }
 
 impl<'data, 'file, Mach, R> read::private::Sealed for MachOSegment<'data, 'file, Mach, R>
@@ -132,6 +163,18 @@ where
                 .read_error("Non UTF-8 Mach-O segment name")?,
         ))
     }
+
+    #[inline]
+    fn flags(&self) -> SegmentFlags {
+        let flags = self.segment.flags(self.file.endian);
+        let maxprot = self.segment.maxprot(self.file.endian);
+        let initprot = self.segment.initprot(self.file.endian);
+        SegmentFlags::MachO {
+            flags,
+            maxprot,
+            initprot,
+        }
+    }
 }
 
 /// A trait for generic access to `SegmentCommand32` and `SegmentCommand64`.
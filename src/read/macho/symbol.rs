@@ -86,6 +86,28 @@ impl<'data, Mach: MachHeader> SymbolTable<'data, Mach> {
         SymbolMap::new(symbols)
     }
 
+    /// Return the STABS debugging entries in the symbol table.
+    ///
+    /// These are the entries that have a bit of `N_STAB` set in `n_type`.
+    /// They are not included in the symbols returned by the `Object` trait.
+    pub fn stabs(&self, endian: Mach::Endian) -> Result<Vec<MachOStab<'data>>> {
+        let mut stabs = Vec::new();
+        for nlist in self.symbols {
+            let n_type = nlist.n_type();
+            if n_type & macho::N_STAB == 0 {
+                continue;
+            }
+            stabs.push(MachOStab {
+                n_type,
+                n_sect: nlist.n_sect(),
+                n_desc: nlist.n_desc(endian),
+                n_value: nlist.n_value(endian).into(),
+                name: nlist.name(endian, self.strings)?,
+            });
+        }
+        Ok(stabs)
+    }
+
     /// Construct a map from addresses to symbol names and object file names.
     pub fn object_map(&self, endian: Mach::Endian) -> ObjectMap<'data> {
         let mut symbols = Vec::new();
@@ -140,6 +162,21 @@ impl<'data, Mach: MachHeader> SymbolTable<'data, Mach> {
     }
 }
 
+/// A STABS debugging entry in the symbol table of a Mach-O file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MachOStab<'data> {
+    /// The type of the entry. This is one of the `N_*` stab constants, such as `N_FUN`.
+    pub n_type: u8,
+    /// The section number, or `NO_SECT`.
+    pub n_sect: u8,
+    /// The description field. Its meaning depends on the type.
+    pub n_desc: u16,
+    /// The value of the entry. Its meaning depends on the type.
+    pub n_value: u64,
+    /// The name of the entry.
+    pub name: &'data [u8],
+}
+
 /// An iterator over the symbols of a `MachOFile32`.
 pub type MachOSymbolTable32<'data, 'file, Endian = Endianness, R = &'data [u8]> =
     MachOSymbolTable<'data, 'file, macho::MachHeader32<Endian>, R>;
@@ -193,6 +230,8 @@ pub type MachOSymbolIterator64<'data, 'file, Endian = Endianness, R = &'data [u8
     MachOSymbolIterator<'data, 'file, macho::MachHeader64<Endian>, R>;
 
 /// An iterator over the symbols of a `MachOFile`.
+///
+/// STABS debugging entries are skipped. Use `MachOFile::stabs` to access them.
 pub struct MachOSymbolIterator<'data, 'file, Mach, R = &'data [u8]>
 where
     Mach: MachHeader,
@@ -284,10 +323,13 @@ where
         self.index
     }
 
+    #[inline]
+    fn name_bytes(&self) -> Result<&'data [u8]> {
+        self.nlist.name(self.file.endian, self.file.symbols.strings)
+    }
+
     fn name(&self) -> Result<&'data str> {
-        let name = self
-            .nlist
-            .name(self.file.endian, self.file.symbols.strings)?;
+        let name = self.name_bytes()?;
         str::from_utf8(name)
             .ok()
             .read_error("Non UTF-8 Mach-O symbol name")
@@ -359,6 +401,16 @@ where
         self.nlist.n_desc(self.file.endian) & (macho::N_WEAK_REF | macho::N_WEAK_DEF) != 0
     }
 
+    #[inline]
+    fn is_ifunc(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn is_thumb(&self) -> bool {
+        self.nlist.n_desc(self.file.endian) & macho::N_ARM_THUMB_DEF != 0
+    }
+
     fn scope(&self) -> SymbolScope {
         let n_type = self.nlist.n_type();
         if n_type & macho::N_TYPE == macho::N_UNDF {
@@ -1,11 +1,13 @@
 //! Interface for reading object files.
 
 use alloc::borrow::Cow;
+use alloc::string::String;
 use alloc::vec::Vec;
+use core::convert::TryFrom;
 use core::{fmt, result};
 
 use crate::common::*;
-use crate::ByteString;
+use crate::{ByteString, Bytes};
 
 mod read_ref;
 pub use read_ref::*;
@@ -77,6 +79,44 @@ impl std::error::Error for Error {}
 /// The result type used within the read module.
 pub type Result<T> = result::Result<T, Error>;
 
+/// The error type returned by `File::parse_complete`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The file data could not be parsed.
+    Invalid(Error),
+    /// The file data is shorter than the file ranges of its sections and segments.
+    Truncated {
+        /// The required file size, as returned by `Object::required_file_size`.
+        expected: u64,
+        /// The size of the file data.
+        actual: u64,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Invalid(error) => error.fmt(f),
+            ParseError::Truncated { expected, actual } => write!(
+                f,
+                "File data is truncated: {} bytes are required, but only {} are present",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl From<Error> for ParseError {
+    #[inline]
+    fn from(error: Error) -> Self {
+        ParseError::Invalid(error)
+    }
+}
+
 trait ReadError<T> {
     fn read_error(self, error: &'static str) -> Result<T>;
 }
@@ -175,16 +215,21 @@ pub enum FileKind {
 
 impl FileKind {
     /// Determine a file kind by parsing the start of the file.
+    ///
+    /// This only checks the file magic (and for PE, the NT headers that the DOS
+    /// header points to), so it can be used to identify a file without parsing it.
     pub fn parse<'data, R: ReadRef<'data>>(data: R) -> Result<FileKind> {
         Self::parse_at(data, 0)
     }
 
     /// Determine a file kind by parsing at the given offset.
     pub fn parse_at<'data, R: ReadRef<'data>>(data: R, offset: u64) -> Result<FileKind> {
+        // Only the first 8 bytes are needed, which is also the size of
+        // the smallest valid Wasm module.
         let magic = data
-            .read_bytes_at(offset, 16)
+            .read_bytes_at(offset, 8)
             .read_error("Could not read file magic")?;
-        if magic.len() < 16 {
+        if magic.len() < 8 {
             return Err(Error("File too short"));
         }
 
@@ -238,10 +283,21 @@ impl FileKind {
 }
 
 /// The index used to identify a section of a file.
+///
+/// This is the index used by the file format, and so it may not start at 0.
+/// For example, Mach-O and COFF section indices start at 1, while index 0 is
+/// reserved for the null section in ELF.
+///
+/// Use `Object::section_by_index` to look up the section for an index.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SectionIndex(pub usize);
 
 /// The index used to identify a symbol of a file.
+///
+/// This is the index of the symbol in the file's symbol table, and is the value
+/// stored in `RelocationTarget::Symbol`.
+///
+/// Use `Object::symbol_by_index` to look up the symbol for an index.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SymbolIndex(pub usize);
 
@@ -260,6 +316,8 @@ pub enum SymbolSection {
     /// The symbol is a zero-initialized symbol that will be combined with duplicate definitions.
     Common,
     /// The symbol is defined in the given section.
+    ///
+    /// The index can be passed to `Object::section_by_index`.
     Section(SectionIndex),
 }
 
@@ -292,9 +350,10 @@ pub struct SymbolMap<T: SymbolMapEntry> {
 impl<T: SymbolMapEntry> SymbolMap<T> {
     /// Construct a new symbol map.
     ///
-    /// This function will sort the symbols by address.
+    /// This function will sort the symbols by address. The sort is stable, so
+    /// symbols with the same address keep their order.
     pub fn new(mut symbols: Vec<T>) -> Self {
-        symbols.sort_unstable_by_key(|s| s.address());
+        symbols.sort_by_key(|s| s.address());
         SymbolMap { symbols }
     }
 
@@ -430,7 +489,60 @@ impl<'data> SymbolMapEntry for ObjectMapEntry<'data> {
     }
 }
 
+/// A map from addresses to file data.
+///
+/// This is used to translate many virtual addresses to file data without
+/// searching the section table each time.
+#[derive(Debug, Default, Clone)]
+pub struct AddressMap<'data> {
+    ranges: Vec<(u64, &'data [u8])>,
+}
+
+impl<'data> AddressMap<'data> {
+    /// Construct a new address map from the start address and data of each range.
+    ///
+    /// Empty ranges are ignored. If ranges overlap, then the range with the lowest
+    /// address is used, and for ranges with the same address, the first one is used.
+    pub fn new(ranges: Vec<(u64, &'data [u8])>) -> Self {
+        let index = util::AddressIndex::new(
+            ranges
+                .iter()
+                .enumerate()
+                .map(|(i, &(address, data))| (address, data.len() as u64, i)),
+        );
+        let ranges = index
+            .iter()
+            .map(|(address, i)| (address, ranges[i].1))
+            .collect();
+        AddressMap { ranges }
+    }
+
+    /// Get the data from the given address to the end of the range containing it.
+    pub fn data_at(&self, address: u64) -> Option<&'data [u8]> {
+        let index = match self
+            .ranges
+            .binary_search_by_key(&address, |&(address, _)| address)
+        {
+            Ok(index) => index,
+            Err(index) => index.checked_sub(1)?,
+        };
+        let (start, data) = self.ranges[index];
+        let offset = usize::try_from(address - start).ok()?;
+        data.get(offset..).filter(|data| !data.is_empty())
+    }
+
+    /// Read a null terminated string at the given address.
+    ///
+    /// The string must be terminated before the end of the range containing the address.
+    /// Does not assume any encoding. Does not return the null byte.
+    pub fn cstr_at(&self, address: u64) -> Option<&'data [u8]> {
+        Bytes(self.data_at(address)?).read_string().ok()
+    }
+}
+
 /// An imported symbol.
+///
+/// Names are returned as bytes because they are not required to be valid UTF-8.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Import<'data> {
     // TODO: or ordinal
@@ -445,14 +557,28 @@ impl<'data> Import<'data> {
         self.name.0
     }
 
+    /// The symbol name, with any invalid UTF-8 replaced.
+    #[inline]
+    pub fn name_lossy(&self) -> Cow<'data, str> {
+        String::from_utf8_lossy(self.name.0)
+    }
+
     /// The name of the library to import the symbol from.
     #[inline]
     pub fn library(&self) -> &'data [u8] {
         self.library.0
     }
+
+    /// The name of the library to import the symbol from, with any invalid UTF-8 replaced.
+    #[inline]
+    pub fn library_lossy(&self) -> Cow<'data, str> {
+        String::from_utf8_lossy(self.library.0)
+    }
 }
 
 /// An exported symbol.
+///
+/// Names are returned as bytes because they are not required to be valid UTF-8.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Export<'data> {
     // TODO: and ordinal?
@@ -467,11 +593,178 @@ impl<'data> Export<'data> {
         self.name.0
     }
 
+    /// The symbol name, with any invalid UTF-8 replaced.
+    #[inline]
+    pub fn name_lossy(&self) -> Cow<'data, str> {
+        String::from_utf8_lossy(self.name.0)
+    }
+
+    /// The virtual address of the symbol.
+    #[inline]
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+}
+
+/// A symbol that is exported by a file for use by other files.
+///
+/// Unlike `Export`, this also includes the binding of the symbol, and the name
+/// may have been constructed from multiple pieces of the file data.
+///
+/// Names are returned as bytes because they are not required to be valid UTF-8.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExportedSymbol<'data> {
+    name: Cow<'data, [u8]>,
+    address: u64,
+    is_weak: bool,
+}
+
+impl<'data> ExportedSymbol<'data> {
+    /// The symbol name.
+    #[inline]
+    pub fn name(&self) -> &[u8] {
+        &self.name
+    }
+
+    /// The symbol name, with any invalid UTF-8 replaced.
+    #[inline]
+    pub fn name_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.name)
+    }
+
     /// The virtual address of the symbol.
     #[inline]
     pub fn address(&self) -> u64 {
         self.address
     }
+
+    /// Return true if the symbol is a weak definition.
+    #[inline]
+    pub fn is_weak(&self) -> bool {
+        self.is_weak
+    }
+}
+
+impl<'data> From<Export<'data>> for ExportedSymbol<'data> {
+    fn from(export: Export<'data>) -> Self {
+        ExportedSymbol {
+            name: Cow::Borrowed(export.name.0),
+            address: export.address,
+            is_weak: false,
+        }
+    }
+}
+
+impl<'data> fmt::Debug for ExportedSymbol<'data> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExportedSymbol")
+            .field("name", &ByteString(&self.name))
+            .field("address", &self.address)
+            .field("is_weak", &self.is_weak)
+            .finish()
+    }
+}
+
+/// A group of sections that a linker combines into a single section.
+///
+/// Returned by `Object::grouped_sections`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct GroupedSection<'data> {
+    name: String,
+    sections: Vec<SectionIndex>,
+    data: Cow<'data, [u8]>,
+}
+
+impl<'data> GroupedSection<'data> {
+    /// The name of the combined section.
+    ///
+    /// For COFF grouped sections, this does not include the `$` or the suffix.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The indices of the sections in the group, in the order that they are combined.
+    #[inline]
+    pub fn sections(&self) -> &[SectionIndex] {
+        &self.sections
+    }
+
+    /// The combined data of the sections in the group.
+    ///
+    /// The data of each section is padded to the alignment of the next section.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'data> fmt::Debug for GroupedSection<'data> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupedSection")
+            .field("name", &self.name)
+            .field("sections", &self.sections)
+            .field("size", &self.data.len())
+            .finish()
+    }
+}
+
+/// A copy of a symbol table entry that does not borrow from the file data.
+///
+/// Returned by `Object::owned_symbols`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedSymbol {
+    name: String,
+    address: u64,
+    size: u64,
+    kind: SymbolKind,
+    section: SymbolSection,
+    scope: SymbolScope,
+    is_weak: bool,
+}
+
+impl OwnedSymbol {
+    /// The name of the symbol.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The address of the symbol. May be zero if the address is unknown.
+    #[inline]
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// The size of the symbol. May be zero if the size is unknown.
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The kind of the symbol.
+    #[inline]
+    pub fn kind(&self) -> SymbolKind {
+        self.kind
+    }
+
+    /// The section where the symbol is defined.
+    #[inline]
+    pub fn section(&self) -> SymbolSection {
+        self.section
+    }
+
+    /// The scope of the symbol.
+    #[inline]
+    pub fn scope(&self) -> SymbolScope {
+        self.scope
+    }
+
+    /// Return true if the symbol is weak.
+    #[inline]
+    pub fn is_weak(&self) -> bool {
+        self.is_weak
+    }
 }
 
 /// PDB Information
@@ -502,6 +795,22 @@ impl<'data> CodeView<'data> {
     }
 }
 
+/// A problem found by `Object::validate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValidationWarning {
+    /// The file ranges of two sections overlap.
+    SectionOverlap(SectionIndex, SectionIndex),
+    /// The file range of a section extends past the end of the file.
+    SectionOutOfBounds(SectionIndex),
+    /// The file size of a segment is larger than its size in memory.
+    ///
+    /// The segment is identified by its position in `Object::segments`.
+    SegmentSizeMismatch(usize),
+    /// A symbol refers to a section that does not exist.
+    SymbolSectionInvalid(SymbolIndex, SectionIndex),
+}
+
 /// The target referenced by a relocation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
@@ -523,15 +832,25 @@ pub struct Relocation {
     target: RelocationTarget,
     addend: i64,
     implicit_addend: bool,
+    flags: RelocationFlags,
 }
 
 impl Relocation {
     /// The operation used to calculate the result of the relocation.
+    ///
+    /// This is normalized across file formats and architectures where possible.
+    /// Use `flags` to get the raw relocation type.
     #[inline]
     pub fn kind(&self) -> RelocationKind {
         self.kind
     }
 
+    /// The raw relocation fields that are specific to the file format and architecture.
+    #[inline]
+    pub fn flags(&self) -> RelocationFlags {
+        self.flags
+    }
+
     /// Information about how the result of the relocation operation is encoded in the place.
     #[inline]
     pub fn encoding(&self) -> RelocationEncoding {
@@ -572,6 +891,90 @@ impl Relocation {
     }
 }
 
+/// An identifier for a DWARF section.
+///
+/// The variants match the section identifiers used by `gimli`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DwarfSectionId {
+    /// The `.debug_abbrev` section.
+    DebugAbbrev,
+    /// The `.debug_addr` section.
+    DebugAddr,
+    /// The `.debug_aranges` section.
+    DebugAranges,
+    /// The `.debug_cu_index` section.
+    DebugCuIndex,
+    /// The `.debug_frame` section.
+    DebugFrame,
+    /// The `.eh_frame` section.
+    EhFrame,
+    /// The `.eh_frame_hdr` section.
+    EhFrameHdr,
+    /// The `.debug_info` section.
+    DebugInfo,
+    /// The `.debug_line` section.
+    DebugLine,
+    /// The `.debug_line_str` section.
+    DebugLineStr,
+    /// The `.debug_loc` section.
+    DebugLoc,
+    /// The `.debug_loclists` section.
+    DebugLocLists,
+    /// The `.debug_macinfo` section.
+    DebugMacinfo,
+    /// The `.debug_macro` section.
+    DebugMacro,
+    /// The `.debug_pubnames` section.
+    DebugPubNames,
+    /// The `.debug_pubtypes` section.
+    DebugPubTypes,
+    /// The `.debug_ranges` section.
+    DebugRanges,
+    /// The `.debug_rnglists` section.
+    DebugRngLists,
+    /// The `.debug_str` section.
+    DebugStr,
+    /// The `.debug_str_offsets` section.
+    DebugStrOffsets,
+    /// The `.debug_tu_index` section.
+    DebugTuIndex,
+    /// The `.debug_types` section.
+    DebugTypes,
+}
+
+impl DwarfSectionId {
+    /// Return the ELF section name for this section.
+    ///
+    /// `Object::section_by_name` translates this name for other file formats.
+    pub fn name(self) -> &'static str {
+        match self {
+            DwarfSectionId::DebugAbbrev => ".debug_abbrev",
+            DwarfSectionId::DebugAddr => ".debug_addr",
+            DwarfSectionId::DebugAranges => ".debug_aranges",
+            DwarfSectionId::DebugCuIndex => ".debug_cu_index",
+            DwarfSectionId::DebugFrame => ".debug_frame",
+            DwarfSectionId::EhFrame => ".eh_frame",
+            DwarfSectionId::EhFrameHdr => ".eh_frame_hdr",
+            DwarfSectionId::DebugInfo => ".debug_info",
+            DwarfSectionId::DebugLine => ".debug_line",
+            DwarfSectionId::DebugLineStr => ".debug_line_str",
+            DwarfSectionId::DebugLoc => ".debug_loc",
+            DwarfSectionId::DebugLocLists => ".debug_loclists",
+            DwarfSectionId::DebugMacinfo => ".debug_macinfo",
+            DwarfSectionId::DebugMacro => ".debug_macro",
+            DwarfSectionId::DebugPubNames => ".debug_pubnames",
+            DwarfSectionId::DebugPubTypes => ".debug_pubtypes",
+            DwarfSectionId::DebugRanges => ".debug_ranges",
+            DwarfSectionId::DebugRngLists => ".debug_rnglists",
+            DwarfSectionId::DebugStr => ".debug_str",
+            DwarfSectionId::DebugStrOffsets => ".debug_str_offsets",
+            DwarfSectionId::DebugTuIndex => ".debug_tu_index",
+            DwarfSectionId::DebugTypes => ".debug_types",
+        }
+    }
+}
+
 /// A data compression format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
@@ -584,6 +987,11 @@ pub enum CompressionFormat {
     ///
     /// Used for ELF compression and GNU compressed debug information.
     Zlib,
+    /// XZ.
+    ///
+    /// Used for the ELF `.gnu_debugdata` section. Decompressing this format
+    /// requires the `xz` feature.
+    Xz,
 }
 
 /// A range in a file that may be compressed.
@@ -658,8 +1066,9 @@ impl<'data> CompressedData<'data> {
     /// Return the uncompressed data.
     ///
     /// Returns an error for invalid data or unsupported compression.
-    /// This includes if the data is compressed but the `compression` feature
-    /// for this crate is disabled.
+    /// This includes if the data is compressed with ZLIB but the `compression`
+    /// feature for this crate is disabled, or compressed with XZ but the `xz`
+    /// feature is disabled.
     pub fn decompress(self) -> Result<Cow<'data, [u8]>> {
         match self.format {
             CompressionFormat::None => Ok(Cow::Borrowed(self.data)),
@@ -683,7 +1092,85 @@ impl<'data> CompressedData<'data> {
                     .read_error("Invalid zlib compressed data")?;
                 Ok(Cow::Owned(decompressed))
             }
+            #[cfg(feature = "xz")]
+            CompressionFormat::Xz => {
+                use core::convert::TryInto;
+                let size = self
+                    .uncompressed_size
+                    .try_into()
+                    .ok()
+                    .read_error("Uncompressed data size is too large.")?;
+                // `lzma_rs` does not support stream padding, so remove it first.
+                let mut data = self.data;
+                while data.len() >= 4 && data[data.len() - 4..] == [0; 4] {
+                    data = &data[..data.len() - 4];
+                }
+                let mut decompressed = Vec::with_capacity(size);
+                lzma_rs::xz_decompress(&mut data, &mut decompressed)
+                    .ok()
+                    .read_error("Invalid xz compressed data")?;
+                Ok(Cow::Owned(decompressed))
+            }
             _ => Err(Error("Unsupported compressed data.")),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use core::mem;
+
+    #[cfg(all(
+        feature = "archive",
+        feature = "coff",
+        feature = "elf",
+        feature = "macho",
+        feature = "pe"
+    ))]
+    #[test]
+    fn file_kind() {
+        fn kind(data: &[u8]) -> Option<FileKind> {
+            let mut padded = data.to_vec();
+            padded.resize(16, 0);
+            FileKind::parse(&*padded).ok()
+        }
+
+        assert_eq!(kind(b"!<arch>\n"), Some(FileKind::Archive));
+        assert_eq!(kind(b"\x7fELF\x01"), Some(FileKind::Elf32));
+        assert_eq!(kind(b"\x7fELF\x02"), Some(FileKind::Elf64));
+        assert_eq!(kind(&[0xce, 0xfa, 0xed, 0xfe]), Some(FileKind::MachO32));
+        assert_eq!(kind(&[0xfe, 0xed, 0xfa, 0xcf]), Some(FileKind::MachO64));
+        assert_eq!(kind(&[0xca, 0xfe, 0xba, 0xbe]), Some(FileKind::MachOFat32));
+        assert_eq!(kind(b"dyld_v1   arm64"), Some(FileKind::DyldCache));
+        assert_eq!(kind(&[0x64, 0x86]), Some(FileKind::Coff));
+        assert_eq!(kind(b"\x7fELF\x03"), None);
+        assert_eq!(kind(b"\0\0\0\0"), None);
+        assert!(FileKind::parse(&b"\x7fELF"[..]).is_err());
+
+        // A PE file is detected by following `e_lfanew` to the NT headers.
+        let mut pe = Vec::new();
+        pe.resize(0x40 + mem::size_of::<crate::pe::ImageNtHeaders32>(), 0);
+        pe[0..2].copy_from_slice(b"MZ");
+        pe[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        pe[0x40..0x44].copy_from_slice(b"PE\0\0");
+        pe[0x58..0x5a].copy_from_slice(&crate::pe::IMAGE_NT_OPTIONAL_HDR64_MAGIC.to_le_bytes());
+        assert_eq!(FileKind::parse(&*pe).ok(), Some(FileKind::Pe64));
+        pe[0x58..0x5a].copy_from_slice(&crate::pe::IMAGE_NT_OPTIONAL_HDR32_MAGIC.to_le_bytes());
+        assert_eq!(FileKind::parse(&*pe).ok(), Some(FileKind::Pe32));
+        pe[0x40..0x44].copy_from_slice(b"NE\0\0");
+        assert!(FileKind::parse(&*pe).is_err());
+        pe[0x3c..0x40].copy_from_slice(&0x1000u32.to_le_bytes());
+        assert!(FileKind::parse(&*pe).is_err());
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn file_kind_wasm() {
+        // The smallest valid Wasm module.
+        let module = b"\0asm\x01\0\0\0";
+        assert_eq!(FileKind::parse(&module[..]).ok(), Some(FileKind::Wasm));
+        assert!(File::parse(&module[..]).is_ok());
+    }
+}
@@ -1,19 +1,24 @@
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::Debug;
 use core::{mem, str};
 
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 
 use crate::read::coff::{CoffCommon, CoffSymbol, CoffSymbolIterator, CoffSymbolTable, SymbolTable};
+use crate::read::util::{AddressIndex, LazyBox};
 use crate::read::{
-    self, Architecture, ComdatKind, Error, Export, FileFlags, Import, NoDynamicRelocationIterator,
-    Object, ObjectComdat, ReadError, ReadRef, Result, SectionIndex, SymbolIndex,
+    self, util, Architecture, ComdatKind, Error, Export, FileFlags, Import, Object, ObjectComdat,
+    ReadError, ReadRef, Result, SectionIndex, SymbolIndex,
 };
 use crate::{
     pe, ByteString, Bytes, CodeView, LittleEndian as LE, Pod, U16Bytes, U32Bytes, U32, U64,
 };
 
-use super::{PeSection, PeSectionIterator, PeSegment, PeSegmentIterator, SectionTable};
+use super::{
+    LoadConfig, PeDynamicRelocationIterator, PeSection, PeSectionIterator, PeSegment,
+    PeSegmentIterator, RelocationBlockIterator, ResourceDirectory, RichHeader, SectionTable,
+};
 
 /// A PE32 (32-bit) image file.
 pub type PeFile32<'data, R = &'data [u8]> = PeFile<'data, pe::ImageNtHeaders32, R>;
@@ -32,6 +37,7 @@ where
     pub(super) data_directories: &'data [pe::ImageDataDirectory],
     pub(super) common: CoffCommon<'data>,
     pub(super) data: R,
+    pub(super) address_index: LazyBox<AddressIndex>,
 }
 
 impl<'data, Pe, R> PeFile<'data, Pe, R>
@@ -56,8 +62,10 @@ where
                 sections,
                 symbols,
                 image_base,
+                section_names: LazyBox::new(),
             },
             data,
+            address_index: LazyBox::new(),
         })
     }
 
@@ -75,6 +83,335 @@ where
         self.nt_headers
     }
 
+    /// Return the "Rich" header of this file.
+    ///
+    /// Returns `None` if the header is not present or its checksum is invalid.
+    pub fn rich_header(&self) -> Option<RichHeader> {
+        RichHeader::parse(self.data, self.dos_header.nt_headers_offset().into())
+    }
+
+    /// Return the overlay data that is appended after the end of the image.
+    ///
+    /// This is the data following the end of the headers and the raw data of
+    /// every section. It is commonly used by installers and self-extracting archives.
+    /// Note that this includes the certificate table, if any, since the certificate
+    /// table is also stored after the image.
+    ///
+    /// Returns `None` if there is no data after the image.
+    pub fn overlay(&self) -> Option<&'data [u8]> {
+        let mut end = u64::from(self.nt_headers.optional_header().size_of_headers());
+        for section in self.common.sections.iter() {
+            let size = section.size_of_raw_data.get(LE);
+            if size != 0 {
+                let offset = section.pointer_to_raw_data.get(LE);
+                end = end.max(u64::from(offset) + u64::from(size));
+            }
+        }
+        let len = self.data.len().ok()?;
+        if end >= len {
+            return None;
+        }
+        self.data.read_bytes_at(end, len - end).ok()
+    }
+
+    /// Return the section table of this file.
+    pub fn section_table(&self) -> SectionTable<'data> {
+        self.common.sections
+    }
+
+    /// Return the resource directory of this file.
+    ///
+    /// Returns `Ok(None)` if the file has no resources.
+    pub fn resources(&self) -> Result<Option<ResourceDirectory<'data>>> {
+        let data_dir = match self.data_directory(pe::IMAGE_DIRECTORY_ENTRY_RESOURCE) {
+            Some(data_dir) => data_dir,
+            None => return Ok(None),
+        };
+        let data = data_dir.data(self.data, &self.common.sections)?;
+        Ok(Some(ResourceDirectory::new(data)))
+    }
+
+    /// Return the load configuration directory of this file.
+    ///
+    /// This contains the `/GS` security cookie and the Control Flow Guard
+    /// and SafeSEH tables.
+    ///
+    /// Returns `Ok(None)` if the file has no load configuration directory.
+    pub fn load_config(&self) -> Result<Option<LoadConfig>> {
+        let data_dir = match self.data_directory(pe::IMAGE_DIRECTORY_ENTRY_LOAD_CONFIG) {
+            Some(data_dir) => data_dir,
+            None => return Ok(None),
+        };
+        let data = self
+            .data_at(data_dir.virtual_address.get(LE))
+            .read_error("Invalid PE load config address")?;
+        LoadConfig::parse(data.0, self.is_64()).map(Some)
+    }
+
+    /// Return the delay-loaded imports of this file.
+    ///
+    /// These are parsed from the `IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT` data directory.
+    /// The DLLs are only loaded when one of their functions is first called, and
+    /// these imports are not included in `Object::imports`.
+    ///
+    /// Descriptors that don't have the `IMAGE_DELAYLOAD_RVA_BASED` attribute are
+    /// from older linkers, and contain virtual addresses instead of relative virtual
+    /// addresses. These addresses are converted using the image base.
+    ///
+    /// Returns an empty vector if the file has no delay-loaded imports.
+    pub fn delay_imports(&self) -> Result<Vec<PeDelayImport<'data>>> {
+        let data_dir = match self.data_directory(pe::IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT) {
+            Some(data_dir) => data_dir,
+            None => return Ok(Vec::new()),
+        };
+        let mut descriptors = data_dir.data(self.data, &self.common.sections).map(Bytes)?;
+        let mut imports = Vec::new();
+        loop {
+            let descriptor = descriptors
+                .read::<pe::ImageDelayloadDescriptor>()
+                .read_error("Missing PE null delay load descriptor")?;
+            if descriptor.dll_name_rva.get(LE) == 0 {
+                break;
+            }
+            let base = if descriptor.attributes.get(LE) & pe::IMAGE_DELAYLOAD_RVA_BASED != 0 {
+                0
+            } else {
+                self.common.image_base
+            };
+            let data_at = |address: u64| {
+                u32::try_from(address.wrapping_sub(base))
+                    .ok()
+                    .and_then(|va| self.data_at(va))
+            };
+
+            let library = data_at(descriptor.dll_name_rva.get(LE).into())
+                .read_error("Invalid PE delay load descriptor name")?
+                .read_string()
+                .read_error("Invalid PE delay load descriptor name")?;
+
+            let mut thunk_data = data_at(descriptor.import_name_table_rva.get(LE).into())
+                .read_error("Invalid PE delay load name table address")?;
+            let mut names = Vec::new();
+            loop {
+                let (thunk, is_ordinal) = if self.is_64() {
+                    let thunk = thunk_data
+                        .read::<U64<_>>()
+                        .read_error("Missing PE null delay load thunk")?
+                        .get(LE);
+                    (thunk, thunk & pe::IMAGE_ORDINAL_FLAG64 != 0)
+                } else {
+                    let thunk = thunk_data
+                        .read::<U32<_>>()
+                        .read_error("Missing PE null delay load thunk")?
+                        .get(LE);
+                    (thunk.into(), thunk & pe::IMAGE_ORDINAL_FLAG32 != 0)
+                };
+                if thunk == 0 {
+                    break;
+                }
+                if is_ordinal {
+                    names.push(PeImportName::Ordinal(thunk as u16));
+                    continue;
+                }
+                let mut hint_name =
+                    data_at(thunk).read_error("Invalid PE delay load thunk name")?;
+                let hint = hint_name
+                    .read::<U16Bytes<_>>()
+                    .read_error("Invalid PE delay load thunk name")?
+                    .get(LE);
+                let name = hint_name
+                    .read_string()
+                    .read_error("Invalid PE delay load thunk name")?;
+                names.push(PeImportName::Name { hint, name });
+            }
+            imports.push(PeDelayImport { library, names });
+        }
+        Ok(imports)
+    }
+
+    /// Return the exports that are forwarded to another module.
+    ///
+    /// These are not included in `Object::exports`. The address of each export
+    /// is the address of its forwarder string, which can be parsed using
+    /// `PeFile::resolve_forwarder`.
+    pub fn forwarded_exports(&self) -> Result<Vec<Export<'data>>> {
+        self.export_entries(true)
+    }
+
+    /// Parse the forwarder string of an export that is forwarded to another module.
+    ///
+    /// Returns the name of the module and the export within that module.
+    /// Forwarder strings have the form `Module.Function` or `Module.#Ordinal`.
+    /// The module name does not include the file extension.
+    ///
+    /// Returns `None` if the export is not a forwarder, or the forwarder string is invalid.
+    pub fn resolve_forwarder(
+        &self,
+        export: &Export<'data>,
+    ) -> Option<(&'data [u8], ForwardTarget<'data>)> {
+        let data_dir = self.data_directory(pe::IMAGE_DIRECTORY_ENTRY_EXPORT)?;
+        let export_va = data_dir.virtual_address.get(LE);
+        let offset = export
+            .address()
+            .wrapping_sub(self.common.image_base)
+            .checked_sub(export_va.into())?;
+        if offset >= data_dir.size.get(LE).into() {
+            return None;
+        }
+        let export_data = data_dir.data(self.data, &self.common.sections).ok()?;
+        let forwarder = Bytes(export_data).read_string_at(offset as usize).ok()?;
+        parse_forwarder(forwarder)
+    }
+
+    /// Return a copy of the file data that has been rebased to a new image base.
+    ///
+    /// This applies the base relocations to the raw data of the sections,
+    /// adjusting each pointer by the difference between `new_base` and the image base,
+    /// and updates the image base in the optional header.
+    /// Only the `IMAGE_REL_BASED_HIGHLOW`, `IMAGE_REL_BASED_DIR64`,
+    /// `IMAGE_REL_BASED_HIGH` and `IMAGE_REL_BASED_LOW` relocation types are supported.
+    ///
+    /// Returns an error if the file has no base relocations and `new_base`
+    /// differs from the image base, or if the file is a 32-bit image and
+    /// `new_base` does not fit in 32 bits.
+    pub fn relocate(&self, new_base: u64) -> Result<Vec<u8>> {
+        if !self.is_64() && new_base > u64::from(u32::max_value()) {
+            return Err(Error("Invalid image base for 32-bit PE file"));
+        }
+        let len = self.data.len().read_error("Invalid PE file size")?;
+        let mut data = self
+            .data
+            .read_bytes_at(0, len)
+            .read_error("Invalid PE file size")?
+            .to_vec();
+        let delta = new_base.wrapping_sub(self.common.image_base);
+
+        let image_base_offset = self.dos_header.nt_headers_offset() as usize
+            + mem::size_of::<U32<LE>>()
+            + mem::size_of::<pe::ImageFileHeader>();
+        if self.is_64() {
+            // Offset of `image_base` in `ImageOptionalHeader64`.
+            patch_bytes(&mut data, image_base_offset + 24, &new_base.to_le_bytes())?;
+        } else {
+            // Offset of `image_base` in `ImageOptionalHeader32`.
+            patch_bytes(
+                &mut data,
+                image_base_offset + 28,
+                &(new_base as u32).to_le_bytes(),
+            )?;
+        }
+
+        let data_dir = match self.data_directory(pe::IMAGE_DIRECTORY_ENTRY_BASERELOC) {
+            Some(data_dir) => data_dir,
+            None if delta == 0 => return Ok(data),
+            None => return Err(Error("PE file has no base relocations")),
+        };
+        let reloc_data = data_dir.data(self.data, &self.common.sections)?;
+        let mut blocks = RelocationBlockIterator::new(reloc_data);
+        while let Some(relocs) = blocks.next()? {
+            for reloc in relocs {
+                let offset = self
+                    .common
+                    .sections
+                    .iter()
+                    .find_map(|section| {
+                        let (offset, size) = section.pe_file_range();
+                        let offset_in_section = reloc
+                            .virtual_address
+                            .checked_sub(section.virtual_address.get(LE))?;
+                        if offset_in_section < size {
+                            Some(offset as usize + offset_in_section as usize)
+                        } else {
+                            None
+                        }
+                    })
+                    .read_error("Invalid PE base relocation address")?;
+                match reloc.typ {
+                    pe::IMAGE_REL_BASED_HIGHLOW => {
+                        let value = read_bytes::<[u8; 4]>(&data, offset)?;
+                        let value = u32::from_le_bytes(value).wrapping_add(delta as u32);
+                        patch_bytes(&mut data, offset, &value.to_le_bytes())?;
+                    }
+                    pe::IMAGE_REL_BASED_DIR64 => {
+                        let value = read_bytes::<[u8; 8]>(&data, offset)?;
+                        let value = u64::from_le_bytes(value).wrapping_add(delta);
+                        patch_bytes(&mut data, offset, &value.to_le_bytes())?;
+                    }
+                    pe::IMAGE_REL_BASED_HIGH => {
+                        let value = read_bytes::<[u8; 2]>(&data, offset)?;
+                        let value = u16::from_le_bytes(value).wrapping_add((delta >> 16) as u16);
+                        patch_bytes(&mut data, offset, &value.to_le_bytes())?;
+                    }
+                    pe::IMAGE_REL_BASED_LOW => {
+                        let value = read_bytes::<[u8; 2]>(&data, offset)?;
+                        let value = u16::from_le_bytes(value).wrapping_add(delta as u16);
+                        patch_bytes(&mut data, offset, &value.to_le_bytes())?;
+                    }
+                    _ => return Err(Error("Unsupported PE base relocation type")),
+                }
+            }
+        }
+        Ok(data)
+    }
+
+    fn export_entries(&self, forwarded: bool) -> Result<Vec<Export<'data>>> {
+        let data_dir = match self.data_directory(pe::IMAGE_DIRECTORY_ENTRY_EXPORT) {
+            Some(data_dir) => data_dir,
+            None => return Ok(Vec::new()),
+        };
+        let export_va = data_dir.virtual_address.get(LE);
+        let export_size = data_dir.size.get(LE);
+        let export_data = data_dir.data(self.data, &self.common.sections).map(Bytes)?;
+        let export_dir = export_data
+            .read_at::<pe::ImageExportDirectory>(0)
+            .read_error("Invalid PE export dir size")?;
+        let addresses = export_data
+            .read_slice_at::<U32Bytes<_>>(
+                export_dir
+                    .address_of_functions
+                    .get(LE)
+                    .wrapping_sub(export_va) as usize,
+                export_dir.number_of_functions.get(LE) as usize,
+            )
+            .read_error("Invalid PE export address table")?;
+        let number = export_dir.number_of_names.get(LE) as usize;
+        let names = export_data
+            .read_slice_at::<U32Bytes<_>>(
+                export_dir.address_of_names.get(LE).wrapping_sub(export_va) as usize,
+                number,
+            )
+            .read_error("Invalid PE export name table")?;
+        let ordinals = export_data
+            .read_slice_at::<U16Bytes<_>>(
+                export_dir
+                    .address_of_name_ordinals
+                    .get(LE)
+                    .wrapping_sub(export_va) as usize,
+                number,
+            )
+            .read_error("Invalid PE export ordinal table")?;
+
+        let mut exports = Vec::new();
+        for (name, ordinal) in names.iter().zip(ordinals.iter()) {
+            let name = export_data
+                .read_string_at(name.get(LE).wrapping_sub(export_va) as usize)
+                .read_error("Invalid PE export name entry")?;
+            let address = addresses
+                .get(ordinal.get(LE) as usize)
+                .read_error("Invalid PE export ordinal entry")?
+                .get(LE);
+            // Check for export address (vs forwarder address).
+            let is_forwarder = address >= export_va && (address - export_va) < export_size;
+            if is_forwarder == forwarded {
+                exports.push(Export {
+                    name: ByteString(name),
+                    address: self.common.image_base.wrapping_add(address.into()),
+                })
+            }
+        }
+        Ok(exports)
+    }
+
     fn data_directory(&self, id: usize) -> Option<&'data pe::ImageDataDirectory> {
         self.data_directories
             .get(id)
@@ -108,7 +445,7 @@ where
     type Symbol = CoffSymbol<'data, 'file>;
     type SymbolIterator = CoffSymbolIterator<'data, 'file>;
     type SymbolTable = CoffSymbolTable<'data, 'file>;
-    type DynamicRelocationIterator = NoDynamicRelocationIterator;
+    type DynamicRelocationIterator = PeDynamicRelocationIterator<'data>;
 
     fn architecture(&self) -> Architecture {
         match self.nt_headers.file_header().machine.get(LE) {
@@ -131,17 +468,21 @@ where
         self.nt_headers.is_type_64()
     }
 
+    #[inline]
+    fn is_relocatable(&self) -> bool {
+        false
+    }
+
     fn segments(&'file self) -> PeSegmentIterator<'data, 'file, Pe, R> {
         PeSegmentIterator {
             file: self,
-            iter: self.common.sections.iter(),
+            iter: self.common.sections.iter().enumerate(),
         }
     }
 
     fn section_by_name(&'file self, section_name: &str) -> Option<PeSection<'data, 'file, Pe, R>> {
         self.common
-            .sections
-            .section_by_name(self.common.symbols.strings(), section_name.as_bytes())
+            .section_by_name(section_name.as_bytes())
             .map(|(index, section)| PeSection {
                 file: self,
                 index: SectionIndex(index),
@@ -149,6 +490,14 @@ where
             })
     }
 
+    fn sections_by_name(&'file self, section_name: &str) -> Vec<PeSection<'data, 'file, Pe, R>> {
+        self.common
+            .section_indices_by_name(section_name.as_bytes())
+            .iter()
+            .filter_map(|&index| self.section_by_index(SectionIndex(index)).ok())
+            .collect()
+    }
+
     fn section_by_index(
         &'file self,
         index: SectionIndex,
@@ -168,6 +517,10 @@ where
         }
     }
 
+    fn cstr_at(&'file self, address: u64) -> Option<&'data [u8]> {
+        util::indexed_cstr_at(self, &self.address_index, address)
+    }
+
     fn comdats(&'file self) -> PeComdatIterator<'data, 'file, Pe, R> {
         PeComdatIterator { file: self }
     }
@@ -204,8 +557,15 @@ where
         None
     }
 
-    fn dynamic_relocations(&'file self) -> Option<NoDynamicRelocationIterator> {
-        None
+    /// Returns the base relocations of the file.
+    fn dynamic_relocations(&'file self) -> Option<PeDynamicRelocationIterator<'data>> {
+        let data_dir = self.data_directory(pe::IMAGE_DIRECTORY_ENTRY_BASERELOC)?;
+        let data = data_dir.data(self.data, &self.common.sections).ok()?;
+        Some(PeDynamicRelocationIterator {
+            image_base: self.common.image_base,
+            blocks: RelocationBlockIterator::new(data),
+            relocs: None,
+        })
     }
 
     fn imports(&self) -> Result<Vec<Import<'data>>> {
@@ -277,60 +637,7 @@ where
     }
 
     fn exports(&self) -> Result<Vec<Export<'data>>> {
-        let data_dir = match self.data_directory(pe::IMAGE_DIRECTORY_ENTRY_EXPORT) {
-            Some(data_dir) => data_dir,
-            None => return Ok(Vec::new()),
-        };
-        let export_va = data_dir.virtual_address.get(LE);
-        let export_size = data_dir.size.get(LE);
-        let export_data = data_dir.data(self.data, &self.common.sections).map(Bytes)?;
-        let export_dir = export_data
-            .read_at::<pe::ImageExportDirectory>(0)
-            .read_error("Invalid PE export dir size")?;
-        let addresses = export_data
-            .read_slice_at::<U32Bytes<_>>(
-                export_dir
-                    .address_of_functions
-                    .get(LE)
-                    .wrapping_sub(export_va) as usize,
-                export_dir.number_of_functions.get(LE) as usize,
-            )
-            .read_error("Invalid PE export address table")?;
-        let number = export_dir.number_of_names.get(LE) as usize;
-        let names = export_data
-            .read_slice_at::<U32Bytes<_>>(
-                export_dir.address_of_names.get(LE).wrapping_sub(export_va) as usize,
-                number,
-            )
-            .read_error("Invalid PE export name table")?;
-        let ordinals = export_data
-            .read_slice_at::<U16Bytes<_>>(
-                export_dir
-                    .address_of_name_ordinals
-                    .get(LE)
-                    .wrapping_sub(export_va) as usize,
-                number,
-            )
-            .read_error("Invalid PE export ordinal table")?;
-
-        let mut exports = Vec::new();
-        for (name, ordinal) in names.iter().zip(ordinals.iter()) {
-            let name = export_data
-                .read_string_at(name.get(LE).wrapping_sub(export_va) as usize)
-                .read_error("Invalid PE export name entry")?;
-            let address = addresses
-                .get(ordinal.get(LE) as usize)
-                .read_error("Invalid PE export ordinal entry")?
-                .get(LE);
-            // Check for export address (vs forwarder address).
-            if address < export_va || (address - export_va) >= export_size {
-                exports.push(Export {
-                    name: ByteString(name),
-                    address: self.common.image_base.wrapping_add(address.into()),
-                })
-            }
-        }
-        Ok(exports)
+        self.export_entries(false)
     }
 
     fn pdb_info(&self) -> Result<Option<CodeView>> {
@@ -384,6 +691,17 @@ where
         }))
     }
 
+    fn compiler_info(&self) -> Vec<String> {
+        match self.rich_header() {
+            Some(rich) => rich
+                .entries
+                .iter()
+                .map(|entry| format!("product {} build {}", entry.product_id, entry.build_id))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     fn has_debug_symbols(&self) -> bool {
         self.section_by_name(".debug_info").is_some()
     }
@@ -392,6 +710,10 @@ where
         self.common.image_base
     }
 
+    fn preferred_load_address(&self) -> u64 {
+        self.common.image_base
+    }
+
     fn entry(&self) -> u64 {
         u64::from(self.nt_headers.optional_header().address_of_entry_point())
             .wrapping_add(self.common.image_base)
@@ -515,6 +837,55 @@ where
     }
 }
 
+/// The export that a forwarded export refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForwardTarget<'data> {
+    /// The export is forwarded to an export with this name.
+    Name(&'data [u8]),
+    /// The export is forwarded to an export with this ordinal.
+    Ordinal(u32),
+}
+
+/// The delay-loaded imports from a single DLL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeDelayImport<'data> {
+    /// The name of the DLL.
+    pub library: &'data [u8],
+    /// The imported functions, in the order of the import name table.
+    pub names: Vec<PeImportName<'data>>,
+}
+
+/// The function that is imported by a PE import name table entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeImportName<'data> {
+    /// The function is imported by name.
+    Name {
+        /// The index into the export name table of the DLL that is tried first.
+        hint: u16,
+        /// The name of the function.
+        name: &'data [u8],
+    },
+    /// The function is imported by ordinal.
+    Ordinal(u16),
+}
+
+/// Parse a forwarder string of the form `Module.Function` or `Module.#Ordinal`.
+fn parse_forwarder(forwarder: &[u8]) -> Option<(&[u8], ForwardTarget<'_>)> {
+    let dot = forwarder.iter().rposition(|&b| b == b'.')?;
+    let module = &forwarder[..dot];
+    let target = &forwarder[dot + 1..];
+    if module.is_empty() || target.is_empty() {
+        return None;
+    }
+    let target = if target[0] == b'#' {
+        let ordinal = str::from_utf8(&target[1..]).ok()?;
+        ForwardTarget::Ordinal(ordinal.parse().ok()?)
+    } else {
+        ForwardTarget::Name(target)
+    };
+    Some((module, target))
+}
+
 impl pe::ImageDosHeader {
     /// Read the DOS header.
     ///
@@ -1044,3 +1415,294 @@ impl pe::ImageDataDirectory {
             .read_error("Invalid data dir size")
     }
 }
+
+fn read_bytes<T>(data: &[u8], offset: usize) -> Result<T>
+where
+    for<'a> T: TryFrom<&'a [u8]>,
+{
+    data.get(offset..)
+        .and_then(|data| data.get(..mem::size_of::<T>()))
+        .and_then(|data| data.try_into().ok())
+        .read_error("Invalid PE base relocation offset")
+}
+
+fn patch_bytes(data: &mut [u8], offset: usize, bytes: &[u8]) -> Result<()> {
+    data.get_mut(offset..)
+        .and_then(|data| data.get_mut(..bytes.len()))
+        .read_error("Invalid PE base relocation offset")?
+        .copy_from_slice(bytes);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read::{ObjectSection, ObjectSegment};
+
+    #[test]
+    fn forwarder() {
+        assert_eq!(
+            parse_forwarder(b"NTDLL.RtlAllocateHeap"),
+            Some((&b"NTDLL"[..], ForwardTarget::Name(b"RtlAllocateHeap")))
+        );
+        assert_eq!(
+            parse_forwarder(b"api-ms-win-core-com-l1-1-0.#12"),
+            Some((
+                &b"api-ms-win-core-com-l1-1-0"[..],
+                ForwardTarget::Ordinal(12)
+            ))
+        );
+        assert_eq!(
+            parse_forwarder(b"MOD\xff.F\xfe"),
+            Some((&b"MOD\xff"[..], ForwardTarget::Name(b"F\xfe")))
+        );
+        assert_eq!(parse_forwarder(b"NTDLL"), None);
+        assert_eq!(parse_forwarder(b"NTDLL."), None);
+        assert_eq!(parse_forwarder(b".Function"), None);
+        assert_eq!(parse_forwarder(b"NTDLL.#x"), None);
+    }
+
+    /// Return a 64-bit image with a single section.
+    ///
+    /// The section has an RVA of 0x1000 and a file offset of 0x200.
+    fn image() -> Vec<u8> {
+        let mut data = vec![0u8; 0x300];
+        data[0..2].copy_from_slice(b"MZ");
+        data[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        data[0x40..0x44].copy_from_slice(b"PE\0\0");
+        // File header.
+        data[0x44..0x46].copy_from_slice(&pe::IMAGE_FILE_MACHINE_AMD64.to_le_bytes());
+        data[0x46..0x48].copy_from_slice(&1u16.to_le_bytes());
+        data[0x54..0x56].copy_from_slice(&0xf0u16.to_le_bytes());
+        // Optional header.
+        let optional = 0x58;
+        data[optional..][..2].copy_from_slice(&pe::IMAGE_NT_OPTIONAL_HDR64_MAGIC.to_le_bytes());
+        data[optional + 16..][..4].copy_from_slice(&0x1010u32.to_le_bytes());
+        data[optional + 24..][..8].copy_from_slice(&0x1_4000_0000u64.to_le_bytes());
+        data[optional + 60..][..4].copy_from_slice(&0x200u32.to_le_bytes());
+        data[optional + 108..][..4].copy_from_slice(&16u32.to_le_bytes());
+        // Section header.
+        let section = optional + 0xf0;
+        data[section..][..5].copy_from_slice(b".text");
+        data[section + 8..][..4].copy_from_slice(&0x100u32.to_le_bytes());
+        data[section + 12..][..4].copy_from_slice(&0x1000u32.to_le_bytes());
+        data[section + 16..][..4].copy_from_slice(&0x100u32.to_le_bytes());
+        data[section + 20..][..4].copy_from_slice(&0x200u32.to_le_bytes());
+        data
+    }
+
+    /// Return a 32-bit image with a single section.
+    ///
+    /// The section has an RVA of 0x1000 and a file offset of 0x200.
+    fn image32() -> Vec<u8> {
+        let mut data = vec![0u8; 0x300];
+        data[0..2].copy_from_slice(b"MZ");
+        data[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        data[0x40..0x44].copy_from_slice(b"PE\0\0");
+        // File header.
+        data[0x44..0x46].copy_from_slice(&pe::IMAGE_FILE_MACHINE_I386.to_le_bytes());
+        data[0x46..0x48].copy_from_slice(&1u16.to_le_bytes());
+        data[0x54..0x56].copy_from_slice(&0xe0u16.to_le_bytes());
+        // Optional header.
+        let optional = 0x58;
+        data[optional..][..2].copy_from_slice(&pe::IMAGE_NT_OPTIONAL_HDR32_MAGIC.to_le_bytes());
+        data[optional + 16..][..4].copy_from_slice(&0x1010u32.to_le_bytes());
+        data[optional + 28..][..4].copy_from_slice(&0x40_0000u32.to_le_bytes());
+        data[optional + 60..][..4].copy_from_slice(&0x200u32.to_le_bytes());
+        data[optional + 92..][..4].copy_from_slice(&16u32.to_le_bytes());
+        // Section header.
+        let section = optional + 0xe0;
+        data[section..][..5].copy_from_slice(b".text");
+        data[section + 8..][..4].copy_from_slice(&0x100u32.to_le_bytes());
+        data[section + 12..][..4].copy_from_slice(&0x1000u32.to_le_bytes());
+        data[section + 16..][..4].copy_from_slice(&0x100u32.to_le_bytes());
+        data[section + 20..][..4].copy_from_slice(&0x200u32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn overlay() {
+        let mut data = image();
+        let file = PeFile64::parse(&*data).unwrap();
+        assert_eq!(file.overlay(), None);
+
+        data.extend_from_slice(b"overlay");
+        let file = PeFile64::parse(&*data).unwrap();
+        assert_eq!(file.overlay(), Some(&b"overlay"[..]));
+    }
+
+    #[test]
+    fn absolute_addresses() {
+        let data = image();
+        let file = PeFile64::parse(&*data).unwrap();
+        assert_eq!(file.relative_address_base(), 0x1_4000_0000);
+        assert_eq!(file.entry(), 0x1_4000_1010);
+        let section = file.sections().next().unwrap();
+        assert_eq!(section.address(), 0x1_4000_1000);
+        let segment = file.segments().next().unwrap();
+        assert_eq!(segment.address(), 0x1_4000_1000);
+        assert_eq!(
+            file.address_map()
+                .data_at(0x1_4000_1000)
+                .map(|data| data.len()),
+            Some(0x100)
+        );
+    }
+
+    #[test]
+    fn relative_addresses() {
+        let data = image();
+        let file = PeFile64::parse(&*data).unwrap();
+        let section = file.sections().next().unwrap();
+        assert_eq!(section.relative_address(), 0x1000);
+        let segment = file.segments().next().unwrap();
+        assert_eq!(segment.relative_address(), 0x1000);
+
+        let file = crate::read::File::parse(&*data).unwrap();
+        let base = file.relative_address_base();
+        let section = file.sections().next().unwrap();
+        assert_eq!(section.relative_address(), section.address() - base);
+        let segment = file.segments().next().unwrap();
+        assert_eq!(segment.relative_address(), segment.address() - base);
+    }
+
+    #[test]
+    fn uuid() {
+        let mut data = image();
+        // Debug data directory.
+        let debug_dir = 0x58 + 112 + 8 * pe::IMAGE_DIRECTORY_ENTRY_DEBUG;
+        data[debug_dir..][..4].copy_from_slice(&0x1000u32.to_le_bytes());
+        data[debug_dir + 4..][..4].copy_from_slice(&28u32.to_le_bytes());
+        // Debug directory.
+        data[0x20c..0x210].copy_from_slice(&pe::IMAGE_DEBUG_TYPE_CODEVIEW.to_le_bytes());
+        data[0x210..0x214].copy_from_slice(&30u32.to_le_bytes());
+        data[0x218..0x21c].copy_from_slice(&0x240u32.to_le_bytes());
+        // CodeView info.
+        data[0x240..0x244].copy_from_slice(b"RSDS");
+        let guid = [
+            0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        data[0x244..0x254].copy_from_slice(&guid);
+        data[0x254..0x258].copy_from_slice(&1u32.to_le_bytes());
+        data[0x258..0x25e].copy_from_slice(b"a.pdb\0");
+
+        let file = PeFile64::parse(&*data).unwrap();
+        assert_eq!(file.pdb_info().unwrap().unwrap().guid(), guid);
+        assert_eq!(
+            file.uuid().unwrap(),
+            Some([
+                0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff,
+            ])
+        );
+    }
+
+    #[test]
+    fn relocate() {
+        let mut data = image();
+        // Base relocation data directory.
+        let reloc_dir = 0x58 + 112 + 8 * pe::IMAGE_DIRECTORY_ENTRY_BASERELOC;
+        data[reloc_dir..][..4].copy_from_slice(&0x1080u32.to_le_bytes());
+        data[reloc_dir + 4..][..4].copy_from_slice(&12u32.to_le_bytes());
+        // One block with a 64-bit pointer and a padding entry.
+        data[0x280..0x284].copy_from_slice(&0x1000u32.to_le_bytes());
+        data[0x284..0x288].copy_from_slice(&12u32.to_le_bytes());
+        let dir64 = pe::IMAGE_REL_BASED_DIR64 << 12 | 0x8;
+        data[0x288..0x28a].copy_from_slice(&dir64.to_le_bytes());
+        // Pointer to the entry point.
+        data[0x208..0x210].copy_from_slice(&0x1_4000_1010u64.to_le_bytes());
+
+        let file = PeFile64::parse(&*data).unwrap();
+        let relocated = file.relocate(0x2_0000_0000).unwrap();
+        assert_eq!(relocated[0x208..0x210], 0x2_0000_1010u64.to_le_bytes());
+        let relocated = PeFile64::parse(&*relocated).unwrap();
+        assert_eq!(relocated.relative_address_base(), 0x2_0000_0000);
+        assert_eq!(relocated.entry(), 0x2_0000_1010);
+
+        // An unsupported relocation type.
+        let highadj = pe::IMAGE_REL_BASED_HIGHADJ << 12 | 0x8;
+        data[0x288..0x28a].copy_from_slice(&highadj.to_le_bytes());
+        let file = PeFile64::parse(&*data).unwrap();
+        assert!(file.relocate(0x2_0000_0000).is_err());
+
+        // No base relocations.
+        let data = image();
+        let file = PeFile64::parse(&*data).unwrap();
+        assert!(file.relocate(0x2_0000_0000).is_err());
+        assert_eq!(file.relocate(0x1_4000_0000).unwrap(), data);
+    }
+
+    #[test]
+    fn relocate32() {
+        let mut data = image32();
+        // Base relocation data directory.
+        let reloc_dir = 0x58 + 96 + 8 * pe::IMAGE_DIRECTORY_ENTRY_BASERELOC;
+        data[reloc_dir..][..4].copy_from_slice(&0x1080u32.to_le_bytes());
+        data[reloc_dir + 4..][..4].copy_from_slice(&12u32.to_le_bytes());
+        // One block with a 32-bit pointer and a padding entry.
+        data[0x280..0x284].copy_from_slice(&0x1000u32.to_le_bytes());
+        data[0x284..0x288].copy_from_slice(&12u32.to_le_bytes());
+        let highlow = pe::IMAGE_REL_BASED_HIGHLOW << 12 | 0x8;
+        data[0x288..0x28a].copy_from_slice(&highlow.to_le_bytes());
+        // Pointer to the entry point.
+        data[0x208..0x20c].copy_from_slice(&0x40_1010u32.to_le_bytes());
+
+        let file = PeFile32::parse(&*data).unwrap();
+        let relocated = file.relocate(0x1000_0000).unwrap();
+        assert_eq!(relocated[0x208..0x20c], 0x1000_1010u32.to_le_bytes());
+        let relocated = PeFile32::parse(&*relocated).unwrap();
+        assert_eq!(relocated.relative_address_base(), 0x1000_0000);
+        assert_eq!(relocated.entry(), 0x1000_1010);
+
+        // The new base must fit in the 32-bit optional header.
+        assert!(file.relocate(0x1_0000_0000).is_err());
+        assert!(file.relocate(0x1_0040_0000).is_err());
+    }
+
+    #[test]
+    fn delay_imports() {
+        let mut data = image();
+        let delay_dir = 0x58 + 112 + 8 * pe::IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT;
+        data[delay_dir..][..4].copy_from_slice(&0x1000u32.to_le_bytes());
+        data[delay_dir + 4..][..4].copy_from_slice(&64u32.to_le_bytes());
+        // Descriptor followed by a null descriptor.
+        data[0x200..0x204].copy_from_slice(&pe::IMAGE_DELAYLOAD_RVA_BASED.to_le_bytes());
+        data[0x204..0x208].copy_from_slice(&0x1040u32.to_le_bytes());
+        data[0x210..0x214].copy_from_slice(&0x1050u32.to_le_bytes());
+        data[0x240..0x248].copy_from_slice(b"foo.dll\0");
+        // Name table with a name, an ordinal, and a null entry.
+        data[0x250..0x258].copy_from_slice(&0x1070u64.to_le_bytes());
+        data[0x258..0x260].copy_from_slice(&(pe::IMAGE_ORDINAL_FLAG64 | 5).to_le_bytes());
+        data[0x270..0x272].copy_from_slice(&3u16.to_le_bytes());
+        data[0x272..0x276].copy_from_slice(b"bar\0");
+
+        let expected = vec![PeDelayImport {
+            library: b"foo.dll",
+            names: vec![
+                PeImportName::Name {
+                    hint: 3,
+                    name: b"bar",
+                },
+                PeImportName::Ordinal(5),
+            ],
+        }];
+        let file = PeFile64::parse(&*data).unwrap();
+        assert_eq!(file.delay_imports().unwrap(), expected);
+        assert!(file.imports().unwrap().is_empty());
+
+        // Older descriptors use virtual addresses.
+        let base = 0x40_0000u64;
+        data[0x58 + 24..][..8].copy_from_slice(&base.to_le_bytes());
+        data[0x200..0x204].copy_from_slice(&0u32.to_le_bytes());
+        data[0x204..0x208].copy_from_slice(&(base as u32 + 0x1040).to_le_bytes());
+        data[0x210..0x214].copy_from_slice(&(base as u32 + 0x1050).to_le_bytes());
+        data[0x250..0x258].copy_from_slice(&(base + 0x1070).to_le_bytes());
+        let file = PeFile64::parse(&*data).unwrap();
+        assert_eq!(file.delay_imports().unwrap(), expected);
+
+        let data = image();
+        let file = PeFile64::parse(&*data).unwrap();
+        assert!(file.delay_imports().unwrap().is_empty());
+    }
+}
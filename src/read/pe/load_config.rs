@@ -0,0 +1,164 @@
+use alloc::vec;
+use core::{cmp, mem};
+
+use crate::endian::{LittleEndian as LE, U32Bytes};
+use crate::pe;
+use crate::pod::{self, Bytes, Pod};
+use crate::read::{ReadError, Result};
+
+/// The exploit mitigation fields of the load configuration directory of a PE file.
+///
+/// The load configuration directory has grown over time, and its `size` field
+/// gives the number of bytes that are present. Fields that are beyond this size
+/// are returned as zero.
+///
+/// Addresses are virtual addresses, which include the image base.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LoadConfig {
+    /// The size of the directory in bytes, as given by the directory itself.
+    pub size: u32,
+    /// The address of the `/GS` security cookie.
+    pub security_cookie: u64,
+    /// The address of the table of SafeSEH handler RVAs. This is only used for 32-bit images.
+    pub se_handler_table: u64,
+    /// The number of entries in the SafeSEH handler table.
+    pub se_handler_count: u64,
+    /// The address of the Control Flow Guard check function pointer.
+    pub guard_cf_check_function_pointer: u64,
+    /// The address of the Control Flow Guard dispatch function pointer.
+    pub guard_cf_dispatch_function_pointer: u64,
+    /// The address of the Control Flow Guard function table.
+    pub guard_cf_function_table: u64,
+    /// The number of entries in the Control Flow Guard function table.
+    pub guard_cf_function_count: u64,
+    /// The Control Flow Guard flags. A combination of the `pe::IMAGE_GUARD_*` flags.
+    pub guard_flags: u32,
+}
+
+impl LoadConfig {
+    /// Parse the load configuration directory.
+    ///
+    /// `data` must start at the directory. It may extend past the end of the directory.
+    /// `is_64` selects between the `ImageLoadConfigDirectory32` and
+    /// `ImageLoadConfigDirectory64` layouts.
+    pub fn parse(data: &[u8], is_64: bool) -> Result<Self> {
+        let data = Bytes(data);
+        let size = data
+            .read_at::<U32Bytes<LE>>(0)
+            .read_error("Invalid PE load config size")?
+            .get(LE);
+        let data = data
+            .read_bytes_at(0, size as usize)
+            .read_error("Invalid PE load config size")?;
+        Ok(if is_64 {
+            let directory = read_directory::<pe::ImageLoadConfigDirectory64>(data)?;
+            LoadConfig {
+                size,
+                security_cookie: directory.security_cookie.get(LE),
+                se_handler_table: directory.sehandler_table.get(LE),
+                se_handler_count: directory.sehandler_count.get(LE),
+                guard_cf_check_function_pointer: directory.guard_cf_check_function_pointer.get(LE),
+                guard_cf_dispatch_function_pointer: directory
+                    .guard_cf_dispatch_function_pointer
+                    .get(LE),
+                guard_cf_function_table: directory.guard_cf_function_table.get(LE),
+                guard_cf_function_count: directory.guard_cf_function_count.get(LE),
+                guard_flags: directory.guard_flags.get(LE),
+            }
+        } else {
+            let directory = read_directory::<pe::ImageLoadConfigDirectory32>(data)?;
+            LoadConfig {
+                size,
+                security_cookie: directory.security_cookie.get(LE).into(),
+                se_handler_table: directory.sehandler_table.get(LE).into(),
+                se_handler_count: directory.sehandler_count.get(LE).into(),
+                guard_cf_check_function_pointer: directory
+                    .guard_cf_check_function_pointer
+                    .get(LE)
+                    .into(),
+                guard_cf_dispatch_function_pointer: directory
+                    .guard_cf_dispatch_function_pointer
+                    .get(LE)
+                    .into(),
+                guard_cf_function_table: directory.guard_cf_function_table.get(LE).into(),
+                guard_cf_function_count: directory.guard_cf_function_count.get(LE).into(),
+                guard_flags: directory.guard_flags.get(LE),
+            }
+        })
+    }
+
+    /// Return true if the image was built with Control Flow Guard instrumentation.
+    pub fn has_guard_cf(&self) -> bool {
+        self.guard_flags & pe::IMAGE_GUARD_CF_INSTRUMENTED != 0
+    }
+
+    /// Return the size in bytes of each entry in the Control Flow Guard function table.
+    ///
+    /// Each entry is a 4 byte RVA followed by a number of bytes of metadata
+    /// that is given by the guard flags.
+    pub fn guard_cf_function_table_stride(&self) -> u64 {
+        let extra = (self.guard_flags & pe::IMAGE_GUARD_CF_FUNCTION_TABLE_SIZE_MASK)
+            >> pe::IMAGE_GUARD_CF_FUNCTION_TABLE_SIZE_SHIFT;
+        4 + u64::from(extra)
+    }
+}
+
+/// Read a load configuration directory that may be shorter than `T`.
+///
+/// The missing fields at the end are zero.
+fn read_directory<T: Pod>(data: Bytes) -> Result<T> {
+    // Copy into an aligned buffer, since the directory may be truncated.
+    let mut buf = vec![0u64; (mem::size_of::<T>() + 7) / 8];
+    let len = cmp::min(data.len(), mem::size_of::<T>());
+    pod::bytes_of_slice_mut(&mut buf)[..len].copy_from_slice(&data.0[..len]);
+    let directory = Bytes(pod::bytes_of_slice(&buf))
+        .read_at::<T>(0)
+        .read_error("Invalid PE load config size")?;
+    Ok(*directory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem;
+
+    #[test]
+    fn load_config() {
+        let size = mem::size_of::<pe::ImageLoadConfigDirectory64>();
+        let mut data = vec![0u8; size];
+        data[0..4].copy_from_slice(&(size as u32).to_le_bytes());
+        data[88..96].copy_from_slice(&0x1_4000_3000u64.to_le_bytes());
+        data[128..136].copy_from_slice(&0x1_4000_4000u64.to_le_bytes());
+        data[136..144].copy_from_slice(&3u64.to_le_bytes());
+        let flags =
+            pe::IMAGE_GUARD_CF_INSTRUMENTED | 1 << pe::IMAGE_GUARD_CF_FUNCTION_TABLE_SIZE_SHIFT;
+        data[144..148].copy_from_slice(&flags.to_le_bytes());
+        let config = LoadConfig::parse(&data, true).unwrap();
+        assert_eq!(config.security_cookie, 0x1_4000_3000);
+        assert_eq!(config.guard_cf_function_table, 0x1_4000_4000);
+        assert_eq!(config.guard_cf_function_count, 3);
+        assert!(config.has_guard_cf());
+        assert_eq!(config.guard_cf_function_table_stride(), 5);
+
+        // An older directory that ends before the Control Flow Guard fields.
+        data[0..4].copy_from_slice(&112u32.to_le_bytes());
+        let config = LoadConfig::parse(&data, true).unwrap();
+        assert_eq!(config.security_cookie, 0x1_4000_3000);
+        assert_eq!(config.guard_cf_function_table, 0);
+        assert!(!config.has_guard_cf());
+
+        let size = mem::size_of::<pe::ImageLoadConfigDirectory32>();
+        let mut data = vec![0u8; size];
+        data[0..4].copy_from_slice(&(size as u32).to_le_bytes());
+        data[60..64].copy_from_slice(&0x40_3000u32.to_le_bytes());
+        data[64..68].copy_from_slice(&0x40_5000u32.to_le_bytes());
+        data[68..72].copy_from_slice(&2u32.to_le_bytes());
+        let config = LoadConfig::parse(&data, false).unwrap();
+        assert_eq!(config.security_cookie, 0x40_3000);
+        assert_eq!(config.se_handler_table, 0x40_5000);
+        assert_eq!(config.se_handler_count, 2);
+
+        assert!(LoadConfig::parse(&data[..2], false).is_err());
+        assert!(LoadConfig::parse(&data[..8], false).is_err());
+    }
+}
@@ -13,4 +13,16 @@ pub use file::*;
 mod section;
 pub use section::*;
 
+mod load_config;
+pub use load_config::*;
+
+mod relocation;
+pub use relocation::*;
+
+mod resource;
+pub use resource::*;
+
+mod rich;
+pub use rich::*;
+
 pub use super::coff::{SectionTable, SymbolTable};
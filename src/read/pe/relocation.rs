@@ -0,0 +1,167 @@
+use core::slice;
+
+use crate::endian::{LittleEndian as LE, U16Bytes};
+use crate::pe;
+use crate::pod::Bytes;
+use crate::read::{
+    Error, ReadError, Relocation, RelocationEncoding, RelocationFlags, RelocationKind,
+    RelocationTarget, Result,
+};
+
+/// An iterator over the blocks in the base relocation directory of a PE file.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RelocationBlockIterator<'data> {
+    data: Bytes<'data>,
+}
+
+impl<'data> RelocationBlockIterator<'data> {
+    /// Construct a new iterator from the data of the base relocation directory.
+    pub fn new(data: &'data [u8]) -> Self {
+        RelocationBlockIterator { data: Bytes(data) }
+    }
+
+    /// Read the next relocation block.
+    pub fn next(&mut self) -> Result<Option<RelocationIterator<'data>>> {
+        if self.data.is_empty() {
+            return Ok(None);
+        }
+        let header = self
+            .data
+            .read::<pe::ImageBaseRelocation>()
+            .read_error("Invalid PE reloc section size")?;
+        let virtual_address = header.virtual_address.get(LE);
+        let size = header.size_of_block.get(LE);
+        if size < 8 {
+            return Err(Error("Invalid PE reloc block size"));
+        }
+        let count = (size - 8) / 2;
+        let relocs = self
+            .data
+            .read_slice::<U16Bytes<LE>>(count as usize)
+            .read_error("Invalid PE reloc block size")?
+            .iter();
+        Ok(Some(RelocationIterator {
+            virtual_address,
+            relocs,
+        }))
+    }
+}
+
+/// An iterator of the relocations in a block in the base relocation directory of a PE file.
+#[derive(Debug, Clone)]
+pub struct RelocationIterator<'data> {
+    virtual_address: u32,
+    relocs: slice::Iter<'data, U16Bytes<LE>>,
+}
+
+impl<'data> RelocationIterator<'data> {
+    /// Return the virtual address of the page that this block of relocations applies to.
+    pub fn virtual_address(&self) -> u32 {
+        self.virtual_address
+    }
+}
+
+impl<'data> Iterator for RelocationIterator<'data> {
+    type Item = PeRelocation;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let reloc = self.relocs.next()?.get(LE);
+            let typ = reloc >> 12;
+            // `IMAGE_REL_BASED_ABSOLUTE` is used for padding.
+            if typ != pe::IMAGE_REL_BASED_ABSOLUTE {
+                return Some(PeRelocation {
+                    virtual_address: self.virtual_address.wrapping_add(u32::from(reloc & 0xfff)),
+                    typ,
+                });
+            }
+        }
+    }
+}
+
+/// A relocation in the base relocation directory of a PE file.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PeRelocation {
+    /// The virtual address of the relocation, relative to the image base.
+    pub virtual_address: u32,
+    /// One of the `pe::IMAGE_REL_BASED_*` constants.
+    pub typ: u16,
+}
+
+/// An iterator over the base relocations in a PE file.
+///
+/// Each relocation adjusts the value at its address by the difference between
+/// the actual load address and the image base. These are returned as
+/// relocations with an absolute target and an implicit addend.
+#[derive(Debug)]
+pub struct PeDynamicRelocationIterator<'data> {
+    pub(super) image_base: u64,
+    pub(super) blocks: RelocationBlockIterator<'data>,
+    pub(super) relocs: Option<RelocationIterator<'data>>,
+}
+
+impl<'data> Iterator for PeDynamicRelocationIterator<'data> {
+    type Item = (u64, Relocation);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ref mut relocs) = self.relocs {
+                if let Some(reloc) = relocs.next() {
+                    let (kind, size) = match reloc.typ {
+                        pe::IMAGE_REL_BASED_HIGHLOW => (RelocationKind::Absolute, 32),
+                        pe::IMAGE_REL_BASED_DIR64 => (RelocationKind::Absolute, 64),
+                        typ => (RelocationKind::Coff(typ), 0),
+                    };
+                    let relocation = Relocation {
+                        kind,
+                        encoding: RelocationEncoding::Generic,
+                        size,
+                        target: RelocationTarget::Absolute,
+                        addend: 0,
+                        implicit_addend: true,
+                        flags: RelocationFlags::Coff { typ: reloc.typ },
+                    };
+                    let address = self.image_base.wrapping_add(reloc.virtual_address.into());
+                    return Some((address, relocation));
+                }
+            }
+            self.relocs = Some(self.blocks.next().ok()??);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn base_relocations() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0x1000u32.to_le_bytes());
+        data.extend_from_slice(&12u32.to_le_bytes());
+        data.extend_from_slice(&((pe::IMAGE_REL_BASED_DIR64 << 12) | 0x10).to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&0x2000u32.to_le_bytes());
+        data.extend_from_slice(&10u32.to_le_bytes());
+        data.extend_from_slice(&((pe::IMAGE_REL_BASED_HIGHLOW << 12) | 0x8).to_le_bytes());
+
+        let relocs: Vec<_> = PeDynamicRelocationIterator {
+            image_base: 0x4000_0000,
+            blocks: RelocationBlockIterator::new(&data),
+            relocs: None,
+        }
+        .map(|(address, reloc)| (address, reloc.kind, reloc.size))
+        .collect();
+        assert_eq!(
+            relocs,
+            [
+                (0x4000_1010, RelocationKind::Absolute, 64),
+                (0x4000_2008, RelocationKind::Absolute, 32),
+            ]
+        );
+
+        let mut blocks = RelocationBlockIterator::new(&data[..6]);
+        assert!(blocks.next().is_err());
+    }
+}
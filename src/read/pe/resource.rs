@@ -0,0 +1,360 @@
+use alloc::string::String;
+use core::{char, slice};
+
+use crate::read::{Error, ReadError, ReadRef, Result};
+use crate::{pe, Bytes, LittleEndian as LE, U16Bytes};
+
+use super::SectionTable;
+
+/// The maximum depth of nested resource directory tables.
+///
+/// Resource trees normally only have 3 levels (type, name, and language).
+/// This limit prevents a malformed tree from causing unbounded recursion
+/// when following table entries.
+const MAX_RESOURCE_DEPTH: usize = 16;
+
+/// The `.rsrc` section of a PE file.
+///
+/// This is the data referenced by the `IMAGE_DIRECTORY_ENTRY_RESOURCE` data directory.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceDirectory<'data> {
+    data: Bytes<'data>,
+}
+
+impl<'data> ResourceDirectory<'data> {
+    /// Construct from the data of the resource directory.
+    pub fn new(data: &'data [u8]) -> Self {
+        ResourceDirectory { data: Bytes(data) }
+    }
+
+    /// Parses the root resource directory table.
+    pub fn root(&self) -> Result<ResourceDirectoryTable<'data>> {
+        ResourceDirectoryTable::parse(*self, 0, 0)
+    }
+}
+
+/// A table of resource entries.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceDirectoryTable<'data> {
+    directory: ResourceDirectory<'data>,
+    header: &'data pe::ImageResourceDirectory,
+    entries: &'data [pe::ImageResourceDirectoryEntry],
+    depth: usize,
+}
+
+impl<'data> ResourceDirectoryTable<'data> {
+    fn parse(directory: ResourceDirectory<'data>, offset: u32, depth: usize) -> Result<Self> {
+        if depth >= MAX_RESOURCE_DEPTH {
+            return Err(Error("PE resource directory is nested too deeply"));
+        }
+        let mut data = directory.data;
+        data.skip(offset as usize)
+            .read_error("Invalid resource table offset")?;
+        let header = data
+            .read::<pe::ImageResourceDirectory>()
+            .read_error("Invalid resource table header")?;
+        let entries_count = header.number_of_id_entries.get(LE) as usize
+            + header.number_of_named_entries.get(LE) as usize;
+        let entries = data
+            .read_slice::<pe::ImageResourceDirectoryEntry>(entries_count)
+            .read_error("Invalid resource table entries")?;
+        Ok(ResourceDirectoryTable {
+            directory,
+            header,
+            entries,
+            depth,
+        })
+    }
+
+    /// Return the header of the table.
+    #[inline]
+    pub fn header(&self) -> &'data pe::ImageResourceDirectory {
+        self.header
+    }
+
+    /// Return an iterator over all of the entries in the table.
+    ///
+    /// The named entries are returned before the ID entries.
+    #[inline]
+    pub fn entries(&self) -> ResourceDirectoryEntryIterator<'data> {
+        self.iter(self.entries)
+    }
+
+    /// Return an iterator over the entries in the table that are identified by name.
+    pub fn named_entries(&self) -> ResourceDirectoryEntryIterator<'data> {
+        let count = self.header.number_of_named_entries.get(LE) as usize;
+        self.iter(&self.entries[..count])
+    }
+
+    /// Return an iterator over the entries in the table that are identified by ID.
+    pub fn id_entries(&self) -> ResourceDirectoryEntryIterator<'data> {
+        let count = self.header.number_of_named_entries.get(LE) as usize;
+        self.iter(&self.entries[count..])
+    }
+
+    fn iter(
+        &self,
+        entries: &'data [pe::ImageResourceDirectoryEntry],
+    ) -> ResourceDirectoryEntryIterator<'data> {
+        ResourceDirectoryEntryIterator {
+            directory: self.directory,
+            iter: entries.iter(),
+            depth: self.depth + 1,
+        }
+    }
+}
+
+/// An iterator over the entries of a `ResourceDirectoryTable`.
+#[derive(Debug)]
+pub struct ResourceDirectoryEntryIterator<'data> {
+    directory: ResourceDirectory<'data>,
+    iter: slice::Iter<'data, pe::ImageResourceDirectoryEntry>,
+    depth: usize,
+}
+
+impl<'data> Iterator for ResourceDirectoryEntryIterator<'data> {
+    type Item = ResourceDirectoryEntry<'data>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|entry| ResourceDirectoryEntry {
+            directory: self.directory,
+            entry,
+            depth: self.depth,
+        })
+    }
+}
+
+/// An entry in a `ResourceDirectoryTable`.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceDirectoryEntry<'data> {
+    directory: ResourceDirectory<'data>,
+    entry: &'data pe::ImageResourceDirectoryEntry,
+    depth: usize,
+}
+
+impl<'data> ResourceDirectoryEntry<'data> {
+    /// Return the raw directory entry.
+    #[inline]
+    pub fn raw_entry(&self) -> &'data pe::ImageResourceDirectoryEntry {
+        self.entry
+    }
+
+    /// Return true if the entry has a name, rather than an ID.
+    #[inline]
+    pub fn has_name(&self) -> bool {
+        self.entry.name_or_id.get(LE) & pe::IMAGE_RESOURCE_NAME_IS_STRING != 0
+    }
+
+    /// Return the name or ID of the entry.
+    pub fn name_or_id(&self) -> ResourceNameOrId<'data> {
+        let value = self.entry.name_or_id.get(LE);
+        if self.has_name() {
+            ResourceNameOrId::Name(ResourceName {
+                directory: self.directory,
+                offset: value & !pe::IMAGE_RESOURCE_NAME_IS_STRING,
+            })
+        } else {
+            ResourceNameOrId::Id(value as u16)
+        }
+    }
+
+    /// Return true if the entry is a subtable.
+    #[inline]
+    pub fn is_table(&self) -> bool {
+        self.entry.offset_to_data_or_directory.get(LE) & pe::IMAGE_RESOURCE_DATA_IS_DIRECTORY != 0
+    }
+
+    /// Return the data of the entry.
+    ///
+    /// Returns an error if the entry is a subtable that is nested too deeply.
+    pub fn data(&self) -> Result<ResourceDirectoryEntryData<'data>> {
+        let offset = self.entry.offset_to_data_or_directory.get(LE);
+        if self.is_table() {
+            ResourceDirectoryTable::parse(
+                self.directory,
+                offset & !pe::IMAGE_RESOURCE_DATA_IS_DIRECTORY,
+                self.depth,
+            )
+            .map(ResourceDirectoryEntryData::Table)
+        } else {
+            self.directory
+                .data
+                .read_at::<pe::ImageResourceDataEntry>(offset as usize)
+                .read_error("Invalid resource data entry")
+                .map(ResourceDirectoryEntryData::Data)
+        }
+    }
+}
+
+/// Data associated with a `ResourceDirectoryEntry`.
+#[derive(Debug, Clone, Copy)]
+pub enum ResourceDirectoryEntryData<'data> {
+    /// A subtable entry.
+    Table(ResourceDirectoryTable<'data>),
+    /// A resource data entry.
+    ///
+    /// Use `ImageResourceDataEntry::data` to read the resource data.
+    Data(&'data pe::ImageResourceDataEntry),
+}
+
+impl<'data> ResourceDirectoryEntryData<'data> {
+    /// Converts to an option of table.
+    ///
+    /// Helper for iterator filtering.
+    pub fn table(self) -> Option<ResourceDirectoryTable<'data>> {
+        match self {
+            Self::Table(dir) => Some(dir),
+            _ => None,
+        }
+    }
+
+    /// Converts to an option of data entry.
+    ///
+    /// Helper for iterator filtering.
+    pub fn data(self) -> Option<&'data pe::ImageResourceDataEntry> {
+        match self {
+            Self::Data(rsc) => Some(rsc),
+            _ => None,
+        }
+    }
+}
+
+/// A resource name or ID.
+#[derive(Debug, Clone, Copy)]
+pub enum ResourceNameOrId<'data> {
+    /// A resource name.
+    Name(ResourceName<'data>),
+    /// A resource ID.
+    Id(u16),
+}
+
+/// A resource name.
+///
+/// The name is stored as a length prefixed UTF-16 string.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceName<'data> {
+    directory: ResourceDirectory<'data>,
+    offset: u32,
+}
+
+impl<'data> ResourceName<'data> {
+    /// Return the raw UTF-16 code units of the name.
+    pub fn raw_data(&self) -> Result<&'data [U16Bytes<LE>]> {
+        let mut data = self.directory.data;
+        data.skip(self.offset as usize)
+            .read_error("Invalid resource name offset")?;
+        let len = data
+            .read::<U16Bytes<LE>>()
+            .read_error("Invalid resource name length")?;
+        data.read_slice::<U16Bytes<LE>>(len.get(LE) as usize)
+            .read_error("Invalid resource name length")
+    }
+
+    /// Convert the name to a string.
+    ///
+    /// Invalid UTF-16 is replaced with the replacement character.
+    pub fn to_string_lossy(&self) -> Result<String> {
+        let data = self.raw_data()?;
+        Ok(char::decode_utf16(data.iter().map(|c| c.get(LE)))
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect())
+    }
+}
+
+impl pe::ImageResourceDataEntry {
+    /// Get the data referenced by this resource data entry.
+    ///
+    /// `offset_to_data` is a relative virtual address, so this uses the section table
+    /// to find the data in the file.
+    pub fn data<'data, R: ReadRef<'data>>(
+        &self,
+        data: R,
+        sections: &SectionTable<'data>,
+    ) -> Result<&'data [u8]> {
+        sections
+            .pe_data_at(data, self.offset_to_data.get(LE))
+            .read_error("Invalid resource data entry address")?
+            .get(..self.size.get(LE) as usize)
+            .read_error("Invalid resource data entry size")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn table(data: &mut Vec<u8>, named: u16, id: u16) {
+        data.extend_from_slice(&[0; 12]);
+        data.extend_from_slice(&named.to_le_bytes());
+        data.extend_from_slice(&id.to_le_bytes());
+    }
+
+    fn entry(data: &mut Vec<u8>, name_or_id: u32, offset: u32) {
+        data.extend_from_slice(&name_or_id.to_le_bytes());
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+
+    #[test]
+    fn tree() {
+        let mut data = Vec::new();
+        // Root table at 0 with one named entry and one ID entry.
+        table(&mut data, 1, 1);
+        entry(&mut data, 0x8000_0000 | 72, 0x8000_0000 | 32);
+        entry(&mut data, 3, 56);
+        // Subtable at 32 with one ID entry.
+        table(&mut data, 0, 1);
+        entry(&mut data, 0x409, 56);
+        // Data entry at 56.
+        for value in &[0x1000u32, 4, 1200, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        // Name at 72.
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[b'H', 0, b'i', 0]);
+
+        let root = ResourceDirectory::new(&data).root().unwrap();
+        assert_eq!(root.entries().count(), 2);
+
+        let named = root.named_entries().collect::<Vec<_>>();
+        assert_eq!(named.len(), 1);
+        match named[0].name_or_id() {
+            ResourceNameOrId::Name(name) => assert_eq!(name.to_string_lossy().unwrap(), "Hi"),
+            ResourceNameOrId::Id(_) => panic!("expected name"),
+        }
+        let subtable = named[0].data().unwrap().table().unwrap();
+        let language = subtable.entries().next().unwrap();
+        assert!(!language.is_table());
+        let leaf = language.data().unwrap().data().unwrap();
+        assert_eq!(leaf.offset_to_data.get(LE), 0x1000);
+        assert_eq!(leaf.size.get(LE), 4);
+        assert_eq!(leaf.code_page.get(LE), 1200);
+
+        let ids = root.id_entries().collect::<Vec<_>>();
+        assert_eq!(ids.len(), 1);
+        match ids[0].name_or_id() {
+            ResourceNameOrId::Id(id) => assert_eq!(id, 3),
+            ResourceNameOrId::Name(_) => panic!("expected id"),
+        }
+    }
+
+    #[test]
+    fn cycle() {
+        let mut data = Vec::new();
+        // Root table with an entry that refers back to the root.
+        table(&mut data, 0, 1);
+        entry(&mut data, 1, 0x8000_0000);
+
+        let mut table = ResourceDirectory::new(&data).root().unwrap();
+        let mut depth = 0;
+        loop {
+            let entry = table.entries().next().unwrap();
+            match entry.data() {
+                Ok(data) => table = data.table().unwrap(),
+                Err(_) => break,
+            }
+            depth += 1;
+        }
+        assert_eq!(depth, MAX_RESOURCE_DEPTH - 1);
+    }
+}
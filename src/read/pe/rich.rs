@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::read::ReadRef;
+
+/// The "Rich" header of a PE file.
+///
+/// This is an undocumented header that is placed between the DOS stub and the
+/// NT headers by Microsoft linkers. It records the tools that were used to build
+/// the object files in the image.
+#[derive(Debug, Clone)]
+pub struct RichHeader {
+    /// The offset of the start of the header in the file.
+    ///
+    /// This is the offset of the `DanS` marker.
+    pub offset: usize,
+    /// The length of the header in bytes, including the trailing `Rich` marker and key.
+    pub length: usize,
+    /// The key that the header is masked with.
+    ///
+    /// This is also a checksum of the DOS header and the header entries.
+    pub xor_key: u32,
+    /// The decoded entries of the header.
+    pub entries: Vec<RichEntry>,
+}
+
+/// An entry in a PE "Rich" header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RichEntry {
+    /// The identifier of the tool that produced the objects.
+    pub product_id: u16,
+    /// The build number of the tool.
+    pub build_id: u16,
+    /// The number of objects that were produced by this tool.
+    pub count: u32,
+}
+
+impl RichEntry {
+    #[inline]
+    fn comp_id(&self) -> u32 {
+        (u32::from(self.product_id) << 16) | u32::from(self.build_id)
+    }
+}
+
+impl RichHeader {
+    /// Parse the rich header from the data of a PE file.
+    ///
+    /// `nt_header_offset` is the offset of the NT headers, which is the end of the
+    /// region that is searched for the header.
+    ///
+    /// Returns `None` if the header is not found or if its checksum is invalid.
+    pub fn parse<'data, R: ReadRef<'data>>(data: R, nt_header_offset: u64) -> Option<Self> {
+        let data = data.read_bytes_at(0, nt_header_offset).ok()?;
+        let dwords = data.len() / 4;
+        let dword = |index: usize| -> u32 {
+            let bytes = &data[index * 4..][..4];
+            u32::from_le_bytes(bytes.try_into().unwrap())
+        };
+
+        // The header ends with the `Rich` marker followed by the key.
+        let rich = (1..dwords.checked_sub(1)?)
+            .rev()
+            .find(|&index| &data[index * 4..][..4] == b"Rich")?;
+        let xor_key = dword(rich + 1);
+
+        // The header starts with the masked `DanS` marker followed by 3 masked zeros.
+        let dans = (0..rich)
+            .rev()
+            .find(|&index| dword(index) ^ xor_key == u32::from_le_bytes(*b"DanS"))?;
+        let first = dans + 4;
+        if first > rich || (rich - first) % 2 != 0 {
+            return None;
+        }
+        if (dans + 1..first).any(|index| dword(index) != xor_key) {
+            return None;
+        }
+
+        let entries: Vec<RichEntry> = (first..rich)
+            .step_by(2)
+            .map(|index| {
+                let comp_id = dword(index) ^ xor_key;
+                RichEntry {
+                    product_id: (comp_id >> 16) as u16,
+                    build_id: comp_id as u16,
+                    count: dword(index + 1) ^ xor_key,
+                }
+            })
+            .collect();
+
+        let offset = dans * 4;
+        if checksum(&data[..offset], &entries) != xor_key {
+            return None;
+        }
+
+        Some(RichHeader {
+            offset,
+            length: (rich + 2 - dans) * 4,
+            xor_key,
+            entries,
+        })
+    }
+}
+
+/// Calculate the checksum that is used as the key for the rich header.
+///
+/// `dos_data` is the data of the file up to the start of the rich header.
+fn checksum(dos_data: &[u8], entries: &[RichEntry]) -> u32 {
+    let mut checksum = dos_data.len() as u32;
+    for (i, byte) in dos_data.iter().enumerate() {
+        // The `e_lfanew` field is skipped.
+        if (0x3c..0x40).contains(&i) {
+            continue;
+        }
+        checksum = checksum.wrapping_add(u32::from(*byte).rotate_left(i as u32));
+    }
+    for entry in entries {
+        checksum = checksum.wrapping_add(entry.comp_id().rotate_left(entry.count));
+    }
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(entries: &[RichEntry], corrupt: bool) -> Vec<u8> {
+        let mut data = vec![0u8; 0x80];
+        data[0] = b'M';
+        data[1] = b'Z';
+        let mut xor_key = checksum(&data, entries);
+        if corrupt {
+            xor_key ^= 1;
+        }
+        let push = |data: &mut Vec<u8>, value: u32| data.extend_from_slice(&value.to_le_bytes());
+        push(&mut data, u32::from_le_bytes(*b"DanS") ^ xor_key);
+        for _ in 0..3 {
+            push(&mut data, xor_key);
+        }
+        for entry in entries {
+            push(&mut data, entry.comp_id() ^ xor_key);
+            push(&mut data, entry.count ^ xor_key);
+        }
+        data.extend_from_slice(b"Rich");
+        push(&mut data, xor_key);
+        push(&mut data, 0);
+        let len = data.len() as u32;
+        data[0x3c..0x40].copy_from_slice(&len.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn rich_header() {
+        let entries = [
+            RichEntry {
+                product_id: 0x0104,
+                build_id: 0x7809,
+                count: 12,
+            },
+            RichEntry {
+                product_id: 0x0105,
+                build_id: 0x7809,
+                count: 40,
+            },
+        ];
+        let data = build(&entries, false);
+        let rich = RichHeader::parse(&*data, data.len() as u64).unwrap();
+        assert_eq!(rich.offset, 0x80);
+        assert_eq!(rich.length, 4 * 4 + entries.len() * 8 + 8);
+        assert_eq!(rich.entries, entries);
+
+        let data = build(&entries, true);
+        assert!(RichHeader::parse(&*data, data.len() as u64).is_none());
+
+        let data = vec![0u8; 0x80];
+        assert!(RichHeader::parse(&*data, data.len() as u64).is_none());
+    }
+}
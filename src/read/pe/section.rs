@@ -1,11 +1,13 @@
+use alloc::borrow::Cow;
+use core::convert::TryInto;
 use core::marker::PhantomData;
 use core::{cmp, iter, result, slice, str};
 
-use crate::endian::LittleEndian as LE;
+use crate::endian::{Endianness, LittleEndian as LE};
 use crate::pe;
 use crate::read::{
     self, CompressedData, CompressedFileRange, ObjectSection, ObjectSegment, ReadError, ReadRef,
-    Relocation, Result, SectionFlags, SectionIndex, SectionKind,
+    Relocation, Result, SectionFlags, SectionIndex, SectionKind, SegmentFlags,
 };
 
 use super::{ImageNtHeaders, PeFile, SectionTable};
@@ -25,7 +27,7 @@ where
     R: ReadRef<'data>,
 {
     pub(super) file: &'file PeFile<'data, Pe, R>,
-    pub(super) iter: slice::Iter<'file, pe::ImageSectionHeader>,
+    pub(super) iter: iter::Enumerate<slice::Iter<'file, pe::ImageSectionHeader>>,
 }
 
 impl<'data, 'file, Pe, R> Iterator for PeSegmentIterator<'data, 'file, Pe, R>
@@ -36,8 +38,9 @@ where
     type Item = PeSegment<'data, 'file, Pe, R>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|section| PeSegment {
+        self.iter.next().map(|(index, section)| PeSegment {
             file: self.file,
+            index: SectionIndex(index + 1),
             section,
         })
     }
@@ -58,6 +61,7 @@ where
     R: ReadRef<'data>,
 {
     file: &'file PeFile<'data, Pe, R>,
+    index: SectionIndex,
     section: &'file pe::ImageSectionHeader,
 }
 
@@ -71,6 +75,17 @@ where
             .pe_data(self.file.data)
             .read_error("Invalid PE section offset or size")
     }
+
+    /// Return an iterator over the sections in this segment.
+    ///
+    /// PE segments are the same as sections, so this always returns a single section.
+    pub fn sections(&self) -> iter::Once<PeSection<'data, 'file, Pe, R>> {
+        iter::once(PeSection {
+            file: self.file,
+            index: self.index,
+            section: self.section,
+        })
+    }
 }
 
 impl<'data, 'file, Pe, R> read::private::Sealed for PeSegment<'data, 'file, Pe, R>
@@ -90,6 +105,11 @@ where
         u64::from(self.section.virtual_address.get(LE)).wrapping_add(self.file.common.image_base)
     }
 
+    #[inline]
+    fn relative_address(&self) -> u64 {
+        u64::from(self.section.virtual_address.get(LE))
+    }
+
     #[inline]
     fn size(&self) -> u64 {
         u64::from(self.section.virtual_size.get(LE))
@@ -128,6 +148,12 @@ where
                 .read_error("Non UTF-8 PE section name")?,
         ))
     }
+
+    #[inline]
+    fn flags(&self) -> SegmentFlags {
+        let characteristics = self.section.characteristics.get(LE);
+        SegmentFlags::Coff { characteristics }
+    }
 }
 
 /// An iterator over the sections of a `PeFile32`.
@@ -221,6 +247,11 @@ where
         u64::from(self.section.virtual_address.get(LE)).wrapping_add(self.file.common.image_base)
     }
 
+    #[inline]
+    fn relative_address(&self) -> u64 {
+        u64::from(self.section.virtual_address.get(LE))
+    }
+
     #[inline]
     fn size(&self) -> u64 {
         u64::from(self.section.virtual_size.get(LE))
@@ -264,6 +295,11 @@ where
         self.data().map(CompressedData::none)
     }
 
+    /// PE sections do not have relocations, so this returns the section data unchanged.
+    fn relocated_data(&self) -> Result<Cow<'data, [u8]>> {
+        self.data().map(Cow::Borrowed)
+    }
+
     #[inline]
     fn name(&self) -> Result<&str> {
         let name = self.section.name(self.file.common.symbols.strings())?;
@@ -282,6 +318,15 @@ where
         self.section.kind()
     }
 
+    #[inline]
+    fn is_stub(&self) -> bool {
+        false
+    }
+
+    fn endianness(&self) -> Endianness {
+        Endianness::Little
+    }
+
     fn relocations(&self) -> PeRelocationIterator<'data, 'file, R> {
         PeRelocationIterator(PhantomData)
     }
@@ -324,7 +369,7 @@ impl pe::ImageSectionHeader {
         let section_va = self.virtual_address.get(LE);
         let offset = va.checked_sub(section_va)?;
         let section_data = self.pe_data(data).ok()?;
-        section_data.get(offset as usize..)
+        section_data.get(offset.try_into().ok()?..)
     }
 }
 
@@ -16,6 +16,30 @@ use crate::read::ReadRef;
 /// them to be returned. Entries in the cache are never removed.
 /// Entries are keyed on the offset and size of the read.
 /// Currently overlapping reads are considered separate reads.
+///
+/// This allows parsing a file without reading all of it into memory first,
+/// and without needing to manage a separate buffer for the `'data` lifetime.
+/// The cache must outlive the parsed file.
+///
+/// ```no_run
+/// use object::{Object, ObjectSection};
+///
+/// fn dump(path: &str) -> Result<(), Box<dyn std::error::Error>> {
+///     let cache = object::ReadCache::new(std::fs::File::open(path)?);
+///     let file = object::File::parse(&cache)?;
+///     for section in file.sections() {
+///         println!("{}", section.name()?);
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// If memory-mapping is preferred, the mapping can be passed directly to
+/// `File::parse` instead, since it dereferences to `&[u8]`. This crate does
+/// not perform the mapping itself because it requires `unsafe`.
+///
+/// The cache uses interior mutability, so it is not `Sync`, and files that are
+/// parsed from it can't be shared between threads.
 #[derive(Debug)]
 pub struct ReadCache<R: Read + Seek> {
     cache: RefCell<ReadCacheInternal<R>>,
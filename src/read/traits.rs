@@ -1,12 +1,18 @@
 use alloc::borrow::Cow;
+use alloc::string::String;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::{TryFrom, TryInto};
 
 use crate::read::{
-    self, Architecture, CodeView, ComdatKind, CompressedData, CompressedFileRange, Export,
-    FileFlags, Import, ObjectMap, Relocation, Result, SectionFlags, SectionIndex, SectionKind,
-    SymbolFlags, SymbolIndex, SymbolKind, SymbolMap, SymbolMapName, SymbolScope, SymbolSection,
+    self, AddressMap, AddressSize, Architecture, CodeView, ComdatKind, CompressedData,
+    CompressedFileRange, CompressionFormat, DwarfSectionId, Error, Export, ExportedSymbol,
+    FileFlags, GroupedSection, Import, ObjectMap, OwnedSymbol, ReadError, Relocation,
+    RelocationTarget, Result, SectionFlags, SectionIndex, SectionKind, SegmentFlags, SymbolFlags,
+    SymbolIndex, SymbolKind, SymbolMap, SymbolMapName, SymbolScope, SymbolSection,
+    ValidationWarning,
 };
-use crate::Endianness;
+use crate::{Bytes, Endian, Endianness};
 
 /// An object file.
 pub trait Object<'data: 'file, 'file>: read::private::Sealed {
@@ -66,9 +72,74 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
     /// Return true if the file can contain 64-bit addresses.
     fn is_64(&self) -> bool;
 
+    /// Return true if the file is a relocatable object file.
+    ///
+    /// This is true for ELF `ET_REL` files, COFF object files, Mach-O `MH_OBJECT`
+    /// files, and Wasm files that contain a `linking` section. These files are
+    /// intended to be linked, rather than loaded, so section addresses are
+    /// typically zero and relocations must be applied to the section data.
+    ///
+    /// This is false for executables and shared libraries.
+    fn is_relocatable(&self) -> bool;
+
     /// Get an iterator over the segments in the file.
     fn segments(&'file self) -> Self::SegmentIterator;
 
+    /// Get the segment named `segment_name`, if such a segment exists.
+    ///
+    /// For Mach-O, this is the name in the segment load command.
+    /// For PE, each segment is a section, so this matches the section name.
+    /// ELF segments do not have names, so this always returns `None`.
+    ///
+    /// If multiple segments have the same name, the first matching segment will be used.
+    fn segment_by_name(&'file self, segment_name: &str) -> Option<Self::Segment> {
+        self.segments()
+            .find(|segment| segment.name() == Ok(Some(segment_name)))
+    }
+
+    /// Return true if a segment named `segment_name` exists.
+    ///
+    /// This uses the same name matching as `segment_by_name`.
+    #[inline]
+    fn has_segment(&'file self, segment_name: &str) -> bool {
+        self.segment_by_name(segment_name).is_some()
+    }
+
+    /// Get the range of virtual addresses that are mapped by the segments in the file.
+    ///
+    /// Returns the lowest address and the end address of the segments. The end
+    /// address is exclusive and includes any zero-fill memory. Segments with
+    /// a size of zero are ignored.
+    ///
+    /// For Mach-O files, segments without any access permissions, such as `__PAGEZERO`,
+    /// are also ignored.
+    ///
+    /// Returns `(0, 0)` if the file has no segments, or if it is a relocatable
+    /// object file.
+    fn address_range(&'file self) -> (u64, u64) {
+        read::util::segments_address_range(self.segments())
+    }
+
+    /// Return true if the virtual address is within one of the segments in the file.
+    ///
+    /// The range of each segment includes any zero-fill memory. Unlike `address_range`,
+    /// this returns false for addresses in the gaps between segments.
+    ///
+    /// This does not require reading the segment data.
+    ///
+    /// For Mach-O files, segments without any access permissions, such as `__PAGEZERO`,
+    /// are ignored.
+    ///
+    /// Returns false for relocatable object files.
+    fn contains_address(&'file self, address: u64) -> bool {
+        if self.is_relocatable() {
+            return false;
+        }
+        self.segments().any(|segment| {
+            address >= segment.address() && address - segment.address() < segment.size()
+        })
+    }
+
     /// Get the section named `section_name`, if such a section exists.
     ///
     /// If `section_name` starts with a '.' then it is treated as a system section name,
@@ -79,12 +150,71 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
     /// - if ".debug_info" is requested for an ELF object file, then
     /// ".zdebug_info" may be returned (and similarly for other debug sections).
     ///
+    /// For Mach-O, the following names are translated:
+    ///
+    /// | Requested | Mach-O                |
+    /// |-----------|-----------------------|
+    /// | `.text`   | `__text`              |
+    /// | `.data`   | `__data`              |
+    /// | `.bss`    | `__bss`               |
+    /// | `.rodata` | `__const` in `__TEXT` |
+    /// | `.name`   | `__name`              |
+    ///
+    /// Exact format-specific names such as "__text" can also be used.
+    ///
     /// For some object files, multiple segments may contain sections with the same
     /// name. In this case, the first matching section will be used.
     ///
     /// This method skips over sections with invalid names.
     fn section_by_name(&'file self, section_name: &str) -> Option<Self::Section>;
 
+    /// Get all of the sections named `section_name`, in the order they are stored in the file.
+    ///
+    /// Some files contain multiple sections with the same name, such as COFF object
+    /// files that use a separate `.text` section for each COMDAT function.
+    /// `section_by_name` only returns the first of these.
+    ///
+    /// The name is translated for the file format in the same way as for
+    /// `section_by_name`, so the first section that is returned is the section
+    /// that `section_by_name` returns.
+    fn sections_by_name(&'file self, section_name: &str) -> Vec<Self::Section> {
+        self.sections()
+            .filter(|section| section.name() == Ok(section_name))
+            .collect()
+    }
+
+    /// Get the data of the section with the given name.
+    ///
+    /// The name is translated in the same way as for `section_by_name`.
+    ///
+    /// Returns `Ok(None)` if there is no section with that name.
+    fn section_data_by_name(&'file self, section_name: &str) -> Result<Option<&'data [u8]>> {
+        self.section_by_name(section_name)
+            .map(|section| section.data())
+            .transpose()
+    }
+
+    /// Get the uncompressed data of the given DWARF section.
+    ///
+    /// The section name is translated for the file format in the same way as
+    /// for `section_by_name`, so this can be used to load sections for `gimli`.
+    ///
+    /// Returns `Ok(None)` if there is no such section.
+    fn dwarf_section(&'file self, id: DwarfSectionId) -> Result<Option<Cow<'data, [u8]>>> {
+        self.section_by_name(id.name())
+            .map(|section| section.uncompressed_data())
+            .transpose()
+    }
+
+    /// Return true if a section named `section_name` exists.
+    ///
+    /// This uses the same name matching as `section_by_name`, and does not read
+    /// the section data.
+    #[inline]
+    fn has_section(&'file self, section_name: &str) -> bool {
+        self.section_by_name(section_name).is_some()
+    }
+
     /// Get the section at the given index.
     ///
     /// The meaning of the index depends on the object file.
@@ -95,8 +225,125 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
     fn section_by_index(&'file self, index: SectionIndex) -> Result<Self::Section>;
 
     /// Get an iterator over the sections in the file.
+    ///
+    /// The sections are returned in the order that they are stored in the
+    /// section table of the file, so the order is the same each time the
+    /// file is parsed. For Mach-O, this is the order of the sections within the
+    /// segment load commands.
     fn sections(&'file self) -> Self::SectionIterator;
 
+    /// Get the names and data of all sections in the file.
+    ///
+    /// The sections are returned in the same order as `Object::sections`.
+    /// Sections that have no data in the file, such as zero-fill sections,
+    /// are returned with an empty slice.
+    ///
+    /// Returns an error if the name or data of any section is invalid.
+    fn section_data_map(&'file self) -> Result<Vec<(String, &'data [u8])>> {
+        let mut sections = Vec::new();
+        for section in self.sections() {
+            let name = section.name()?.into();
+            let data = section.data()?;
+            sections.push((name, data));
+        }
+        Ok(sections)
+    }
+
+    /// Get the sections in the file, with grouped COFF sections combined.
+    ///
+    /// In COFF files, sections with names of the form `name$suffix` are grouped
+    /// sections. The linker combines all sections with the same name before the `$`
+    /// into a single section, sorted by the suffix. This returns the result of that
+    /// combination, with the data of each section aligned and concatenated.
+    /// Sections with the same full name are kept in the order of the section table.
+    ///
+    /// The groups are returned in the order of the first section of each group
+    /// in the section table. For other file formats, each section is returned
+    /// as its own group.
+    ///
+    /// Returns an error if the name or data of any section is invalid.
+    fn grouped_sections(&'file self) -> Result<Vec<GroupedSection<'data>>> {
+        let mut groups: Vec<(String, Vec<(String, Self::Section)>)> = Vec::new();
+        for section in self.sections() {
+            let name = String::from(section.name()?);
+            let base = match (section.flags(), name.find('$')) {
+                (SectionFlags::Coff { .. }, Some(index)) => String::from(&name[..index]),
+                _ => name.clone(),
+            };
+            let member = (name, section);
+            match groups.iter_mut().find(|group| group.0 == base) {
+                Some(group) => group.1.push(member),
+                None => groups.push((base, vec![member])),
+            }
+        }
+
+        let mut grouped = Vec::new();
+        for (name, mut members) in groups {
+            members.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut sections = Vec::new();
+            let mut data = Cow::Borrowed(&[][..]);
+            for (_, section) in members {
+                let section_data = section.data()?;
+                if sections.is_empty() {
+                    data = Cow::Borrowed(section_data);
+                } else {
+                    let data = data.to_mut();
+                    let align = section.align().max(1) as usize;
+                    let padding = (align - data.len() % align) % align;
+                    data.resize(data.len() + padding, 0);
+                    data.extend_from_slice(section_data);
+                }
+                sections.push(section.index());
+            }
+            grouped.push(GroupedSection {
+                name,
+                sections,
+                data,
+            });
+        }
+        Ok(grouped)
+    }
+
+    /// Read a null terminated string at the given virtual address.
+    ///
+    /// The string is read from the data of the first section containing the address,
+    /// and must be terminated before the end of that section.
+    /// Does not assume any encoding. Does not return the null byte.
+    ///
+    /// Returns `None` if the address is not within the file data of a section,
+    /// or if the string is not terminated.
+    ///
+    /// For executables and shared libraries, the section is found with a binary search
+    /// of an index that is built on the first call and then cached in the file.
+    /// For relocatable files, the sections are searched in order, because their
+    /// addresses usually overlap.
+    ///
+    /// For ELF core files and ELF files without section headers, the `PT_LOAD`
+    /// segments are used instead of the sections.
+    fn cstr_at(&'file self, address: u64) -> Option<&'data [u8]> {
+        read::util::sections_cstr_at(self, address)
+    }
+
+    /// Construct a map from virtual addresses to the file data of the sections.
+    ///
+    /// The map uses a binary search to find the section containing an address.
+    /// Sections that have no data in the file, such as zero-fill sections,
+    /// and sections with invalid data are not included.
+    ///
+    /// This is intended for executables and shared libraries. In relocatable
+    /// object files, section addresses are usually zero, so they overlap
+    /// and only the first section is included.
+    ///
+    /// For ELF core files and ELF files without section headers, the `PT_LOAD`
+    /// segments are used instead of the sections.
+    fn address_map(&'file self) -> AddressMap<'data> {
+        let ranges = self
+            .sections()
+            .filter_map(|section| Some((section.address(), section.data().ok()?)))
+            .collect();
+        AddressMap::new(ranges)
+    }
+
     /// Get an iterator over the COMDAT section groups in the file.
     fn comdats(&'file self) -> Self::ComdatIterator;
 
@@ -110,13 +357,162 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
     /// Returns an error if the index is invalid.
     fn symbol_by_index(&'file self, index: SymbolIndex) -> Result<Self::Symbol>;
 
+    /// Get the data for the given symbol from the section that defines it.
+    ///
+    /// The data is found using the address and size of the symbol.
+    ///
+    /// Returns `Ok(None)` if the symbol is undefined, has a size of zero, or
+    /// is not contained in its section. Also returns `Ok(None)` for TLS symbols
+    /// in files that are not relocatable, because their address is not a
+    /// virtual address.
+    ///
+    /// Mach-O symbols and most COFF symbols do not have a size, so this always
+    /// returns `Ok(None)` for them.
+    fn symbol_data(&'file self, symbol: &Self::Symbol) -> Result<Option<&'data [u8]>> {
+        if symbol.is_undefined()
+            || symbol.size() == 0
+            || (symbol.kind() == SymbolKind::Tls && !self.is_relocatable())
+        {
+            return Ok(None);
+        }
+        let section = match symbol.section_index() {
+            Some(index) => self.section_by_index(index)?,
+            None => return Ok(None),
+        };
+        section.data_range(symbol.address(), symbol.size())
+    }
+
     /// Get an iterator over the debugging symbols in the file.
     ///
     /// This may skip over symbols that are malformed or unsupported.
     ///
-    /// For Mach-O files, this does not include STAB entries.
+    /// For Mach-O files, this does not include STAB entries. These are available
+    /// from `MachOFile::stabs`.
+    ///
+    /// The symbols are returned in the order that they are stored in the
+    /// symbol table of the file, with increasing symbol indices.
     fn symbols(&'file self) -> Self::SymbolIterator;
 
+    /// Get the local symbols in the file.
+    ///
+    /// These are the symbols in `symbols` for which `ObjectSymbol::is_local` is true,
+    /// in the same order.
+    ///
+    /// For ELF, this uses the `sh_info` field of the symbol table, and so it does
+    /// not read the global symbols. This includes the null symbol at index 0.
+    fn local_symbols(&'file self) -> Vec<Self::Symbol> {
+        self.symbols().filter(|symbol| symbol.is_local()).collect()
+    }
+
+    /// Get the global symbols in the file.
+    ///
+    /// These are the symbols in `symbols` for which `ObjectSymbol::is_global` is true,
+    /// in the same order.
+    ///
+    /// For ELF, this uses the `sh_info` field of the symbol table, and so it does
+    /// not read the local symbols.
+    fn global_symbols(&'file self) -> Vec<Self::Symbol> {
+        self.symbols().filter(|symbol| symbol.is_global()).collect()
+    }
+
+    /// Get the symbols that are defined in the given section.
+    ///
+    /// The symbols are sorted by address. Symbols with the same address are
+    /// returned in the order that they are stored in the symbol table.
+    fn symbols_in_section(&'file self, section_index: SectionIndex) -> Vec<Self::Symbol> {
+        let mut symbols: Vec<_> = self
+            .symbols()
+            .filter(|symbol| {
+                symbol.is_definition() && symbol.section_index() == Some(section_index)
+            })
+            .collect();
+        symbols.sort_by_key(|symbol| symbol.address());
+        symbols
+    }
+
+    /// Get the defined symbols, sorted by address.
+    ///
+    /// The symbols are sorted using `ObjectSymbol::cmp_by_address`.
+    fn symbols_sorted_by_address(&'file self) -> Vec<Self::Symbol> {
+        let mut symbols: Vec<_> = self
+            .symbols()
+            .filter(|symbol| symbol.is_definition())
+            .collect();
+        symbols.sort_by(ObjectSymbol::cmp_by_address);
+        symbols
+    }
+
+    /// Get the defined symbol that best describes the given address.
+    ///
+    /// A symbol is a candidate if the address is within the range given by the
+    /// symbol address and size. Symbols with a size of zero, such as labels or
+    /// symbols in file formats that do not record sizes, are candidates if no other
+    /// zero size symbol is between them and the address.
+    ///
+    /// If there are multiple candidates, then the symbol is chosen deterministically
+    /// by preferring, in order:
+    /// - text symbols
+    /// - symbols with a larger size
+    /// - global symbols
+    /// - symbols with a name that sorts first
+    /// - symbols with a lower index
+    fn symbol_for_address(&'file self, address: u64) -> Option<Self::Symbol> {
+        let mut zero_size_address = None;
+        let mut candidates = Vec::new();
+        for symbol in self.symbols() {
+            if !symbol.is_definition() || symbol.address() > address {
+                continue;
+            }
+            if symbol.size() == 0 {
+                if zero_size_address.map_or(true, |zero_size_address| {
+                    zero_size_address < symbol.address()
+                }) {
+                    zero_size_address = Some(symbol.address());
+                }
+            } else if address - symbol.address() >= symbol.size() {
+                continue;
+            }
+            candidates.push(symbol);
+        }
+        candidates
+            .into_iter()
+            .filter(|symbol| symbol.size() != 0 || Some(symbol.address()) == zero_size_address)
+            .min_by(|a, b| {
+                let is_text = |symbol: &Self::Symbol| symbol.kind() == SymbolKind::Text;
+                is_text(b)
+                    .cmp(&is_text(a))
+                    .then_with(|| b.size().cmp(&a.size()))
+                    .then_with(|| b.is_global().cmp(&a.is_global()))
+                    .then_with(|| a.name().unwrap_or("").cmp(b.name().unwrap_or("")))
+                    .then_with(|| a.index().0.cmp(&b.index().0))
+            })
+    }
+
+    /// Get copies of the symbols in the symbol table that do not borrow from the file data.
+    ///
+    /// This is useful for storing symbols in a cache that outlives the file.
+    /// Names that are not valid UTF-8 are converted lossily, so the result
+    /// contains an entry for every symbol returned by `symbols`.
+    fn owned_symbols(&'file self) -> Vec<OwnedSymbol> {
+        self.symbols()
+            .map(|symbol| {
+                let name = symbol
+                    .name_bytes()
+                    .map(|name| String::from_utf8_lossy(name).into_owned())
+                    .unwrap_or_default();
+                OwnedSymbol {
+                    name,
+                    address: symbol.address(),
+                    size: symbol.size(),
+                    kind: symbol.kind(),
+                    section: symbol.section(),
+                    scope: symbol.scope(),
+                    is_weak: symbol.is_weak(),
+                }
+            })
+            .collect()
+    }
+
     /// Get the dynamic linking symbol table, if any.
     ///
     /// Only ELF has a separate dynamic linking symbol table.
@@ -134,20 +530,90 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
     ///
     /// Symbol indices in these relocations refer to the dynamic symbol table.
     ///
-    /// Only ELF has dynamic relocations.
+    /// For ELF, these are the relocations in the dynamic relocation sections.
+    /// For PE, these are the base relocations, with an absolute target and an
+    /// implicit addend.
+    ///
+    /// Mach-O is not yet supported, and returns `None`. Its rebase and bind
+    /// information is not returned; use `MachOFile::chained_fixups` for files
+    /// that use chained fixups.
+    ///
+    /// Other file formats have no dynamic relocations.
     fn dynamic_relocations(&'file self) -> Option<Self::DynamicRelocationIterator>;
 
+    /// Return the sections that have relocations.
+    ///
+    /// Returns pairs of `(relocation_section, target_section)`, where the relocations
+    /// stored in `relocation_section` are applied to `target_section`.
+    /// The relocations are the ones returned by `ObjectSection::relocations`
+    /// for the target section. Dynamic relocations are not included.
+    ///
+    /// For ELF, the relocation section is a separate `SHT_REL` or `SHT_RELA` section,
+    /// and a target section may have multiple relocation sections.
+    /// For other file formats, relocations are attached to the section directly,
+    /// so both indices are the same.
+    fn relocation_sections(&'file self) -> Vec<(SectionIndex, SectionIndex)> {
+        self.sections()
+            .filter(|section| section.relocations().next().is_some())
+            .map(|section| (section.index(), section.index()))
+            .collect()
+    }
+
+    /// Get the complete addend of a relocation at the given offset in a section.
+    ///
+    /// Relocations with an explicit addend store it in the relocation entry, and this
+    /// is the same as `Relocation::addend`. Relocations with an implicit addend store it
+    /// in the section data at the offset being relocated, and this reads the sign
+    /// extended value from the section data and adds it to `Relocation::addend`.
+    /// Use `Relocation::has_implicit_addend` to determine which form is used.
+    ///
+    /// For ELF, `SHT_RELA` sections have explicit addends, and `SHT_REL` sections have
+    /// implicit addends. 32-bit x86, 32-bit ARM and 32-bit MIPS use `SHT_REL`, and
+    /// most other architectures, such as x86-64, AArch64 and RISC-V, use `SHT_RELA`.
+    /// COFF and Mach-O relocations always have implicit addends.
+    ///
+    /// Returns an error if the addend is implicit and the relocation size is unknown,
+    /// or if the offset is invalid.
+    fn relocation_addend(
+        &'file self,
+        section: &Self::Section,
+        offset: u64,
+        relocation: &Relocation,
+    ) -> Result<i64> {
+        if !relocation.has_implicit_addend() {
+            return Ok(relocation.addend());
+        }
+        let size = match relocation.size() {
+            8 => 1,
+            16 => 2,
+            32 => 4,
+            64 => 8,
+            _ => return Err(Error("Unsupported relocation size for implicit addend")),
+        };
+        let data = section.uncompressed_data()?;
+        let bytes = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| data.get(offset..))
+            .and_then(|bytes| bytes.get(..size))
+            .read_error("Invalid relocation offset")?;
+        let value = read::util::read_sint(bytes, !self.is_little_endian());
+        Ok(relocation.addend().wrapping_add(value as i64))
+    }
+
     /// Construct a map from addresses to symbol names.
     ///
     /// The map will only contain defined text and data symbols.
     /// The dynamic symbol table will only be used if there are no debugging symbols.
+    /// Symbols with the same address are ordered using `ObjectSymbol::cmp_by_address`.
     fn symbol_map(&'file self) -> SymbolMap<SymbolMapName<'data>> {
         let mut symbols = Vec::new();
         if let Some(table) = self.symbol_table().or_else(|| self.dynamic_symbol_table()) {
-            for symbol in table.symbols() {
-                if !symbol.is_definition() {
-                    continue;
-                }
+            let mut table_symbols: Vec<_> = table
+                .symbols()
+                .filter(|symbol| symbol.is_definition())
+                .collect();
+            table_symbols.sort_by(ObjectSymbol::cmp_by_address);
+            for symbol in table_symbols {
                 if let Ok(name) = symbol.name() {
                     symbols.push(SymbolMapName::new(symbol.address(), name));
                 }
@@ -169,6 +635,35 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
     /// Get the exported symbols.
     fn exports(&self) -> Result<Vec<Export<'data>>>;
 
+    /// Get the symbols that this file provides for use by other files.
+    ///
+    /// This uses the best source of information that is available for the file format:
+    /// the export directory for PE, the export trie for Mach-O (falling back to the
+    /// symbol table if there is no trie), and the defined global symbols in the
+    /// dynamic symbol table for ELF.
+    fn exported_symbols(&self) -> Result<Vec<ExportedSymbol<'data>>> {
+        Ok(self.exports()?.into_iter().map(Into::into).collect())
+    }
+
+    /// Get the addresses of the static initialization functions.
+    ///
+    /// For ELF, these are the entries in `SHT_PREINIT_ARRAY` sections, `.ctors`
+    /// sections and `SHT_INIT_ARRAY` sections, in the order that they are called.
+    /// For Mach-O, these are the entries in `S_MOD_INIT_FUNC_POINTERS` and
+    /// `S_INIT_FUNC_OFFSETS` sections.
+    /// For COFF, these are the entries in `.CRT$XC*` sections. PE files are not
+    /// supported, because the linker merges these sections with other CRT sections.
+    ///
+    /// For relocatable files, the relocations for these sections are applied.
+    /// For other files, ELF dynamic relocations that have an explicit addend
+    /// and Mach-O chained fixups are applied, and null entries are skipped.
+    fn init_functions(&'file self) -> Result<Vec<u64>> {
+        Ok(init_function_pointers(self)?
+            .into_iter()
+            .map(|(_, function)| function)
+            .collect())
+    }
+
     /// Return true if the file contains debug information sections, false if not.
     fn has_debug_symbols(&self) -> bool;
 
@@ -191,6 +686,10 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
     }
 
     /// The filename and build ID from a `.gnu_debugaltlink` section.
+    ///
+    /// This section refers to a supplementary file containing DWARF sections
+    /// that are shared between multiple files, such as those created by `dwz`.
+    /// The build ID of the supplementary file must match the returned build ID.
     #[inline]
     fn gnu_debugaltlink(&self) -> Result<Option<(&'data [u8], &'data [u8])>> {
         Ok(None)
@@ -202,16 +701,146 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
         Ok(None)
     }
 
+    /// A 16 byte UUID that identifies the file.
+    ///
+    /// This is the Mach-O `LC_UUID`, the first 16 bytes of the ELF build ID,
+    /// or the GUID from the PE CodeView section.
+    ///
+    /// The PE GUID is converted from its mixed-endian storage to the big-endian
+    /// byte order that is used when printing it, matching the byte order of the
+    /// other formats.
+    ///
+    /// Returns `Ok(None)` if there is no UUID, or if the ELF build ID is shorter
+    /// than 16 bytes.
+    fn uuid(&self) -> Result<Option<[u8; 16]>> {
+        if let Some(uuid) = self.mach_uuid()? {
+            return Ok(Some(uuid));
+        }
+        if let Some(build_id) = self.build_id()? {
+            return Ok(build_id.get(..16).and_then(|uuid| uuid.try_into().ok()));
+        }
+        if let Some(pdb_info) = self.pdb_info()? {
+            let mut uuid = pdb_info.guid();
+            uuid[..4].reverse();
+            uuid[4..6].reverse();
+            uuid[6..8].reverse();
+            return Ok(Some(uuid));
+        }
+        Ok(None)
+    }
+
+    /// Get descriptions of the tools that produced the file.
+    ///
+    /// For ELF, these are the strings in the `.comment` section.
+    /// For Mach-O, these are the tools in the `LC_BUILD_VERSION` load commands.
+    /// For PE, these are the products in the "Rich" header.
+    ///
+    /// Malformed data is skipped.
+    fn compiler_info(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Get the base address used for relative virtual addresses.
     ///
     /// Currently this is only non-zero for PE.
+    ///
+    /// All addresses returned by this crate are absolute virtual addresses,
+    /// including for PE. Subtract this base to convert them to relative virtual
+    /// addresses, or use the `relative_address` methods of sections, segments
+    /// and symbols.
     fn relative_address_base(&'file self) -> u64;
 
+    /// Get the address that the linker intended the file to be loaded at.
+    ///
+    /// This is the address that the addresses in the file are relative to when
+    /// the file is not relocated. Unlike `relative_address_base`, this is not
+    /// used to interpret any addresses returned by this crate.
+    ///
+    /// For PE, this is the image base. For Mach-O, this is the address of the
+    /// `__TEXT` segment. For ELF, this is the lowest address of the `PT_LOAD`
+    /// segments.
+    ///
+    /// Returns 0 if the file has no preferred address, such as for relocatable
+    /// object files and most position independent executables.
+    fn preferred_load_address(&'file self) -> u64;
+
     /// Get the virtual address of the entry point of the binary
     fn entry(&'file self) -> u64;
 
     /// File flags that are specific to each file format.
     fn flags(&self) -> FileFlags;
+
+    /// Return the minimum size of the file data that contains the file ranges
+    /// of all sections and segments.
+    ///
+    /// If this is larger than the size of the file data, then the file is truncated.
+    /// See also `File::parse_complete`.
+    fn required_file_size(&'file self) -> u64 {
+        let sections = self
+            .sections()
+            .filter_map(|section| section.file_range())
+            .map(|(offset, size)| offset.saturating_add(size));
+        let segments = self
+            .segments()
+            .map(|segment| segment.file_range())
+            .map(|(offset, size)| offset.saturating_add(size));
+        sections.chain(segments).max().unwrap_or(0)
+    }
+
+    /// Check the file for inconsistencies that may cause other methods to give wrong results.
+    ///
+    /// This checks for overlapping section file ranges, sections that extend past
+    /// the end of the file, segments with a file size that is larger than their
+    /// memory size, and symbols that refer to sections that do not exist.
+    ///
+    /// This does not check everything that may be malformed, and an empty result
+    /// does not mean that the file is valid.
+    fn validate(&'file self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+
+        let mut ranges = Vec::new();
+        for section in self.sections() {
+            if let Some((offset, size)) = section.file_range() {
+                if section.data().is_err() {
+                    warnings.push(ValidationWarning::SectionOutOfBounds(section.index()));
+                } else if size != 0 {
+                    ranges.push((offset, offset.saturating_add(size), section.index()));
+                }
+            }
+        }
+        ranges.sort_by_key(|&(offset, end, _)| (offset, end));
+        let mut previous: Option<(u64, SectionIndex)> = None;
+        for (offset, end, index) in ranges {
+            if let Some((previous_end, previous_index)) = previous {
+                if offset < previous_end {
+                    warnings.push(ValidationWarning::SectionOverlap(previous_index, index));
+                }
+                if end <= previous_end {
+                    continue;
+                }
+            }
+            previous = Some((end, index));
+        }
+
+        for (index, segment) in self.segments().enumerate() {
+            if segment.file_range().1 > segment.size() {
+                warnings.push(ValidationWarning::SegmentSizeMismatch(index));
+            }
+        }
+
+        for symbol in self.symbols() {
+            if let Some(section_index) = symbol.section_index() {
+                if self.section_by_index(section_index).is_err() {
+                    warnings.push(ValidationWarning::SymbolSectionInvalid(
+                        symbol.index(),
+                        section_index,
+                    ));
+                }
+            }
+        }
+
+        warnings
+    }
 }
 
 /// A loadable segment defined in an object file.
@@ -220,8 +849,19 @@ pub trait Object<'data: 'file, 'file>: read::private::Sealed {
 /// For Mach-O, this is a load command with type `LC_SEGMENT` or `LC_SEGMENT_64`.
 pub trait ObjectSegment<'data>: read::private::Sealed {
     /// Returns the virtual address of the segment.
+    ///
+    /// For PE, this includes the image base. See `Object::relative_address_base`.
     fn address(&self) -> u64;
 
+    /// Returns the virtual address of the segment, relative to `Object::relative_address_base`.
+    ///
+    /// This is always equal to `address() - relative_address_base()`. For PE, this is
+    /// the RVA of the segment. For other formats, this is the same as `address`.
+    #[inline]
+    fn relative_address(&self) -> u64 {
+        self.address()
+    }
+
     /// Returns the size of the segment in memory.
     fn size(&self) -> u64;
 
@@ -244,6 +884,72 @@ pub trait ObjectSegment<'data>: read::private::Sealed {
 
     /// Returns the name of the segment.
     fn name(&self) -> Result<Option<&str>>;
+
+    /// Return the flags of segment.
+    fn flags(&self) -> SegmentFlags;
+
+    /// Return true if the segment is readable when loaded.
+    fn is_readable(&self) -> bool {
+        segment_permission(self.flags(), Permission::Read)
+    }
+
+    /// Return true if the segment is writable when loaded.
+    fn is_writable(&self) -> bool {
+        segment_permission(self.flags(), Permission::Write)
+    }
+
+    /// Return true if the segment is executable when loaded.
+    fn is_executable(&self) -> bool {
+        segment_permission(self.flags(), Permission::Execute)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Permission {
+    Read,
+    Write,
+    Execute,
+}
+
+/// Test the segment flags for the given memory permission.
+///
+/// For Mach-O, this uses the initial protection of the segment.
+#[cfg_attr(
+    not(any(feature = "elf", feature = "macho", feature = "coff", feature = "pe")),
+    allow(unused_variables)
+)]
+fn segment_permission(flags: SegmentFlags, permission: Permission) -> bool {
+    match flags {
+        #[cfg(feature = "elf")]
+        SegmentFlags::Elf { p_flags } => {
+            let mask = match permission {
+                Permission::Read => crate::elf::PF_R,
+                Permission::Write => crate::elf::PF_W,
+                Permission::Execute => crate::elf::PF_X,
+            };
+            p_flags & mask != 0
+        }
+        #[cfg(feature = "macho")]
+        SegmentFlags::MachO { initprot, .. } => {
+            let mask = match permission {
+                Permission::Read => crate::macho::VM_PROT_READ,
+                Permission::Write => crate::macho::VM_PROT_WRITE,
+                Permission::Execute => crate::macho::VM_PROT_EXECUTE,
+            };
+            initprot & mask != 0
+        }
+        #[cfg(any(feature = "coff", feature = "pe"))]
+        SegmentFlags::Coff { characteristics } => {
+            let mask = match permission {
+                Permission::Read => crate::pe::IMAGE_SCN_MEM_READ,
+                Permission::Write => crate::pe::IMAGE_SCN_MEM_WRITE,
+                Permission::Execute => crate::pe::IMAGE_SCN_MEM_EXECUTE,
+            };
+            characteristics & mask != 0
+        }
+        #[allow(unreachable_patterns)]
+        _ => false,
+    }
 }
 
 /// A section defined in an object file.
@@ -258,8 +964,19 @@ pub trait ObjectSection<'data>: read::private::Sealed {
     fn index(&self) -> SectionIndex;
 
     /// Returns the address of the section.
+    ///
+    /// For PE, this includes the image base. See `Object::relative_address_base`.
     fn address(&self) -> u64;
 
+    /// Returns the address of the section, relative to `Object::relative_address_base`.
+    ///
+    /// This is always equal to `address() - relative_address_base()`. For PE, this is
+    /// the RVA of the section. For other formats, this is the same as `address`.
+    #[inline]
+    fn relative_address(&self) -> u64 {
+        self.address()
+    }
+
     /// Returns the size of the section in memory.
     fn size(&self) -> u64;
 
@@ -275,6 +992,10 @@ pub trait ObjectSection<'data>: read::private::Sealed {
     /// section in memory.
     ///
     /// This does not do any decompression.
+    ///
+    /// Returns an empty slice for sections that have no data in the file, such as
+    /// zero-fill sections. Returns `Err` if the file range of the section is invalid,
+    /// such as a range that extends past the end of the file data.
     fn data(&self) -> Result<&'data [u8]>;
 
     /// Return the raw contents of the section data in the given range.
@@ -303,6 +1024,52 @@ pub trait ObjectSection<'data>: read::private::Sealed {
         self.compressed_data()?.decompress()
     }
 
+    /// Return true if the contents of the section are entirely zero.
+    ///
+    /// This is true for sections that have no data in the file, such as `SHT_NOBITS`
+    /// sections. Otherwise the data is scanned until a nonzero byte is found.
+    ///
+    /// Uncompressed sections are scanned in place without allocating. Compressed
+    /// sections with a nonzero uncompressed size must be fully decompressed into a
+    /// temporary buffer first, so this is as expensive as `uncompressed_data`.
+    ///
+    /// Returns false if the data cannot be read or decompressed.
+    fn is_zero(&self) -> bool {
+        let range = match self.compressed_file_range() {
+            Ok(range) => range,
+            Err(_) => return false,
+        };
+        if range.format == CompressionFormat::None {
+            return match self.data() {
+                Ok(data) => data.iter().all(|&byte| byte == 0),
+                Err(_) => false,
+            };
+        }
+        if range.uncompressed_size == 0 {
+            return true;
+        }
+        match self.uncompressed_data() {
+            Ok(data) => data.iter().all(|&byte| byte == 0),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the uncompressed section data with its relocations applied.
+    ///
+    /// This is intended for sections in relocatable object files, which contain
+    /// placeholder values at the locations that will be filled in by relocations.
+    ///
+    /// Only `RelocationKind::Absolute`, `RelocationKind::Relative` and
+    /// `RelocationKind::PltRelative` relocations with a generic or x86 encoding are applied.
+    /// Other relocations are left unchanged.
+    /// Symbols are resolved to their address, and undefined symbols are resolved to 0.
+    ///
+    /// The sections of a relocatable file have not been assigned their final addresses,
+    /// so PC-relative relocations to a target in another section are left unchanged.
+    /// PC-relative relocations to undefined symbols are applied as if the symbol
+    /// is at address 0.
+    fn relocated_data(&self) -> Result<Cow<'data, [u8]>>;
+
     /// Returns the name of the section.
     fn name(&self) -> Result<&str>;
 
@@ -312,13 +1079,199 @@ pub trait ObjectSection<'data>: read::private::Sealed {
     /// Return the kind of this section.
     fn kind(&self) -> SectionKind;
 
+    /// Return true if this section contains linker generated stubs or trampolines.
+    ///
+    /// These sections have a kind of `SectionKind::Text`, but do not contain
+    /// functions from the source code.
+    ///
+    /// For ELF, these are executable sections with names starting with `.plt`,
+    /// such as `.plt`, `.plt.got` and `.plt.sec`.
+    /// For Mach-O, these are sections with the `S_SYMBOL_STUBS` type.
+    /// Other file formats always return false.
+    fn is_stub(&self) -> bool;
+
+    /// Returns the endianness of the file containing this section.
+    fn endianness(&self) -> Endianness;
+
+    /// Read a `u16` at the given offset within the section data.
+    ///
+    /// This uses the endianness of the file.
+    /// Returns `None` if the offset is out of bounds or the data cannot be read.
+    fn read_u16(&self, offset: u64) -> Option<u16> {
+        let bytes = section_bytes(self.data(), offset, 2)?;
+        Some(self.endianness().read_u16_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Read a `u32` at the given offset within the section data.
+    ///
+    /// This uses the endianness of the file.
+    /// Returns `None` if the offset is out of bounds or the data cannot be read.
+    fn read_u32(&self, offset: u64) -> Option<u32> {
+        let bytes = section_bytes(self.data(), offset, 4)?;
+        Some(self.endianness().read_u32_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Read a `u64` at the given offset within the section data.
+    ///
+    /// This uses the endianness of the file.
+    /// Returns `None` if the offset is out of bounds or the data cannot be read.
+    fn read_u64(&self, offset: u64) -> Option<u64> {
+        let bytes = section_bytes(self.data(), offset, 8)?;
+        Some(self.endianness().read_u64_bytes(bytes.try_into().ok()?))
+    }
+
     /// Get the relocations for this section.
     fn relocations(&self) -> Self::RelocationIterator;
 
+    /// Return the number of relocations for this section.
+    ///
+    /// Where possible, this is determined from the section headers instead of
+    /// iterating over the relocations.
+    fn relocation_count(&self) -> usize {
+        self.relocations().count()
+    }
+
     /// Section flags that are specific to each file format.
     fn flags(&self) -> SectionFlags;
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum InitSection {
+    #[cfg_attr(not(feature = "elf"), allow(dead_code))]
+    Preinit,
+    Ctors,
+    #[cfg_attr(
+        not(any(feature = "elf", feature = "macho", feature = "coff")),
+        allow(dead_code)
+    )]
+    Init,
+    Offsets,
+}
+
+/// Return the kind of static initialization list that is in the section, if any.
+fn init_section<'data, S: ObjectSection<'data>>(section: &S) -> Option<InitSection> {
+    match section.flags() {
+        #[cfg(feature = "elf")]
+        SectionFlags::Elf { .. } => match section.kind() {
+            SectionKind::Elf(crate::elf::SHT_PREINIT_ARRAY) => Some(InitSection::Preinit),
+            SectionKind::Elf(crate::elf::SHT_INIT_ARRAY) => Some(InitSection::Init),
+            _ if section.name() == Ok(".ctors") => Some(InitSection::Ctors),
+            _ => None,
+        },
+        #[cfg(feature = "macho")]
+        SectionFlags::MachO { flags } => match flags & crate::macho::SECTION_TYPE {
+            crate::macho::S_MOD_INIT_FUNC_POINTERS => Some(InitSection::Init),
+            crate::macho::S_INIT_FUNC_OFFSETS => Some(InitSection::Offsets),
+            _ => None,
+        },
+        #[cfg(feature = "coff")]
+        SectionFlags::Coff { .. } => match section.name() {
+            Ok(name) if name.starts_with(".CRT$XC") => Some(InitSection::Init),
+            _ => None,
+        },
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+/// Return the address and value of each entry in the static initialization lists.
+///
+/// See `Object::init_functions`.
+pub(crate) fn init_function_pointers<'data, 'file, O>(object: &'file O) -> Result<Vec<(u64, u64)>>
+where
+    'data: 'file,
+    O: Object<'data, 'file> + ?Sized,
+{
+    let endian = if object.is_little_endian() {
+        Endianness::Little
+    } else {
+        Endianness::Big
+    };
+    let is_relocatable = object.is_relocatable();
+    let base = object.preferred_load_address();
+    // COFF files are never 64-bit, so use the architecture instead.
+    let is_64 = match object.architecture().address_size() {
+        Some(address_size) => address_size == AddressSize::U64,
+        None => object.is_64(),
+    };
+
+    let mut lists = Vec::new();
+    for section in object.sections() {
+        let kind = match init_section(&section) {
+            Some(kind) => kind,
+            None => continue,
+        };
+        let data = if is_relocatable {
+            section.relocated_data()?
+        } else {
+            Cow::Borrowed(section.data()?)
+        };
+        let size = if kind == InitSection::Offsets || !is_64 {
+            4
+        } else {
+            8
+        };
+        let mut entries = Vec::new();
+        for (i, entry) in data.chunks_exact(size).enumerate() {
+            let address = section.address().wrapping_add((i * size) as u64);
+            let value = if size == 4 {
+                u64::from(endian.read_u32_bytes(entry.try_into().unwrap()))
+            } else {
+                endian.read_u64_bytes(entry.try_into().unwrap())
+            };
+            let value = if kind == InitSection::Offsets {
+                base.wrapping_add(value)
+            } else {
+                value
+            };
+            entries.push((address, value));
+        }
+        if kind == InitSection::Ctors {
+            // `.ctors` is called in reverse order, and may contain -1 and 0 as
+            // the start and end markers.
+            entries.reverse();
+        }
+        lists.push((kind, entries));
+    }
+    // The stable sort keeps the section order within each kind.
+    lists.sort_by_key(|&(kind, _)| kind);
+
+    let mut functions: Vec<(u64, u64)> = lists.into_iter().flat_map(|(_, list)| list).collect();
+    if !is_relocatable && !functions.is_empty() {
+        if let Some(relocations) = object.dynamic_relocations() {
+            for (address, relocation) in relocations {
+                if relocation.target() != RelocationTarget::Absolute
+                    || relocation.has_implicit_addend()
+                {
+                    continue;
+                }
+                for function in functions
+                    .iter_mut()
+                    .filter(|function| function.0 == address)
+                {
+                    function.1 = relocation.addend() as u64;
+                }
+            }
+        }
+    }
+    let all_ones = if is_64 {
+        u64::max_value()
+    } else {
+        u64::from(u32::max_value())
+    };
+    // Relocatable files may have functions at address 0.
+    functions.retain(|&(_, function)| (function != 0 || is_relocatable) && function != all_ones);
+    Ok(functions)
+}
+
+/// Return the bytes at the given offset within the section data.
+fn section_bytes(data: Result<&[u8]>, offset: u64, size: usize) -> Option<&[u8]> {
+    Bytes(data.ok()?)
+        .read_bytes_at(offset.try_into().ok()?, size)
+        .ok()
+        .map(|bytes| bytes.0)
+}
+
 /// A COMDAT section group defined in an object file.
 pub trait ObjectComdat<'data>: read::private::Sealed {
     /// An iterator over the sections in the object file.
@@ -348,6 +1301,9 @@ pub trait ObjectSymbolTable<'data>: read::private::Sealed {
     /// Get an iterator over the symbols in the table.
     ///
     /// This may skip over symbols that are malformed or unsupported.
+    ///
+    /// The symbols are returned in the order that they are stored in the table,
+    /// with increasing symbol indices.
     fn symbols(&self) -> Self::SymbolIterator;
 
     /// Get the symbol at the given index.
@@ -366,9 +1322,48 @@ pub trait ObjectSymbol<'data>: read::private::Sealed {
     /// The name of the symbol.
     fn name(&self) -> Result<&'data str>;
 
+    /// The raw name of the symbol, which may not be valid UTF-8.
+    fn name_bytes(&self) -> Result<&'data [u8]>;
+
     /// The address of the symbol. May be zero if the address is unknown.
+    ///
+    /// For some symbols, this is not a virtual address. For ELF, the value of
+    /// a TLS symbol is an offset into the TLS block, and the value of a common
+    /// symbol is its alignment. Use `tls_offset` and `common_alignment` to
+    /// access these values.
+    ///
+    /// For PE, this includes the image base, so that symbol addresses can be
+    /// compared with section and segment addresses.
     fn address(&self) -> u64;
 
+    /// The address of the symbol, relative to `Object::relative_address_base`.
+    ///
+    /// This is equal to `address() - relative_address_base()`, except that it is
+    /// 0 if `address` is 0 because the symbol has no address. For PE, this is the
+    /// RVA of the symbol. For other formats, this is the same as `address`.
+    #[inline]
+    fn relative_address(&self) -> u64 {
+        self.address()
+    }
+
+    /// The offset of a TLS symbol within the TLS block.
+    ///
+    /// Returns `None` if the symbol is not a TLS symbol, or the offset is not known.
+    /// This is currently only supported for ELF.
+    #[inline]
+    fn tls_offset(&self) -> Option<u64> {
+        None
+    }
+
+    /// The required alignment of a common symbol.
+    ///
+    /// Returns `None` if the symbol is not a common symbol, or the alignment is not known.
+    /// This is currently only supported for ELF.
+    #[inline]
+    fn common_alignment(&self) -> Option<u64> {
+        None
+    }
+
     /// The size of the symbol. May be zero if the size is unknown.
     fn size(&self) -> u64;
 
@@ -400,6 +1395,21 @@ pub trait ObjectSymbol<'data>: read::private::Sealed {
     /// Return true if the symbol is weak.
     fn is_weak(&self) -> bool;
 
+    /// Return true if the symbol is an indirect function.
+    ///
+    /// The address of an indirect function is the address of a resolver function,
+    /// which returns the address of the implementation when called.
+    ///
+    /// This is only supported for ELF `STT_GNU_IFUNC` symbols.
+    fn is_ifunc(&self) -> bool;
+
+    /// Return true if the symbol is an ARM function that uses the Thumb instruction set.
+    ///
+    /// For ELF, this is indicated by setting the low bit of the symbol value,
+    /// and `address` returns the value with this bit cleared.
+    /// For Mach-O, this is indicated by the `N_ARM_THUMB_DEF` flag.
+    fn is_thumb(&self) -> bool;
+
     /// Returns the symbol scope.
     fn scope(&self) -> SymbolScope;
 
@@ -413,6 +1423,21 @@ pub trait ObjectSymbol<'data>: read::private::Sealed {
 
     /// Symbol flags that are specific to each file format.
     fn flags(&self) -> SymbolFlags<SectionIndex>;
+
+    /// Compare two symbols by address.
+    ///
+    /// Symbols with the same address are compared by name, and then by index,
+    /// so that the order is deterministic. Names that are invalid are treated
+    /// as empty.
+    fn cmp_by_address(&self, other: &Self) -> Ordering
+    where
+        Self: Sized,
+    {
+        self.address()
+            .cmp(&other.address())
+            .then_with(|| self.name().unwrap_or("").cmp(other.name().unwrap_or("")))
+            .then_with(|| self.index().0.cmp(&other.index().0))
+    }
 }
 
 /// An iterator for files that don't have dynamic relocations.
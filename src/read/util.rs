@@ -1,6 +1,16 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::convert::TryInto;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use core::{cmp, fmt, ptr};
 
 use crate::pod::Bytes;
+use crate::read::{
+    self, Error, Object, ObjectSection, ObjectSegment, ObjectSymbol, ReadError, RelocationEncoding,
+    RelocationKind, RelocationTarget, SectionIndex,
+};
 
 #[allow(dead_code)]
 #[inline]
@@ -20,6 +30,323 @@ pub(crate) fn data_range(
         .get(..size.try_into().ok()?)
 }
 
+/// A value that is computed on first use, and then cached.
+///
+/// This is used for lookup tables that are expensive to build and not always needed.
+/// Unlike `core::cell::RefCell`, this can be shared between threads.
+/// If multiple threads initialize the value at the same time, then only one of the
+/// values is kept.
+#[cfg_attr(
+    not(any(feature = "coff", feature = "elf", feature = "macho")),
+    allow(dead_code)
+)]
+pub(crate) struct LazyBox<T> {
+    ptr: AtomicPtr<T>,
+    marker: PhantomData<Box<T>>,
+}
+
+#[cfg_attr(
+    not(any(feature = "coff", feature = "elf", feature = "macho")),
+    allow(dead_code)
+)]
+impl<T> LazyBox<T> {
+    pub(crate) fn new() -> Self {
+        LazyBox {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<&T> {
+        let ptr = self.ptr.load(Ordering::Acquire);
+        // Safety: a non-null pointer was created by `Box::into_raw` in `get_or_init`,
+        // and is not freed until `self` is dropped.
+        unsafe { ptr.as_ref() }
+    }
+
+    pub(crate) fn get_or_init<F: FnOnce() -> T>(&self, f: F) -> &T {
+        if let Some(value) = self.get() {
+            return value;
+        }
+        let new = Box::into_raw(Box::new(f()));
+        let ptr = match self.ptr.compare_exchange(
+            ptr::null_mut(),
+            new,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => new,
+            Err(old) => {
+                // Another thread initialized the value first.
+                // Safety: `new` was created by `Box::into_raw` above and was not shared.
+                drop(unsafe { Box::from_raw(new) });
+                old
+            }
+        };
+        // Safety: see `get`.
+        unsafe { &*ptr }
+    }
+}
+
+impl<T> Default for LazyBox<T> {
+    fn default() -> Self {
+        LazyBox::new()
+    }
+}
+
+impl<T> Drop for LazyBox<T> {
+    fn drop(&mut self) {
+        let ptr = *self.ptr.get_mut();
+        if !ptr.is_null() {
+            // Safety: see `get`.
+            drop(unsafe { Box::from_raw(ptr) });
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LazyBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LazyBox").field(&self.get()).finish()
+    }
+}
+
+// Safety: the value may be created on one thread and then accessed or dropped
+// on another thread, so `T` must be both `Send` and `Sync`.
+unsafe impl<T: Send + Sync> Sync for LazyBox<T> {}
+
+/// A sorted index of the address ranges of the sections or segments in a file.
+///
+/// This stores the index of each section or segment instead of its data,
+/// so that it can be cached in a file without borrowing from the file.
+#[derive(Debug, Default)]
+pub(crate) struct AddressIndex {
+    // The start address, end address, and section or segment index of each range.
+    ranges: Vec<(u64, u64, usize)>,
+}
+
+impl AddressIndex {
+    /// Construct an index from the start address, size, and section or segment
+    /// index of each range.
+    ///
+    /// Empty ranges are ignored. If ranges overlap, then the range with the lowest
+    /// address is used, and for ranges with the same address, the first one is used.
+    pub(crate) fn new<I: Iterator<Item = (u64, u64, usize)>>(ranges: I) -> Self {
+        let mut ranges = ranges
+            .filter(|&(_, size, _)| size != 0)
+            .map(|(address, size, index)| (address, address.saturating_add(size), index))
+            .collect::<Vec<_>>();
+        // This sort is stable, so the first range is kept for equal addresses.
+        ranges.sort_by_key(|&(address, _, _)| address);
+        let mut end = 0;
+        let mut first = true;
+        ranges.retain(|&(address, range_end, _)| {
+            if !first && address < end {
+                return false;
+            }
+            first = false;
+            end = range_end;
+            true
+        });
+        AddressIndex { ranges }
+    }
+
+    /// Find the range containing the given address.
+    ///
+    /// Returns the section or segment index of the range, and the offset of
+    /// the address within the range.
+    pub(crate) fn find(&self, address: u64) -> Option<(usize, u64)> {
+        let i = match self
+            .ranges
+            .binary_search_by_key(&address, |&(start, _, _)| start)
+        {
+            Ok(i) => i,
+            Err(i) => i.checked_sub(1)?,
+        };
+        let (start, end, index) = self.ranges[i];
+        if address >= end {
+            return None;
+        }
+        Some((index, address - start))
+    }
+
+    /// Return the start address and section or segment index of each range.
+    pub(crate) fn iter<'a>(&'a self) -> impl Iterator<Item = (u64, usize)> + 'a {
+        self.ranges
+            .iter()
+            .map(|&(address, _, index)| (address, index))
+    }
+}
+
+/// Read a null terminated string at the given address by searching the sections in order.
+///
+/// This is the implementation of `Object::cstr_at` for relocatable files, in which
+/// section addresses usually overlap.
+pub(crate) fn sections_cstr_at<'data: 'file, 'file, O: Object<'data, 'file> + ?Sized>(
+    file: &'file O,
+    address: u64,
+) -> Option<&'data [u8]> {
+    for section in file.sections() {
+        let offset = match address.checked_sub(section.address()) {
+            Some(offset) if offset < section.size() => offset,
+            _ => continue,
+        };
+        let data = section.data().ok()?;
+        return Bytes(data).read_string_at(offset.try_into().ok()?).ok();
+    }
+    None
+}
+
+/// Read a null terminated string at the given address using a cached index of the sections.
+///
+/// This is the implementation of `Object::cstr_at` for Mach-O and PE files.
+#[cfg_attr(not(any(feature = "macho", feature = "pe")), allow(dead_code))]
+pub(crate) fn indexed_cstr_at<'data: 'file, 'file, O: Object<'data, 'file>>(
+    file: &'file O,
+    index: &'file LazyBox<AddressIndex>,
+    address: u64,
+) -> Option<&'data [u8]> {
+    if file.is_relocatable() {
+        return sections_cstr_at(file, address);
+    }
+    let index = index.get_or_init(|| {
+        AddressIndex::new(file.sections().filter_map(|section| {
+            let size = section.data().ok()?.len() as u64;
+            Some((section.address(), size, section.index().0))
+        }))
+    });
+    let (section_index, offset) = index.find(address)?;
+    let data = file
+        .section_by_index(SectionIndex(section_index))
+        .ok()?
+        .data()
+        .ok()?;
+    Bytes(data).read_string_at(offset.try_into().ok()?).ok()
+}
+
+/// Return the range of addresses that are mapped by the given segments.
+///
+/// This is the implementation of `Object::address_range`.
+pub(crate) fn segments_address_range<'data, S: ObjectSegment<'data>>(
+    segments: impl Iterator<Item = S>,
+) -> (u64, u64) {
+    segments
+        .filter(|segment| segment.size() != 0)
+        .map(|segment| {
+            let address = segment.address();
+            (address, address.saturating_add(segment.size()))
+        })
+        .fold(None, |range, (start, end)| match range {
+            Some((min, max)) => Some((cmp::min(min, start), cmp::max(max, end))),
+            None => Some((start, end)),
+        })
+        .unwrap_or((0, 0))
+}
+
+/// Return the data of a section with its relocations applied.
+///
+/// This is the implementation of `ObjectSection::relocated_data`.
+///
+/// If `implicit_section_targets` is true, then relocations targeting a section are
+/// assumed to already contain the final value, and are not applied. This is the case
+/// for Mach-O, where the implicit addend includes the address of the target section.
+#[cfg_attr(
+    not(any(feature = "coff", feature = "elf", feature = "macho")),
+    allow(dead_code)
+)]
+pub(crate) fn relocated_data<'data, 'file, O: Object<'data, 'file>>(
+    file: &'file O,
+    section: &O::Section,
+    implicit_section_targets: bool,
+) -> read::Result<Cow<'data, [u8]>> {
+    let mut data = section.uncompressed_data()?;
+    let big_endian = !file.is_little_endian();
+    let relocatable = file.is_relocatable();
+    for (offset, relocation) in section.relocations() {
+        match relocation.encoding() {
+            RelocationEncoding::Generic
+            | RelocationEncoding::X86Signed
+            | RelocationEncoding::X86RipRelative
+            | RelocationEncoding::X86Branch => {}
+            _ => continue,
+        }
+        let relative = match relocation.kind() {
+            RelocationKind::Absolute => false,
+            RelocationKind::Relative | RelocationKind::PltRelative => true,
+            _ => continue,
+        };
+        let size = match relocation.size() {
+            8 => 1,
+            16 => 2,
+            32 => 4,
+            64 => 8,
+            _ => continue,
+        };
+        let (target, target_section) = match relocation.target() {
+            RelocationTarget::Symbol(index) => {
+                let symbol = file.symbol_by_index(index)?;
+                if symbol.is_undefined() {
+                    (0, None)
+                } else {
+                    (symbol.address(), symbol.section_index())
+                }
+            }
+            RelocationTarget::Section(_) if implicit_section_targets => continue,
+            RelocationTarget::Section(index) => {
+                (file.section_by_index(index)?.address(), Some(index))
+            }
+            RelocationTarget::Absolute => (0, None),
+        };
+        // The sections of a relocatable file have not been assigned their final
+        // addresses, so the distance to a target in another section is unknown.
+        if relative
+            && relocatable
+            && target_section.is_some()
+            && target_section != Some(section.index())
+        {
+            continue;
+        }
+
+        let start: usize = offset
+            .try_into()
+            .map_err(|_| Error("Invalid relocation offset"))?;
+        let bytes = data
+            .get(start..)
+            .and_then(|bytes| bytes.get(..size))
+            .read_error("Invalid relocation offset")?;
+        let mut addend = relocation.addend() as u64;
+        if relocation.has_implicit_addend() {
+            addend = addend.wrapping_add(read_sint(bytes, big_endian));
+        }
+        let mut value = target.wrapping_add(addend);
+        if relative {
+            value = value.wrapping_sub(section.address().wrapping_add(offset));
+        }
+        write_uint(&mut data.to_mut()[start..][..size], value, big_endian);
+    }
+    Ok(data)
+}
+
+/// Read a sign extended integer of the size of the given bytes.
+pub(crate) fn read_sint(bytes: &[u8], big_endian: bool) -> u64 {
+    let fold = |value: u64, byte: &u8| (value << 8) | u64::from(*byte);
+    let value = if big_endian {
+        bytes.iter().fold(0, fold)
+    } else {
+        bytes.iter().rev().fold(0, fold)
+    };
+    let shift = 64 - 8 * bytes.len() as u32;
+    (((value << shift) as i64) >> shift) as u64
+}
+
+/// Write the low bytes of an integer to the given bytes.
+fn write_uint(bytes: &mut [u8], value: u64, big_endian: bool) {
+    let len = bytes.len();
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let shift = if big_endian { 8 * (len - 1 - i) } else { 8 * i };
+        *byte = (value >> shift) as u8;
+    }
+}
+
 /// A table of zero-terminated strings.
 ///
 /// This is used for most file formats.
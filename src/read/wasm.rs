@@ -3,6 +3,12 @@
 //! Provides `WasmFile` and related types which implement the `Object` trait.
 //!
 //! Currently implements the minimum required to access DWARF debugging information.
+//!
+//! Symbols are created for imports, exports and local functions. Local functions and
+//! the module itself are named using the `name` custom section, if present. Local
+//! variable names are not exposed as symbols. Custom sections, including `name` and
+//! `producers`, can be accessed by name using `Object::section_data_by_name`.
+use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::marker::PhantomData;
@@ -13,8 +19,10 @@ use crate::read::{
     self, Architecture, ComdatKind, CompressedData, CompressedFileRange, Error, Export, FileFlags,
     Import, NoDynamicRelocationIterator, Object, ObjectComdat, ObjectSection, ObjectSegment,
     ObjectSymbol, ObjectSymbolTable, ReadError, ReadRef, Relocation, Result, SectionFlags,
-    SectionIndex, SectionKind, SymbolFlags, SymbolIndex, SymbolKind, SymbolScope, SymbolSection,
+    SectionIndex, SectionKind, SegmentFlags, SymbolFlags, SymbolIndex, SymbolKind, SymbolScope,
+    SymbolSection,
 };
+use crate::Endianness;
 
 const SECTION_CUSTOM: usize = 0;
 const SECTION_TYPE: usize = 1;
@@ -86,6 +94,7 @@ impl<'data, R: ReadRef<'data>> WasmFile<'data, R> {
             scope: SymbolScope::Compilation,
         });
 
+        let mut main_file_symbol_id = None;
         let mut imported_funcs_count = 0;
         let mut local_func_kinds = Vec::new();
         let mut entry_func_id = None;
@@ -147,6 +156,7 @@ impl<'data, R: ReadRef<'data>> WasmFile<'data, R> {
                 }
                 wp::SectionCode::Export => {
                     if let Some(main_file_symbol) = main_file_symbol.take() {
+                        main_file_symbol_id = Some(file.symbols.len());
                         file.symbols.push(main_file_symbol);
                     }
 
@@ -200,6 +210,7 @@ impl<'data, R: ReadRef<'data>> WasmFile<'data, R> {
                 }
                 wp::SectionCode::Code => {
                     if let Some(main_file_symbol) = main_file_symbol.take() {
+                        main_file_symbol_id = Some(file.symbols.len());
                         file.symbols.push(main_file_symbol);
                     }
 
@@ -253,11 +264,23 @@ impl<'data, R: ReadRef<'data>> WasmFile<'data, R> {
                         .get_name_section_reader()
                         .read_error("Couldn't read header of the name section")?
                     {
-                        let name =
-                            match name.read_error("Couldn't read header of a name subsection")? {
-                                wp::Name::Function(name) => name,
-                                _ => continue,
-                            };
+                        let name = match name
+                            .read_error("Couldn't read header of a name subsection")?
+                        {
+                            wp::Name::Module(name) => {
+                                let name = name
+                                    .get_name()
+                                    .read_error("Couldn't read the module name")?;
+                                match (&mut main_file_symbol, main_file_symbol_id) {
+                                    (Some(main_file_symbol), _) => main_file_symbol.name = name,
+                                    (None, Some(symbol_id)) => file.symbols[symbol_id].name = name,
+                                    (None, None) => {}
+                                }
+                                continue;
+                            }
+                            wp::Name::Function(name) => name,
+                            _ => continue,
+                        };
                         let mut name_map = name
                             .get_map()
                             .read_error("Couldn't read header of the function name subsection")?;
@@ -268,10 +291,10 @@ impl<'data, R: ReadRef<'data>> WasmFile<'data, R> {
                             if let Some(local_index) =
                                 naming.index.checked_sub(imported_funcs_count)
                             {
-                                if let LocalFunctionKind::Local { symbol_id } =
-                                    local_func_kinds[local_index as usize]
+                                if let Some(LocalFunctionKind::Local { symbol_id }) =
+                                    local_func_kinds.get(local_index as usize)
                                 {
-                                    file.symbols[symbol_id as usize].name = naming.name;
+                                    file.symbols[*symbol_id as usize].name = naming.name;
                                 }
                             }
                         }
@@ -326,6 +349,18 @@ where
         false
     }
 
+    fn is_relocatable(&self) -> bool {
+        self.sections.iter().any(|section| {
+            matches!(
+                section.code,
+                wp::SectionCode::Custom {
+                    kind: wp::CustomSectionKind::Linking,
+                    ..
+                }
+            )
+        })
+    }
+
     fn segments(&'file self) -> Self::SegmentIterator {
         WasmSegmentIterator { file: self }
     }
@@ -415,6 +450,10 @@ where
         0
     }
 
+    fn preferred_load_address(&self) -> u64 {
+        0
+    }
+
     #[inline]
     fn entry(&'file self) -> u64 {
         self.entry
@@ -447,6 +486,18 @@ pub struct WasmSegment<'data, 'file, R = &'data [u8]> {
     file: &'file WasmFile<'data, R>,
 }
 
+impl<'data, 'file, R> WasmSegment<'data, 'file, R> {
+    /// Return an iterator over the sections in this segment.
+    ///
+    /// Wasm files do not have segments, so this iterator is always empty.
+    pub fn sections(&self) -> WasmSectionIterator<'data, 'file, R> {
+        WasmSectionIterator {
+            sections: [].iter(),
+            marker: PhantomData,
+        }
+    }
+}
+
 impl<'data, 'file, R> read::private::Sealed for WasmSegment<'data, 'file, R> {}
 
 impl<'data, 'file, R> ObjectSegment<'data> for WasmSegment<'data, 'file, R> {
@@ -482,6 +533,11 @@ impl<'data, 'file, R> ObjectSegment<'data> for WasmSegment<'data, 'file, R> {
     fn name(&self) -> Result<Option<&str>> {
         unreachable!()
     }
+
+    #[inline]
+    fn flags(&self) -> SegmentFlags {
+        unreachable!()
+    }
 }
 
 /// An iterator over the sections of a `WasmFile`.
@@ -566,6 +622,11 @@ impl<'data, 'file, R> ObjectSection<'data> for WasmSection<'data, 'file, R> {
         self.data().map(CompressedData::none)
     }
 
+    /// Relocations are not supported for Wasm, so this returns the section data unchanged.
+    fn relocated_data(&self) -> Result<Cow<'data, [u8]>> {
+        self.data().map(Cow::Borrowed)
+    }
+
     #[inline]
     fn name(&self) -> Result<&str> {
         Ok(match self.section.code {
@@ -614,6 +675,15 @@ impl<'data, 'file, R> ObjectSection<'data> for WasmSection<'data, 'file, R> {
         }
     }
 
+    #[inline]
+    fn is_stub(&self) -> bool {
+        false
+    }
+
+    fn endianness(&self) -> Endianness {
+        Endianness::Little
+    }
+
     #[inline]
     fn relocations(&self) -> WasmRelocationIterator<'data, 'file, R> {
         WasmRelocationIterator(PhantomData)
@@ -764,6 +834,11 @@ impl<'data, 'file> ObjectSymbol<'data> for WasmSymbol<'data, 'file> {
         Ok(self.symbol.name)
     }
 
+    #[inline]
+    fn name_bytes(&self) -> read::Result<&'data [u8]> {
+        Ok(self.symbol.name.as_bytes())
+    }
+
     #[inline]
     fn address(&self) -> u64 {
         self.symbol.address
@@ -804,6 +879,16 @@ impl<'data, 'file> ObjectSymbol<'data> for WasmSymbol<'data, 'file> {
         false
     }
 
+    #[inline]
+    fn is_ifunc(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn is_thumb(&self) -> bool {
+        false
+    }
+
     #[inline]
     fn scope(&self) -> SymbolScope {
         self.symbol.scope
@@ -857,3 +942,64 @@ fn section_code_to_id(code: wp::SectionCode) -> usize {
         wp::SectionCode::DataCount => SECTION_DATA_COUNT,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Append a section with the given id and contents.
+    fn section(module: &mut Vec<u8>, id: u8, contents: &[u8]) {
+        module.push(id);
+        module.push(contents.len() as u8);
+        module.extend_from_slice(contents);
+    }
+
+    /// Append a custom section with the given name and contents.
+    fn custom_section(module: &mut Vec<u8>, name: &[u8], contents: &[u8]) {
+        let mut data = vec![name.len() as u8];
+        data.extend_from_slice(name);
+        data.extend_from_slice(contents);
+        section(module, 0, &data);
+    }
+
+    #[test]
+    fn name_section() {
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        // One function type with no parameters or results.
+        section(&mut module, 1, &[0x01, 0x60, 0x00, 0x00]);
+        // Two functions.
+        section(&mut module, 3, &[0x02, 0x00, 0x00]);
+        // Export the second function as "exp".
+        section(&mut module, 7, &[0x01, 0x03, b'e', b'x', b'p', 0x00, 0x01]);
+        // Two empty function bodies.
+        section(&mut module, 10, &[0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b]);
+        // Module name "mod" and function name "f0".
+        custom_section(
+            &mut module,
+            b"name",
+            &[
+                0x00, 0x04, 0x03, b'm', b'o', b'd', //
+                0x01, 0x05, 0x01, 0x00, 0x02, b'f', b'0',
+            ],
+        );
+        custom_section(&mut module, b"producers", &[0x00]);
+
+        let file = WasmFile::parse(&*module).unwrap();
+        let symbols = file.symbols().collect::<Vec<_>>();
+        assert_eq!(symbols.len(), 3);
+        assert_eq!(symbols[0].kind(), SymbolKind::File);
+        assert_eq!(symbols[0].name(), Ok("mod"));
+        assert_eq!(symbols[1].name(), Ok("exp"));
+        assert_eq!(symbols[1].scope(), SymbolScope::Dynamic);
+        assert_eq!(symbols[2].name(), Ok("f0"));
+        assert_eq!(symbols[2].kind(), SymbolKind::Text);
+        assert_eq!(symbols[2].scope(), SymbolScope::Compilation);
+        assert_ne!(symbols[1].address(), symbols[2].address());
+
+        let name = file.section_data_by_name("name").unwrap().unwrap();
+        assert_eq!(name.len(), 13);
+        let producers = file.section_data_by_name("producers").unwrap();
+        assert_eq!(producers, Some(&[0x00][..]));
+        assert!(file.section_by_name("producers").is_some());
+    }
+}
@@ -88,6 +88,7 @@ impl Object {
             Architecture::Riscv64 => true,
             Architecture::Riscv32 => true,
             Architecture::S390x => true,
+            Architecture::Sparc => true,
             Architecture::Sparc64 => true,
             _ => {
                 return Err(Error(format!(
@@ -116,6 +117,10 @@ impl Object {
                 | RelocationKind::GotRelative
                 | RelocationKind::GotBaseRelative
                 | RelocationKind::PltRelative
+                | RelocationKind::TlsGd
+                | RelocationKind::TlsLd
+                | RelocationKind::TlsIe
+                | RelocationKind::TlsLe
                 | RelocationKind::Elf(_) => return false,
                 // Absolute relocations are preemptible for non-local data.
                 // TODO: not sure if this rule is exactly correct
@@ -371,6 +376,7 @@ impl Object {
             Architecture::Riscv32 => elf::EM_RISCV,
             Architecture::Riscv64 => elf::EM_RISCV,
             Architecture::S390x => elf::EM_S390,
+            Architecture::Sparc => elf::EM_SPARC,
             Architecture::Sparc64 => elf::EM_SPARCV9,
             _ => {
                 return Err(Error(format!(
@@ -640,6 +646,10 @@ impl Object {
                             (RelocationKind::Relative, 16) => elf::R_386_PC16,
                             (RelocationKind::Absolute, 8) => elf::R_386_8,
                             (RelocationKind::Relative, 8) => elf::R_386_PC8,
+                            (RelocationKind::TlsGd, 32) => elf::R_386_TLS_GD,
+                            (RelocationKind::TlsLd, 32) => elf::R_386_TLS_LDM,
+                            (RelocationKind::TlsIe, 32) => elf::R_386_TLS_IE,
+                            (RelocationKind::TlsLe, 32) => elf::R_386_TLS_LE,
                             (RelocationKind::Elf(x), _) => x,
                             _ => {
                                 return Err(Error(format!("unimplemented relocation {:?}", reloc)));
@@ -664,6 +674,11 @@ impl Object {
                                 (RelocationKind::Relative, _, 16) => elf::R_X86_64_PC16,
                                 (RelocationKind::Absolute, _, 8) => elf::R_X86_64_8,
                                 (RelocationKind::Relative, _, 8) => elf::R_X86_64_PC8,
+                                (RelocationKind::TlsGd, _, 32) => elf::R_X86_64_TLSGD,
+                                (RelocationKind::TlsLd, _, 32) => elf::R_X86_64_TLSLD,
+                                (RelocationKind::TlsIe, _, 32) => elf::R_X86_64_GOTTPOFF,
+                                (RelocationKind::TlsLe, _, 32) => elf::R_X86_64_TPOFF32,
+                                (RelocationKind::TlsLe, _, 64) => elf::R_X86_64_TPOFF64,
                                 (RelocationKind::Elf(x), _, _) => x,
                                 _ => {
                                     return Err(Error(format!(
@@ -796,15 +811,20 @@ impl Object {
                                 return Err(Error(format!("unimplemented relocation {:?}", reloc)));
                             }
                         },
-                        Architecture::Sparc64 => match (reloc.kind, reloc.encoding, reloc.size) {
-                            // TODO: use R_SPARC_32/R_SPARC_64 if aligned.
-                            (RelocationKind::Absolute, _, 32) => elf::R_SPARC_UA32,
-                            (RelocationKind::Absolute, _, 64) => elf::R_SPARC_UA64,
-                            (RelocationKind::Elf(x), _, _) => x,
-                            _ => {
-                                return Err(Error(format!("unimplemented relocation {:?}", reloc)));
+                        Architecture::Sparc | Architecture::Sparc64 => {
+                            match (reloc.kind, reloc.encoding, reloc.size) {
+                                // TODO: use R_SPARC_32/R_SPARC_64 if aligned.
+                                (RelocationKind::Absolute, _, 32) => elf::R_SPARC_UA32,
+                                (RelocationKind::Absolute, _, 64) => elf::R_SPARC_UA64,
+                                (RelocationKind::Elf(x), _, _) => x,
+                                _ => {
+                                    return Err(Error(format!(
+                                        "unimplemented relocation {:?}",
+                                        reloc
+                                    )));
+                                }
                             }
-                        },
+                        }
                         _ => {
                             if let RelocationKind::Elf(x) = reloc.kind {
                                 x
@@ -23,7 +23,7 @@ mod util;
 
 /// The error type used within the write module.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Error(String);
+pub struct Error(pub(crate) String);
 
 impl fmt::Display for Error {
     #[inline]
@@ -8,7 +8,75 @@ fn parse_self() {
     let data = fs::read(exe).unwrap();
     let object = File::parse(&*data).unwrap();
     assert!(object.entry() != 0);
+    assert!(!object.is_relocatable());
     assert!(object.sections().count() != 0);
+
+    let mut count = 0;
+    for _section in &object {
+        count += 1;
+    }
+    assert_eq!(count, object.sections().count());
+}
+
+#[test]
+fn parse_self_try_from() {
+    use std::convert::TryFrom;
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(exe).unwrap();
+    let object = File::try_from(&*data).unwrap();
+    assert!(object.entry() != 0);
+    assert!(File::try_from(&[0u8; 4][..]).is_err());
+}
+
+#[test]
+fn parse_self_owned() {
+    use object::{ObjectSection, OwnedFile};
+    struct Holder {
+        file: OwnedFile,
+    }
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(exe).unwrap();
+    let len = data.len();
+    let holder = Holder {
+        file: OwnedFile::parse(data).unwrap(),
+    };
+    let object = holder.file.file();
+    assert!(object.entry() != 0);
+    let section = object.sections().find(|s| s.size() != 0).unwrap();
+    let data = section.data().unwrap();
+    let start = holder.file.data().as_ptr() as usize;
+    let offset = (data.as_ptr() as usize).wrapping_sub(start);
+    assert!(offset < len);
+    assert_eq!(holder.file.into_data().len(), len);
+
+    assert!(OwnedFile::parse(vec![0; 4]).is_err());
+}
+
+#[test]
+fn parse_self_address_map() {
+    use object::ObjectSection;
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(exe).unwrap();
+    let object = File::parse(&*data).unwrap();
+    let map = object.address_map();
+    let mut count = 0;
+    for section in object.sections() {
+        let data = section.data().unwrap();
+        if section.address() == 0 || data.is_empty() {
+            continue;
+        }
+        let address = section.address();
+        assert_eq!(map.data_at(address), Some(data));
+        assert_eq!(
+            map.data_at(address + data.len() as u64 - 1),
+            Some(&data[data.len() - 1..])
+        );
+        let cstr = data.iter().position(|&b| b == 0).map(|end| &data[..end]);
+        assert_eq!(map.cstr_at(address), cstr);
+        assert_eq!(object.cstr_at(address), cstr);
+        count += 1;
+    }
+    assert!(count != 0);
 }
 
 #[cfg(feature = "std")]
@@ -23,3 +91,159 @@ fn parse_self_cache() {
     assert!(object.entry() != 0);
     assert!(object.sections().count() != 0);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn parse_self_cache_matches_slice() {
+    use object::read::ReadCache;
+    use object::{ObjectSection, ObjectSymbol};
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(&exe).unwrap();
+    let cache = ReadCache::new(fs::File::open(&exe).unwrap());
+    let slice_object = File::parse(&*data).unwrap();
+    let cache_object = File::parse(&cache).unwrap();
+    assert_eq!(slice_object.entry(), cache_object.entry());
+    assert_eq!(
+        slice_object.sections().count(),
+        cache_object.sections().count()
+    );
+    for (slice_section, cache_section) in slice_object.sections().zip(cache_object.sections()) {
+        assert_eq!(slice_section.name(), cache_section.name());
+        assert_eq!(slice_section.data(), cache_section.data());
+    }
+    assert!(slice_object
+        .symbols()
+        .map(|symbol| symbol.name())
+        .eq(cache_object.symbols().map(|symbol| symbol.name())));
+}
+
+#[cfg(all(target_os = "linux", target_pointer_width = "64"))]
+#[test]
+fn parse_self_runtime_flags() {
+    use object::read::elf::ElfFile64;
+    use object::{elf, SegmentFlags};
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(exe).unwrap();
+    let object = ElfFile64::<object::Endianness>::parse(&*data).unwrap();
+    let text = object.section_by_name(".text").unwrap();
+    match text.runtime_flags() {
+        Some(SegmentFlags::Elf { p_flags }) => {
+            assert_ne!(p_flags & elf::PF_X, 0);
+            assert_eq!(p_flags & elf::PF_W, 0);
+        }
+        flags => panic!("unexpected flags {:?}", flags),
+    }
+    let comment = object.section_by_name(".comment");
+    assert!(comment.map_or(true, |section| section.runtime_flags().is_none()));
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu", target_pointer_width = "64"))]
+#[test]
+fn parse_self_dynamic_entries() {
+    use object::read::elf::{DynamicTag, ElfFile64};
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(exe).unwrap();
+    let object = ElfFile64::<object::Endianness>::parse(&*data).unwrap();
+    let entries = object.dynamic_entries().unwrap();
+    assert!(entries.iter().any(|entry| entry.tag == DynamicTag::Needed));
+    assert!(entries.iter().any(|entry| entry.tag == DynamicTag::StrTab));
+    assert!(entries.iter().all(|entry| entry.tag != DynamicTag::Null));
+    for entry in &entries {
+        assert_eq!(DynamicTag::from_raw(entry.tag.raw()), entry.tag);
+    }
+}
+
+#[test]
+fn parse_self_exported_symbols() {
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(exe).unwrap();
+    let object = File::parse(&*data).unwrap();
+    let symbols = check_exported_symbols(&object);
+
+    // Mach-O executables always export the header symbol.
+    if object.format() == object::BinaryFormat::MachO {
+        assert!(symbols
+            .iter()
+            .any(|symbol| symbol.name() == b"__mh_execute_header"));
+    }
+
+    // ELF executables usually export nothing, so check the dynamic linker that
+    // loaded this executable, which is a linked shared object.
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    {
+        use object::ObjectSection;
+        let interp = object.section_by_name(".interp").unwrap().data().unwrap();
+        let path = interp.split(|&byte| byte == 0).next().unwrap();
+        let data = fs::read(std::str::from_utf8(path).unwrap()).unwrap();
+        let object = File::parse(&*data).unwrap();
+        let symbols = check_exported_symbols(&object);
+        assert!(!symbols.is_empty());
+        assert!(symbols
+            .iter()
+            .any(|symbol| symbol.name() == b"__tls_get_addr" && symbol.address() != 0));
+    }
+}
+
+/// Check that the exported symbols match the exports, and return them.
+fn check_exported_symbols<'data>(object: &File<'data>) -> Vec<object::ExportedSymbol<'data>> {
+    let exports = object.exports().unwrap();
+    let symbols = object.exported_symbols().unwrap();
+    for symbol in &symbols {
+        assert!(exports
+            .iter()
+            .any(|export| export.name() == symbol.name() && export.address() == symbol.address()));
+    }
+    symbols
+}
+
+#[test]
+fn parse_self_segment_permissions() {
+    use object::ObjectSegment;
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(exe).unwrap();
+    let object = File::parse(&*data).unwrap();
+    let entry = object.entry();
+    let text = object
+        .segments()
+        .find(|segment| entry >= segment.address() && entry - segment.address() < segment.size())
+        .unwrap();
+    assert!(text.is_readable());
+    assert!(text.is_executable());
+    assert!(!text.is_writable());
+    assert!(object.segments().any(|segment| segment.is_writable()));
+}
+
+#[test]
+fn parse_self_init_functions() {
+    use object::{ObjectSection, SectionKind};
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(exe).unwrap();
+    let object = File::parse(&*data).unwrap();
+    for function in object.init_functions().unwrap() {
+        assert!(object.sections().any(|section| {
+            section.kind() == SectionKind::Text
+                && function >= section.address()
+                && function - section.address() < section.size()
+        }));
+    }
+}
+
+#[test]
+fn send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<File>();
+    assert_send_sync::<object::OwnedFile>();
+    assert_send_sync::<object::read::Section>();
+    assert_send_sync::<object::read::Segment>();
+    assert_send_sync::<object::read::Symbol>();
+    assert_send_sync::<object::read::SymbolTable>();
+    assert_send_sync::<object::read::Comdat>();
+    assert_send_sync::<object::read::SymbolMap<object::read::SymbolMapName>>();
+    assert_send_sync::<object::read::ObjectMap>();
+    assert_send_sync::<object::read::elf::ElfFile64>();
+    assert_send_sync::<object::read::macho::MachOFile64>();
+    assert_send_sync::<object::read::pe::PeFile64>();
+    assert_send_sync::<object::read::coff::CoffFile>();
+    #[cfg(feature = "wasm")]
+    assert_send_sync::<object::read::wasm::WasmFile>();
+}
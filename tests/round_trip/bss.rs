@@ -253,3 +253,37 @@ fn macho_x86_64_bss() {
     let symbol = symbols.next();
     assert!(symbol.is_none(), "unexpected symbol {:?}", symbol);
 }
+
+#[test]
+fn section_data_map_bss() {
+    for format in &[BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO] {
+        let mut object = write::Object::new(*format, Architecture::X86_64, Endianness::Little);
+
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0xcc; 4], 4);
+        let bss = object.section_id(write::StandardSection::UninitializedData);
+        object.append_section_bss(bss, 16, 4);
+
+        let bytes = object.write().unwrap();
+        let object = read::File::parse(&*bytes).unwrap();
+        let map = object.section_data_map().unwrap();
+        assert_eq!(map.len(), object.sections().count());
+
+        let text_name = object
+            .section_by_name(".text")
+            .unwrap()
+            .name()
+            .unwrap()
+            .to_string();
+        let (_, data) = map.iter().find(|(name, _)| *name == text_name).unwrap();
+        assert_eq!(*data, &[0xcc; 4][..]);
+        let bss_name = object
+            .section_by_name(".bss")
+            .unwrap()
+            .name()
+            .unwrap()
+            .to_string();
+        let (_, data) = map.iter().find(|(name, _)| *name == bss_name).unwrap();
+        assert!(data.is_empty());
+    }
+}
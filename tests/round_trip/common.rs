@@ -164,6 +164,34 @@ fn elf_x86_64_common() {
     assert!(symbol.is_none(), "unexpected symbol {:?}", symbol);
 }
 
+#[test]
+fn elf_common_alignment() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+    // The ELF symbol value of a common symbol is its alignment.
+    object.add_symbol(write::Symbol {
+        name: b"v1".to_vec(),
+        value: 16,
+        size: 4,
+        kind: SymbolKind::Data,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Common,
+        flags: SymbolFlags::None,
+    });
+
+    let bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+    let symbol = object
+        .symbols()
+        .find(|symbol| symbol.name() == Ok("v1"))
+        .unwrap();
+    assert_eq!(symbol.section(), read::SymbolSection::Common);
+    assert_eq!(symbol.common_alignment(), Some(16));
+    assert_eq!(symbol.tls_offset(), None);
+}
+
 #[test]
 fn macho_x86_64_common() {
     let mut object = write::Object::new(
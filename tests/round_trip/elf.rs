@@ -1,9 +1,10 @@
 use object::read::elf::{FileHeader, SectionHeader};
-use object::read::{Object, ObjectSymbol};
+use object::read::{Object, ObjectSection, ObjectSymbol};
 use object::{
     elf, read, write, Architecture, BinaryFormat, Endianness, LittleEndian, SectionIndex,
     SectionKind, SymbolFlags, SymbolKind, SymbolScope, SymbolSection, U32,
 };
+use std::convert::TryInto;
 use std::io::Write;
 
 #[test]
@@ -40,6 +41,43 @@ fn symtab_shndx() {
             SymbolSection::Section(SectionIndex(symbol.index().0))
         );
     }
+
+    // Check a symbol that requires an extended section index.
+    let symbol = object.symbols().last().unwrap();
+    assert_eq!(symbol.name(), Ok("func65535"));
+    assert_eq!(symbol.kind(), SymbolKind::Text);
+    let section_index = symbol.section_index().unwrap();
+    assert!(section_index.0 >= elf::SHN_LORESERVE as usize);
+    let section = object.section_by_index(section_index).unwrap();
+    assert_eq!(section.kind(), SectionKind::Text);
+    assert_eq!(section.name(), Ok(".text.func65535"));
+    let symbols = object.symbols_in_section(section_index);
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].index(), symbol.index());
+}
+
+#[test]
+fn extended_section_header_fields() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc], 1);
+    let mut bytes = object.write().unwrap();
+
+    // Move `e_shnum` and `e_shstrndx` into section 0, as is done for files
+    // with at least `SHN_LORESERVE` sections.
+    let shoff = u64::from_le_bytes(bytes[0x28..0x30].try_into().unwrap()) as usize;
+    let shnum = u16::from_le_bytes(bytes[0x3c..0x3e].try_into().unwrap());
+    let shstrndx = u16::from_le_bytes(bytes[0x3e..0x40].try_into().unwrap());
+    bytes[0x3c..0x3e].copy_from_slice(&0u16.to_le_bytes());
+    bytes[0x3e..0x40].copy_from_slice(&elf::SHN_XINDEX.to_le_bytes());
+    bytes[shoff + 0x20..shoff + 0x28].copy_from_slice(&u64::from(shnum).to_le_bytes());
+    bytes[shoff + 0x28..shoff + 0x2c].copy_from_slice(&u32::from(shstrndx).to_le_bytes());
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.sections().count(), shnum as usize);
+    let text = object.section_by_name(".text").unwrap();
+    assert_eq!(text.data(), Ok(&[0xcc][..]));
 }
 
 #[cfg(feature = "compression")]
@@ -84,6 +122,33 @@ fn compression_zlib() {
     let section = object.section_by_name(".debug_info").unwrap();
     let uncompressed = section.uncompressed_data().unwrap();
     assert_eq!(data, &*uncompressed);
+    assert!(!section.is_zero());
+
+    // A compressed section that only contains zeros.
+    ch.ch_size.set(LE, 64);
+    let mut buf = Vec::new();
+    buf.write_all(object::bytes_of(&ch)).unwrap();
+    let mut encoder = flate2::write::ZlibEncoder::new(buf, flate2::Compression::default());
+    encoder.write_all(&[0; 64]).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let section = object.add_section(
+        Vec::new(),
+        b".debug_info".to_vec(),
+        object::SectionKind::Other,
+    );
+    object.section_mut(section).set_data(compressed, 1);
+    object.section_mut(section).flags = object::SectionFlags::Elf {
+        sh_flags: object::elf::SHF_COMPRESSED.into(),
+    };
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let section = object.section_by_name(".debug_info").unwrap();
+    assert!(!section.data().unwrap().iter().all(|&byte| byte == 0));
+    assert!(section.is_zero());
 }
 
 #[cfg(feature = "compression")]
@@ -121,6 +186,10 @@ fn compression_gnu() {
     let section = object.section_by_name(".zdebug_info").unwrap();
     let uncompressed = section.uncompressed_data().unwrap();
     assert_eq!(data, &*uncompressed);
+
+    let sections = object.sections_by_name(".debug_info");
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].index(), section.index());
 }
 
 #[test]
@@ -216,3 +285,1602 @@ fn note() {
     assert_eq!(note.n_type(endian), 2);
     assert!(notes.next().unwrap().is_none());
 }
+
+#[test]
+fn eh_frame_hdr() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let section = object.add_section(Vec::new(), b".eh_frame".to_vec(), SectionKind::ReadOnlyData);
+    object.append_section_data(section, &[1; 16], 8);
+    let section = object.add_section(
+        Vec::new(),
+        b".eh_frame_hdr".to_vec(),
+        SectionKind::ReadOnlyData,
+    );
+    object.append_section_data(section, &[2; 8], 4);
+    let bytes = object.write().unwrap();
+
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    let eh_frame = object.section_by_name(".eh_frame").unwrap();
+    assert_eq!(eh_frame.data().unwrap(), &[1; 16]);
+    let eh_frame_hdr = object.eh_frame_hdr().unwrap();
+    assert_eq!(eh_frame_hdr.name().unwrap(), ".eh_frame_hdr");
+    assert_eq!(eh_frame_hdr.data().unwrap(), &[2; 8]);
+    // Relocatable files have no program headers.
+    assert!(object.eh_frame_hdr_segment().is_none());
+}
+
+#[test]
+fn ifunc() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 2], 1);
+    for (name, st_type) in &[
+        (b"resolver", elf::STT_GNU_IFUNC),
+        (b"function", elf::STT_FUNC),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: 0,
+            size: 1,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::Elf {
+                st_info: (elf::STB_GLOBAL << 4) + st_type,
+                st_other: elf::STV_DEFAULT,
+            },
+        });
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let symbol = object
+        .symbols()
+        .find(|s| s.name() == Ok("resolver"))
+        .unwrap();
+    assert!(symbol.is_ifunc());
+    let symbol = object
+        .symbols()
+        .find(|s| s.name() == Ok("function"))
+        .unwrap();
+    assert!(!symbol.is_ifunc());
+}
+
+#[test]
+fn local_global_symbols() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 2], 1);
+    for (name, scope) in &[
+        (b"global", SymbolScope::Dynamic),
+        (b"local1", SymbolScope::Compilation),
+        (b"local2", SymbolScope::Compilation),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: 0,
+            size: 1,
+            kind: SymbolKind::Text,
+            scope: *scope,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    let symbols = object.symbol_table().unwrap();
+    let locals = symbols.local_symbols().collect::<Vec<_>>();
+    assert!(locals.iter().all(|symbol| symbol.is_local()));
+    assert!(locals.iter().any(|symbol| symbol.name() == Ok("local1")));
+    assert!(locals.iter().any(|symbol| symbol.name() == Ok("local2")));
+    let globals = symbols.global_symbols().collect::<Vec<_>>();
+    assert_eq!(globals.len(), 1);
+    assert_eq!(globals[0].name(), Ok("global"));
+    assert_eq!(
+        locals.len() + globals.len(),
+        object.symbols().count(),
+        "all symbols must be either local or global"
+    );
+
+    // The generic methods use `sh_info` for ELF, and filter the symbols for other formats.
+    let object = read::File::parse(&*bytes).unwrap();
+    let names = |symbols: Vec<read::Symbol>| {
+        symbols
+            .iter()
+            .map(|symbol| symbol.name().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    let filtered = |local: bool| {
+        object
+            .symbols()
+            .filter(|symbol| symbol.is_local() == local)
+            .map(|symbol| symbol.name().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(object.local_symbols()), filtered(true));
+    assert_eq!(names(object.global_symbols()), vec!["global"]);
+    assert_eq!(names(object.global_symbols()), filtered(false));
+
+    let mut object =
+        write::Object::new(BinaryFormat::Coff, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 2], 1);
+    for (name, scope) in &[
+        (b"global", SymbolScope::Dynamic),
+        (b"local1", SymbolScope::Compilation),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: 0,
+            size: 1,
+            kind: SymbolKind::Text,
+            scope: *scope,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+    }
+    let bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+    let globals = object.global_symbols();
+    assert_eq!(globals.len(), 1);
+    assert_eq!(globals[0].name(), Ok("global"));
+    let locals = object.local_symbols();
+    assert!(locals.iter().all(|symbol| symbol.is_local()));
+    assert!(locals.iter().any(|symbol| symbol.name() == Ok("local1")));
+}
+
+#[test]
+fn relocated_data() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 16], 1);
+    let defined = object.add_symbol(write::Symbol {
+        name: b"defined".to_vec(),
+        value: 8,
+        size: 1,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Section(text),
+        flags: SymbolFlags::None,
+    });
+    let undefined = object.add_symbol(write::Symbol {
+        name: b"undefined".to_vec(),
+        value: 0,
+        size: 0,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Undefined,
+        flags: SymbolFlags::None,
+    });
+    let data = object.section_id(write::StandardSection::Data);
+    object.append_section_data(data, &[0; 16], 8);
+    for (offset, symbol) in &[(0, defined), (8, undefined)] {
+        object
+            .add_relocation(
+                data,
+                write::Relocation {
+                    offset: *offset,
+                    size: 64,
+                    kind: object::RelocationKind::Absolute,
+                    encoding: object::RelocationEncoding::Generic,
+                    symbol: *symbol,
+                    addend: 4,
+                },
+            )
+            .unwrap();
+    }
+    // PC-relative relocations to the same section and to another section.
+    let rodata = object.section_id(write::StandardSection::ReadOnlyData);
+    object.append_section_data(rodata, &[0; 4], 4);
+    for &(section, offset) in &[(text, 0), (rodata, 0)] {
+        object
+            .add_relocation(
+                section,
+                write::Relocation {
+                    offset,
+                    size: 32,
+                    kind: object::RelocationKind::Relative,
+                    encoding: object::RelocationEncoding::Generic,
+                    symbol: defined,
+                    addend: -4,
+                },
+            )
+            .unwrap();
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let section = object.section_by_name(".data").unwrap();
+    assert_eq!(section.data().unwrap(), &[0; 16]);
+    let relocated = section.relocated_data().unwrap();
+    assert_eq!(&relocated[..8], &12u64.to_le_bytes());
+    assert_eq!(&relocated[8..], &4u64.to_le_bytes());
+
+    let section = object.section_by_name(".text").unwrap();
+    let relocated = section.relocated_data().unwrap();
+    assert_eq!(&relocated[..4], &4u32.to_le_bytes());
+    // The PC-relative relocation to another section is left unchanged.
+    let section = object.section_by_name(".rodata").unwrap();
+    assert_eq!(&*section.relocated_data().unwrap(), &[0; 4]);
+}
+
+#[test]
+fn relocated_data_external_call() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    // call g; add $1, %eax; ret
+    object.append_section_data(text, &[0xe8, 0, 0, 0, 0, 0x83, 0xc0, 0x01, 0xc3], 1);
+    let g = object.add_symbol(write::Symbol {
+        name: b"g".to_vec(),
+        value: 0,
+        size: 0,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Undefined,
+        flags: SymbolFlags::None,
+    });
+    object
+        .add_relocation(
+            text,
+            write::Relocation {
+                offset: 1,
+                size: 32,
+                kind: object::RelocationKind::PltRelative,
+                encoding: object::RelocationEncoding::X86Branch,
+                symbol: g,
+                addend: -4,
+            },
+        )
+        .unwrap();
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let section = object.section_by_name(".text").unwrap();
+    let (_, relocation) = section.relocations().next().unwrap();
+    assert_eq!(
+        relocation.flags(),
+        object::RelocationFlags::Elf {
+            r_type: elf::R_X86_64_PLT32
+        }
+    );
+    // The undefined symbol is resolved to address 0.
+    let relocated = section.relocated_data().unwrap();
+    assert_eq!(&relocated[1..5], &(-5i32).to_le_bytes());
+    assert_eq!(&relocated[5..], &[0x83, 0xc0, 0x01, 0xc3]);
+}
+
+#[test]
+fn relocation_kinds() {
+    use object::{RelocationEncoding, RelocationFlags, RelocationKind};
+
+    // The raw relocation type, and the expected kind and size.
+    type Case = (u32, RelocationKind, u8);
+    let cases: &[(Architecture, &[Case])] = &[
+        (
+            Architecture::Aarch64,
+            &[
+                (elf::R_AARCH64_CALL26, RelocationKind::PltRelative, 26),
+                (elf::R_AARCH64_JUMP26, RelocationKind::PltRelative, 26),
+                (elf::R_AARCH64_ADR_PREL_LO21, RelocationKind::Relative, 0),
+                (elf::R_AARCH64_CONDBR19, RelocationKind::Relative, 0),
+                (elf::R_AARCH64_GOT_LD_PREL19, RelocationKind::GotRelative, 0),
+                (elf::R_AARCH64_GOTREL64, RelocationKind::GotBaseOffset, 64),
+                (elf::R_AARCH64_TLSGD_ADR_PAGE21, RelocationKind::TlsGd, 0),
+                (elf::R_AARCH64_TLSLD_ADR_PAGE21, RelocationKind::TlsLd, 0),
+                (
+                    elf::R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21,
+                    RelocationKind::TlsIe,
+                    0,
+                ),
+                (
+                    elf::R_AARCH64_TLSLE_ADD_TPREL_HI12,
+                    RelocationKind::TlsLe,
+                    0,
+                ),
+                (
+                    elf::R_AARCH64_ADR_PREL_PG_HI21,
+                    RelocationKind::Elf(elf::R_AARCH64_ADR_PREL_PG_HI21),
+                    0,
+                ),
+            ],
+        ),
+        (
+            Architecture::Arm,
+            &[
+                (elf::R_ARM_TLS_GD32, RelocationKind::TlsGd, 32),
+                (elf::R_ARM_TLS_LE32, RelocationKind::TlsLe, 32),
+            ],
+        ),
+        (
+            Architecture::I386,
+            &[
+                (elf::R_386_TLS_GD, RelocationKind::TlsGd, 32),
+                (elf::R_386_TLS_LDM, RelocationKind::TlsLd, 32),
+                (elf::R_386_TLS_IE, RelocationKind::TlsIe, 32),
+                (elf::R_386_TLS_LE, RelocationKind::TlsLe, 32),
+            ],
+        ),
+        (
+            Architecture::X86_64,
+            &[
+                (elf::R_X86_64_TLSGD, RelocationKind::TlsGd, 32),
+                (elf::R_X86_64_TLSLD, RelocationKind::TlsLd, 32),
+                (elf::R_X86_64_GOTTPOFF, RelocationKind::TlsIe, 32),
+                (elf::R_X86_64_TPOFF32, RelocationKind::TlsLe, 32),
+                (elf::R_X86_64_TPOFF64, RelocationKind::TlsLe, 64),
+            ],
+        ),
+    ];
+
+    for &(architecture, relocations) in cases {
+        let mut object = write::Object::new(BinaryFormat::Elf, architecture, Endianness::Little);
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0; 128], 4);
+        let symbol = object.add_symbol(write::Symbol {
+            name: b"target".to_vec(),
+            value: 0,
+            size: 0,
+            kind: SymbolKind::Tls,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Undefined,
+            flags: SymbolFlags::None,
+        });
+        for (i, &(r_type, _, _)) in relocations.iter().enumerate() {
+            object
+                .add_relocation(
+                    text,
+                    write::Relocation {
+                        offset: i as u64 * 8,
+                        size: 0,
+                        kind: RelocationKind::Elf(r_type),
+                        encoding: RelocationEncoding::Generic,
+                        symbol,
+                        addend: 0,
+                    },
+                )
+                .unwrap();
+        }
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        let section = object.section_by_name(".text").unwrap();
+        let mut count = 0;
+        for ((offset, relocation), &(r_type, kind, size)) in
+            section.relocations().zip(relocations.iter())
+        {
+            assert_eq!(offset, count * 8, "{:?} {}", architecture, r_type);
+            assert_eq!(relocation.kind(), kind, "{:?} {}", architecture, r_type);
+            assert_eq!(relocation.size(), size, "{:?} {}", architecture, r_type);
+            assert_eq!(relocation.flags(), RelocationFlags::Elf { r_type });
+            count += 1;
+        }
+        assert_eq!(count as usize, relocations.len());
+    }
+
+    // The TLS kinds can also be written directly when the size is unambiguous.
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0; 8], 4);
+    let symbol = object.add_symbol(write::Symbol {
+        name: b"target".to_vec(),
+        value: 0,
+        size: 0,
+        kind: SymbolKind::Tls,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Undefined,
+        flags: SymbolFlags::None,
+    });
+    object
+        .add_relocation(
+            text,
+            write::Relocation {
+                offset: 0,
+                size: 32,
+                kind: RelocationKind::TlsIe,
+                encoding: RelocationEncoding::Generic,
+                symbol,
+                addend: -4,
+            },
+        )
+        .unwrap();
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let section = object.section_by_name(".text").unwrap();
+    let (_, relocation) = section.relocations().next().unwrap();
+    assert_eq!(relocation.kind(), RelocationKind::TlsIe);
+    assert_eq!(
+        relocation.flags(),
+        RelocationFlags::Elf {
+            r_type: elf::R_X86_64_GOTTPOFF
+        }
+    );
+}
+
+#[test]
+fn validate() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 16], 1);
+    let data = object.section_id(write::StandardSection::Data);
+    object.append_section_data(data, &[0; 16], 1);
+    let mut bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.validate(), Vec::new());
+    let text_index = object.section_by_name(".text").unwrap().index();
+    let text_offset = object
+        .section_by_name(".text")
+        .unwrap()
+        .file_range()
+        .unwrap()
+        .0;
+    let data_index = object.section_by_name(".data").unwrap().index();
+
+    let header = elf::FileHeader64::<LittleEndian>::parse(&*bytes).unwrap();
+    let shdr_offset = header.e_shoff.get(LittleEndian) as usize
+        + data_index.0 * std::mem::size_of::<elf::SectionHeader64<LittleEndian>>();
+    // Offset of `sh_offset` within the section header.
+    let sh_offset = shdr_offset + 24;
+
+    bytes[sh_offset..][..8].copy_from_slice(&(text_offset + 8).to_le_bytes());
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(
+        object.validate(),
+        vec![read::ValidationWarning::SectionOverlap(
+            text_index, data_index
+        )]
+    );
+
+    bytes[sh_offset..][..8].copy_from_slice(&0x1000_0000u64.to_le_bytes());
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(
+        object.validate(),
+        vec![read::ValidationWarning::SectionOutOfBounds(data_index)]
+    );
+}
+
+#[test]
+fn big_endian() {
+    for (arch, machine) in &[
+        (Architecture::Mips, elf::EM_MIPS),
+        (Architecture::PowerPc64, elf::EM_PPC64),
+    ] {
+        let mut object = write::Object::new(BinaryFormat::Elf, *arch, Endianness::Big);
+        let mut contents = vec![0; 0x2000];
+        contents[0x1234..][..4].copy_from_slice(&0x0102_0304u32.to_be_bytes());
+        let data = object.section_id(write::StandardSection::Data);
+        object.append_section_data(data, &contents, 8);
+        object.add_symbol(write::Symbol {
+            name: b"value".to_vec(),
+            value: 0x1234,
+            size: 4,
+            kind: SymbolKind::Data,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(data),
+            flags: SymbolFlags::None,
+        });
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        assert_eq!(object.architecture(), *arch);
+        assert_eq!(object.endianness(), Endianness::Big);
+        let e_machine = u16::from_be_bytes([bytes[18], bytes[19]]);
+        assert_eq!(e_machine, *machine);
+
+        let section = object.section_by_name(".data").unwrap();
+        assert_eq!(section.address(), 0);
+        assert_eq!(section.size(), 0x2000);
+        assert_eq!(section.align(), 8);
+        let (offset, size) = section.file_range().unwrap();
+        assert_eq!(size, 0x2000);
+        assert_eq!(&bytes[offset as usize..][..size as usize], &contents[..]);
+
+        let symbol = object.symbols().find(|s| s.name() == Ok("value")).unwrap();
+        assert_eq!(symbol.address(), 0x1234);
+        assert_eq!(symbol.size(), 4);
+        assert_eq!(symbol.section_index(), Some(section.index()));
+        assert_eq!(
+            section.data_range(symbol.address(), symbol.size()),
+            Ok(Some(&[1, 2, 3, 4][..]))
+        );
+    }
+}
+
+#[test]
+fn os_abi() {
+    let object = write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let mut bytes = object.write().unwrap();
+
+    let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert_eq!(file.os_abi(), read::elf::ElfOsAbi::SystemV);
+    assert_eq!(file.abi_version(), 0);
+
+    // Offsets of `os_abi` and `abi_version` in `elf::Ident`.
+    bytes[7] = elf::ELFOSABI_FREEBSD;
+    bytes[8] = 1;
+    let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert_eq!(file.os_abi(), read::elf::ElfOsAbi::FreeBsd);
+    assert_eq!(file.abi_version(), 1);
+
+    bytes[7] = 200;
+    let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert_eq!(file.os_abi(), read::elf::ElfOsAbi::Other(200));
+    assert_eq!(file.os_abi().raw(), 200);
+}
+
+#[test]
+fn gnu_debugaltlink() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let section = object.add_section(
+        Vec::new(),
+        b".gnu_debugaltlink".to_vec(),
+        SectionKind::Debug,
+    );
+    object.append_section_data(section, b"/usr/lib/debug/.dwz/foo.debug\0\x12\x34\x56", 1);
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(
+        object.gnu_debugaltlink(),
+        Ok(Some((
+            &b"/usr/lib/debug/.dwz/foo.debug"[..],
+            &[0x12, 0x34, 0x56][..]
+        )))
+    );
+    assert_eq!(object.gnu_debuglink(), Ok(None));
+}
+
+#[test]
+fn compiler_info() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    assert!(read::File::parse(&*object.write().unwrap())
+        .unwrap()
+        .compiler_info()
+        .is_empty());
+
+    let section = object.add_section(Vec::new(), b".comment".to_vec(), SectionKind::OtherString);
+    object.append_section_data(section, b"\0GCC: (GNU) 10.2.0\0clang version 11.0.0\0", 1);
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(
+        object.compiler_info(),
+        vec!["GCC: (GNU) 10.2.0", "clang version 11.0.0"]
+    );
+}
+
+#[test]
+fn lazy_symbols() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 2], 1);
+    object.add_symbol(write::Symbol {
+        name: b"symbol".to_vec(),
+        value: 0,
+        size: 1,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Section(text),
+        flags: SymbolFlags::None,
+    });
+    let mut bytes = object.write().unwrap();
+
+    let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    let index = file
+        .symbols()
+        .find(|s| s.name() == Ok("symbol"))
+        .unwrap()
+        .index();
+    let symtab = file.section_by_name(".symtab").unwrap();
+    let offset = symtab.file_range().unwrap().0 as usize
+        + index.0 * std::mem::size_of::<elf::Sym64<LittleEndian>>();
+
+    // Corrupt the name of the symbol. Parsing the file must not read it.
+    bytes[offset..][..4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let file = read::File::parse(&*bytes).unwrap();
+    assert!(file.section_by_name(".text").is_some());
+    let symbol = file.symbol_by_index(index).unwrap();
+    assert!(symbol.name().is_err());
+}
+
+#[test]
+fn section_offset_overflow() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let data = object.section_id(write::StandardSection::Data);
+    object.append_section_data(data, &[0; 16], 1);
+    let mut bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let data_index = object.section_by_name(".data").unwrap().index();
+    let header = elf::FileHeader64::<LittleEndian>::parse(&*bytes).unwrap();
+    let shdr_offset = header.e_shoff.get(LittleEndian) as usize
+        + data_index.0 * std::mem::size_of::<elf::SectionHeader64<LittleEndian>>();
+    // Offset of `sh_offset` within the section header.
+    let sh_offset = shdr_offset + 24;
+    bytes[sh_offset..][..8].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let section = object.section_by_name(".data").unwrap();
+    assert!(section.data().is_err());
+    assert!(section.data_range(0, 1).is_err());
+    assert!(section.uncompressed_data().is_err());
+    assert!(object.cstr_at(0).is_none());
+}
+
+#[test]
+fn dynamic_paths() {
+    // A shared object with no section headers, so the dynamic string table
+    // must be found using `DT_STRTAB`.
+    let strtab = b"\0/a:/b\0$ORIGIN/lib\0";
+    let strtab_offset = 64 + 2 * 56;
+    let dynamic_offset = (strtab_offset + strtab.len() + 7) & !7;
+    let dynamic = [
+        (elf::DT_STRTAB, strtab_offset as u64),
+        (elf::DT_STRSZ, strtab.len() as u64),
+        (elf::DT_RPATH, 1),
+        (elf::DT_RUNPATH, 7),
+        (elf::DT_NULL, 0),
+    ];
+    let size = (dynamic_offset + dynamic.len() * 16) as u64;
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&[0x7f, b'E', b'L', b'F', 2, 1, 1, 0]);
+    bytes.resize(16, 0);
+    bytes.extend_from_slice(&elf::ET_DYN.to_le_bytes());
+    bytes.extend_from_slice(&elf::EM_X86_64.to_le_bytes());
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&0u64.to_le_bytes());
+    bytes.extend_from_slice(&64u64.to_le_bytes());
+    bytes.extend_from_slice(&0u64.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    for value in &[64u16, 56, 2, 64, 0, 0] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    for &(p_type, p_flags, offset, size) in &[
+        (elf::PT_LOAD, elf::PF_R | elf::PF_W, 0, size),
+        (
+            elf::PT_DYNAMIC,
+            elf::PF_R | elf::PF_W,
+            dynamic_offset as u64,
+            size - dynamic_offset as u64,
+        ),
+    ] {
+        bytes.extend_from_slice(&p_type.to_le_bytes());
+        bytes.extend_from_slice(&p_flags.to_le_bytes());
+        for value in &[offset, offset, offset, size, size, 8] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    bytes.extend_from_slice(strtab);
+    bytes.resize(dynamic_offset, 0);
+    for &(tag, value) in &dynamic {
+        bytes.extend_from_slice(&u64::from(tag).to_le_bytes());
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert_eq!(object.dynamic_entries().unwrap().len(), 4);
+    assert_eq!(object.rpaths().unwrap(), [&b"/a"[..], &b"/b"[..]]);
+    assert_eq!(object.runpaths().unwrap(), [&b"$ORIGIN/lib"[..]]);
+
+    let object = write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let bytes = object.write().unwrap();
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(object.runpaths().unwrap().is_empty());
+}
+
+#[test]
+fn gnu_properties() {
+    let endian = Endianness::Little;
+    let mut object = write::Object::new(BinaryFormat::Elf, Architecture::X86_64, endian);
+
+    let mut desc = Vec::new();
+    for (pr_type, value) in &[
+        (
+            elf::GNU_PROPERTY_STACK_SIZE,
+            &[0x10, 0, 0, 0, 0, 0, 0, 0][..],
+        ),
+        (
+            elf::GNU_PROPERTY_X86_FEATURE_1_AND,
+            &(elf::GNU_PROPERTY_X86_FEATURE_1_IBT | elf::GNU_PROPERTY_X86_FEATURE_1_SHSTK)
+                .to_le_bytes()[..],
+        ),
+    ] {
+        desc.extend_from_slice(&pr_type.to_le_bytes());
+        desc.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        desc.extend_from_slice(value);
+        // Properties are aligned to 8 bytes for ELFCLASS64.
+        desc.resize((desc.len() + 7) & !7, 0);
+    }
+    let mut buffer = Vec::new();
+    buffer
+        .write_all(object::bytes_of(&elf::NoteHeader32 {
+            n_namesz: U32::new(endian, 4),
+            n_descsz: U32::new(endian, desc.len() as u32),
+            n_type: U32::new(endian, elf::NT_GNU_PROPERTY_TYPE_0),
+        }))
+        .unwrap();
+    buffer.write_all(elf::ELF_NOTE_GNU).unwrap();
+    buffer.write_all(&[0]).unwrap();
+    buffer.write_all(&desc).unwrap();
+    let section = object.add_section(
+        Vec::new(),
+        b".note.gnu.property".to_vec(),
+        SectionKind::Note,
+    );
+    object.section_mut(section).set_data(buffer, 8);
+
+    let bytes = object.write().unwrap();
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    let properties = object.gnu_properties().unwrap();
+    assert_eq!(properties.len(), 2);
+    assert_eq!(properties[0].pr_type(), elf::GNU_PROPERTY_STACK_SIZE);
+    assert_eq!(properties[0].pr_data(), &[0x10, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(properties[1].pr_type(), elf::GNU_PROPERTY_X86_FEATURE_1_AND);
+    assert_eq!(properties[1].data_u32(endian), Ok(3));
+    assert!(object.supports_cet());
+    assert!(!object.supports_bti());
+    assert!(!object.supports_pac());
+}
+
+#[test]
+fn arm_thumb() {
+    let mut object = write::Object::new(BinaryFormat::Elf, Architecture::Arm, Endianness::Little);
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0; 8], 4);
+    for (name, value, kind) in &[
+        (&b"thumb"[..], 1, SymbolKind::Text),
+        (&b"arm"[..], 4, SymbolKind::Text),
+        (&b"byte"[..], 5, SymbolKind::Data),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: *value,
+            size: 0,
+            kind: *kind,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::elf::ElfFile32::<Endianness>::parse(&*bytes).unwrap();
+    let symbols: Vec<_> = object
+        .symbols()
+        .filter(|symbol| symbol.is_definition())
+        .map(|symbol| {
+            (
+                symbol.name().unwrap(),
+                symbol.address(),
+                symbol.raw_address(),
+                symbol.is_thumb(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        symbols,
+        [
+            ("thumb", 0, 1, true),
+            ("arm", 4, 4, false),
+            ("byte", 5, 5, false),
+        ]
+    );
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let map = object.symbol_map();
+    assert_eq!(map.get(0).unwrap().name(), "thumb");
+}
+
+#[test]
+fn plt_is_stub() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xc3], 1);
+    let plt = object.add_section(Vec::new(), b".plt".to_vec(), SectionKind::Text);
+    object.append_section_data(plt, &[0xcc; 16], 16);
+    let data = object.add_section(Vec::new(), b".pltdata".to_vec(), SectionKind::Data);
+    object.append_section_data(data, &[0; 16], 16);
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert!(!object.section_by_name(".text").unwrap().is_stub());
+    assert!(object.section_by_name(".plt").unwrap().is_stub());
+    assert!(!object.section_by_name(".pltdata").unwrap().is_stub());
+}
+
+#[test]
+fn symbol_file_name() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 16], 16);
+    let mut add = |name: &[u8], kind, scope| {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: 0,
+            size: 0,
+            kind,
+            scope,
+            weak: false,
+            section: if kind == SymbolKind::File {
+                write::SymbolSection::None
+            } else {
+                write::SymbolSection::Section(text)
+            },
+            flags: SymbolFlags::None,
+        });
+    };
+    add(b"a.c", SymbolKind::File, SymbolScope::Compilation);
+    add(b"a_local", SymbolKind::Text, SymbolScope::Compilation);
+    add(b"b.c", SymbolKind::File, SymbolScope::Compilation);
+    add(b"b_local", SymbolKind::Text, SymbolScope::Compilation);
+    add(b"global", SymbolKind::Text, SymbolScope::Linkage);
+    let bytes = object.write().unwrap();
+
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    let files: Vec<_> = object
+        .symbols()
+        .skip(1)
+        .map(|symbol| (symbol.name().unwrap(), symbol.file_name().unwrap()))
+        .collect();
+    assert_eq!(
+        files,
+        [
+            ("a.c", Some(&b"a.c"[..])),
+            ("a_local", Some(&b"a.c"[..])),
+            ("b.c", Some(&b"b.c"[..])),
+            ("b_local", Some(&b"b.c"[..])),
+            ("global", None),
+        ]
+    );
+
+    // Symbols that are not returned by an iterator search the symbol table instead.
+    for symbol in object.symbols() {
+        let by_index = object.symbol_by_index(symbol.index()).unwrap();
+        assert_eq!(by_index.file_name(), symbol.file_name());
+    }
+}
+
+#[test]
+fn section_read_integers() {
+    let data = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    for &(architecture, endian) in &[
+        (Architecture::X86_64, Endianness::Little),
+        (Architecture::PowerPc64, Endianness::Big),
+    ] {
+        let mut object = write::Object::new(BinaryFormat::Elf, architecture, endian);
+        let section = object.section_id(write::StandardSection::Data);
+        object.append_section_data(section, &data, 1);
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        let section = object.section_by_name(".data").unwrap();
+        assert_eq!(section.endianness(), endian);
+        if endian == Endianness::Little {
+            assert_eq!(section.read_u16(1), Some(0x0302));
+            assert_eq!(section.read_u32(0), Some(0x0403_0201));
+            assert_eq!(section.read_u64(1), Some(0x0908_0706_0504_0302));
+        } else {
+            assert_eq!(section.read_u16(1), Some(0x0203));
+            assert_eq!(section.read_u32(0), Some(0x0102_0304));
+            assert_eq!(section.read_u64(1), Some(0x0203_0405_0607_0809));
+        }
+        assert_eq!(section.read_u16(8), None);
+        assert_eq!(section.read_u32(6), None);
+        assert_eq!(section.read_u64(2), None);
+        assert_eq!(section.read_u64(u64::max_value()), None);
+    }
+}
+
+#[test]
+fn section_kind_ignores_name() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+    for &(name, kind) in &[
+        (&b"zeroed"[..], SectionKind::UninitializedData),
+        (&b"code"[..], SectionKind::Text),
+        (&b".bss"[..], SectionKind::Data),
+        (&b".text"[..], SectionKind::ReadOnlyData),
+    ] {
+        let section = object.add_section(Vec::new(), name.to_vec(), kind);
+        if kind == SectionKind::UninitializedData {
+            object.append_section_bss(section, 16, 1);
+        } else {
+            object.append_section_data(section, &[1; 16], 1);
+        }
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let kinds = object
+        .sections()
+        .filter(|section| section.size() == 16)
+        .map(|section| (section.name().unwrap().to_string(), section.kind()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            ("zeroed".to_string(), SectionKind::UninitializedData),
+            ("code".to_string(), SectionKind::Text),
+            (".bss".to_string(), SectionKind::Data),
+            (".text".to_string(), SectionKind::ReadOnlyData),
+        ]
+    );
+}
+
+#[test]
+fn build_id_uuid() {
+    for &(len, expect) in &[(20, true), (16, true), (8, false)] {
+        let mut object =
+            write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+        let build_id = (0..len).collect::<Vec<u8>>();
+        let mut note = Vec::new();
+        note.extend_from_slice(&4u32.to_le_bytes());
+        note.extend_from_slice(&(build_id.len() as u32).to_le_bytes());
+        note.extend_from_slice(&elf::NT_GNU_BUILD_ID.to_le_bytes());
+        note.extend_from_slice(b"GNU\0");
+        note.extend_from_slice(&build_id);
+        let section = object.add_section(
+            Vec::new(),
+            b".note.gnu.build-id".to_vec(),
+            SectionKind::Note,
+        );
+        object.append_section_data(section, &note, 4);
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        assert_eq!(object.build_id().unwrap(), Some(&build_id[..]));
+        let uuid = object.uuid().unwrap();
+        if expect {
+            assert_eq!(uuid.as_ref().map(|uuid| &uuid[..]), Some(&build_id[..16]));
+        } else {
+            assert_eq!(uuid, None);
+        }
+    }
+}
+
+#[test]
+fn gnu_debugdata() {
+    // "hello world" repeated 3 times, compressed with XZ.
+    let xz = [
+        0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00, 0x00, 0x04, 0xe6, 0xd6, 0xb4, 0x46, 0x02, 0x00, 0x21,
+        0x01, 0x16, 0x00, 0x00, 0x00, 0x74, 0x2f, 0xe5, 0xa3, 0xe0, 0x00, 0x20, 0x00, 0x11, 0x5d,
+        0x00, 0x34, 0x19, 0x49, 0xee, 0x8d, 0xe9, 0x17, 0x89, 0x3a, 0x33, 0x60, 0x09, 0x19, 0xe9,
+        0x96, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x73, 0xf9, 0xff, 0x8f, 0xe1, 0xa4, 0x55,
+        0x00, 0x01, 0x2d, 0x21, 0x9a, 0xff, 0xc5, 0x4c, 0x1f, 0xb6, 0xf3, 0x7d, 0x01, 0x00, 0x00,
+        0x00, 0x00, 0x04, 0x59, 0x5a,
+    ];
+    let mut xz = xz.to_vec();
+    for &padding in &[0, 8] {
+        xz.resize(xz.len() + padding, 0);
+
+        let mut object =
+            write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+        let section =
+            object.add_section(Vec::new(), b".gnu_debugdata".to_vec(), SectionKind::Other);
+        object.append_section_data(section, &xz, 1);
+        let bytes = object.write().unwrap();
+
+        let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+        let data = object.compressed_gnu_debugdata().unwrap().unwrap();
+        assert_eq!(data.format, read::CompressionFormat::Xz);
+        assert_eq!(data.data, &xz[..]);
+        assert_eq!(data.uncompressed_size, 33);
+        #[cfg(feature = "xz")]
+        assert_eq!(
+            &*object.gnu_debugdata().unwrap().unwrap(),
+            &b"hello worldhello worldhello world"[..]
+        );
+    }
+
+    let object = write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let bytes = object.write().unwrap();
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(object.gnu_debugdata().unwrap().is_none());
+}
+
+#[test]
+fn symbol_data() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1, 2, 3, 4, 5, 6, 7, 8], 1);
+    for &(name, value, size, section) in &[
+        (&b"func"[..], 2, 3, write::SymbolSection::Section(text)),
+        (&b"past_end"[..], 6, 4, write::SymbolSection::Section(text)),
+        (&b"empty"[..], 0, 0, write::SymbolSection::Section(text)),
+        (&b"undef"[..], 0, 4, write::SymbolSection::Undefined),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value,
+            size,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section,
+            flags: SymbolFlags::None,
+        });
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let symbol_data = |name| {
+        let symbol = object
+            .symbols()
+            .find(|symbol| symbol.name() == Ok(name))
+            .unwrap();
+        object.symbol_data(&symbol).unwrap()
+    };
+    assert_eq!(symbol_data("func"), Some(&[3, 4, 5][..]));
+    assert_eq!(symbol_data("past_end"), None);
+    assert_eq!(symbol_data("empty"), None);
+    assert_eq!(symbol_data("undef"), None);
+}
+
+#[test]
+fn symbol_warnings() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+    let section = object.add_section(
+        Vec::new(),
+        b".gnu.warning.gets".to_vec(),
+        SectionKind::ReadOnlyString,
+    );
+    object.append_section_data(section, b"the `gets' function is dangerous\0", 1);
+    let section = object.add_section(Vec::new(), b".gnu.warning".to_vec(), SectionKind::Other);
+    object.append_section_data(section, b"not a symbol warning", 1);
+    let bytes = object.write().unwrap();
+
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert_eq!(
+        object.symbol_warnings().unwrap(),
+        [(&b"gets"[..], &b"the `gets' function is dangerous"[..])]
+    );
+}
+
+#[test]
+fn relr() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+    let section = object.add_section(
+        Vec::new(),
+        b".relr.dyn".to_vec(),
+        SectionKind::Elf(elf::SHT_RELR),
+    );
+    let mut data = Vec::new();
+    for entry in &[0x1000u64, 0b1011, 0b11, 0x2000] {
+        data.extend_from_slice(&entry.to_le_bytes());
+    }
+    object.append_section_data(section, &data, 8);
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let relocations = object.dynamic_relocations().unwrap().collect::<Vec<_>>();
+    let addresses = relocations
+        .iter()
+        .map(|(address, _)| *address)
+        .collect::<Vec<_>>();
+    assert_eq!(addresses, [0x1000, 0x1008, 0x1018, 0x1200, 0x2000]);
+    let relocation = &relocations[0].1;
+    assert_eq!(
+        relocation.kind(),
+        object::RelocationKind::Elf(elf::R_X86_64_RELATIVE)
+    );
+    assert_eq!(relocation.target(), read::RelocationTarget::Absolute);
+    assert!(relocation.has_implicit_addend());
+}
+
+/// A shared object linked with `ld -z pack-relative-relocs` from:
+///
+/// ```c
+/// static int x[8];
+/// int *p[4] = { &x[0], &x[1], &x[2], &x[7] };
+/// int *q = &x[3];
+/// ```
+///
+/// using `gcc -O2 -fPIC -nostdlib -shared -Wl,-z,pack-relative-relocs -Wl,-z,norelro
+/// -Wl,-z,noseparate-code -Wl,-z,max-page-size=0x10 -Wl,--build-id=none`, then `strip -s`.
+#[test]
+fn relr_linked() {
+    use object::elf::FileHeader64;
+    use object::read::elf::ElfFile64;
+
+    let mut data = include_bytes!("../data/relr-x86_64.so").to_vec();
+    let expected = [0x300, 0x320, 0x328, 0x330, 0x338];
+
+    let object = read::File::parse(&*data).unwrap();
+    assert!(!object.is_relocatable());
+    assert!(object.section_by_name(".relr.dyn").is_some());
+    let addresses = object
+        .dynamic_relocations()
+        .unwrap()
+        .map(|(address, _)| address)
+        .collect::<Vec<_>>();
+    assert_eq!(addresses, expected);
+
+    // Strip the section headers, so that `DT_RELR` must be used instead.
+    {
+        let header = object::from_bytes_mut::<FileHeader64<LittleEndian>>(&mut data)
+            .unwrap()
+            .0;
+        header.e_shoff.set(LittleEndian, 0);
+        header.e_shnum.set(LittleEndian, 0);
+        header.e_shstrndx.set(LittleEndian, 0);
+    }
+    let object = ElfFile64::<LittleEndian>::parse(&*data).unwrap();
+    assert_eq!(object.sections().count(), 0);
+    let relocations = object.dynamic_relocations().unwrap().collect::<Vec<_>>();
+    let addresses = relocations
+        .iter()
+        .map(|(address, _)| *address)
+        .collect::<Vec<_>>();
+    assert_eq!(addresses, expected);
+    assert_eq!(
+        relocations[0].1.kind(),
+        object::RelocationKind::Elf(elf::R_X86_64_RELATIVE)
+    );
+}
+
+#[test]
+fn arm_eabi() {
+    for &(e_flags, version, float_abi) in &[
+        (
+            elf::EF_ARM_EABI_VER5 | elf::EF_ARM_ABI_FLOAT_HARD,
+            5,
+            read::elf::ArmFloatAbi::Hard,
+        ),
+        (
+            elf::EF_ARM_EABI_VER5 | elf::EF_ARM_ABI_FLOAT_SOFT,
+            5,
+            read::elf::ArmFloatAbi::Soft,
+        ),
+        (elf::EF_ARM_EABI_VER5, 5, read::elf::ArmFloatAbi::Unknown),
+        (
+            elf::EF_ARM_EABI_VER4 | elf::EF_ARM_VFP_FLOAT,
+            4,
+            read::elf::ArmFloatAbi::Unknown,
+        ),
+    ] {
+        let mut object =
+            write::Object::new(BinaryFormat::Elf, Architecture::Arm, Endianness::Little);
+        object.flags = object::FileFlags::Elf { e_flags };
+        let bytes = object.write().unwrap();
+
+        let object = read::elf::ElfFile32::<Endianness>::parse(&*bytes).unwrap();
+        let eabi = object.arm_eabi().unwrap();
+        assert_eq!(eabi.version, version);
+        assert_eq!(eabi.float_abi, float_abi);
+    }
+
+    let object = write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let bytes = object.write().unwrap();
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert_eq!(object.arm_eabi(), None);
+}
+
+#[test]
+fn parse_complete() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xc3; 16], 1);
+    let mut bytes = object.write().unwrap();
+
+    let (index, offset) = {
+        let object = read::File::parse(&*bytes).unwrap();
+        let section = object.section_by_name(".text").unwrap();
+        (section.index().0, section.file_range().unwrap().0)
+    };
+    // Make the section extend past the end of the file.
+    let header = elf::FileHeader64::<LittleEndian>::parse(&*bytes).unwrap();
+    let sh_size = header.e_shoff(LittleEndian) as usize
+        + index * std::mem::size_of::<elf::SectionHeader64<LittleEndian>>()
+        + 32;
+    bytes[sh_size..][..8].copy_from_slice(&0x10000u64.to_le_bytes());
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.required_file_size(), offset + 0x10000);
+    let error = read::File::parse_complete(&*bytes).err().unwrap();
+    assert_eq!(
+        error,
+        read::ParseError::Truncated {
+            expected: offset + 0x10000,
+            actual: bytes.len() as u64,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "File data is truncated: {} bytes are required, but only {} are present",
+            offset + 0x10000,
+            bytes.len()
+        )
+    );
+    let section = object.section_by_name(".text").unwrap();
+    assert!(section.data().is_err());
+}
+
+#[test]
+fn notype_label() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xc3; 16], 1);
+    for &(name, section) in &[
+        (&b"label"[..], write::SymbolSection::Section(text)),
+        (b"absolute", write::SymbolSection::Absolute),
+        (b"undefined", write::SymbolSection::Undefined),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: 0,
+            size: 0,
+            kind: SymbolKind::Label,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section,
+            flags: SymbolFlags::None,
+        });
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let kind = |name| {
+        object
+            .symbols()
+            .find(|symbol| symbol.name() == Ok(name))
+            .unwrap()
+            .kind()
+    };
+    assert_eq!(kind("label"), SymbolKind::Label);
+    assert_eq!(kind("absolute"), SymbolKind::Unknown);
+    assert_eq!(kind("undefined"), SymbolKind::Unknown);
+}
+
+#[test]
+fn core_file() {
+    fn push(data: &mut [u8], offset: usize, bytes: &[u8]) {
+        data[offset..][..bytes.len()].copy_from_slice(bytes);
+    }
+
+    let mut data = vec![0u8; 0x200];
+    // File header.
+    push(&mut data, 0, &[0x7f, b'E', b'L', b'F', 2, 1, 1]);
+    push(&mut data, 16, &elf::ET_CORE.to_le_bytes());
+    push(&mut data, 18, &elf::EM_X86_64.to_le_bytes());
+    push(&mut data, 20, &1u32.to_le_bytes());
+    push(&mut data, 32, &64u64.to_le_bytes());
+    push(&mut data, 52, &64u16.to_le_bytes());
+    push(&mut data, 54, &56u16.to_le_bytes());
+    push(&mut data, 56, &2u16.to_le_bytes());
+
+    // The NT_FILE note.
+    let mut desc = Vec::new();
+    for word in &[1u64, 0x1000, 0x40_0000, 0x40_1000, 2] {
+        desc.extend_from_slice(&word.to_le_bytes());
+    }
+    desc.extend_from_slice(b"/bin/true\0");
+    let mut note = Vec::new();
+    note.extend_from_slice(&5u32.to_le_bytes());
+    note.extend_from_slice(&(desc.len() as u32).to_le_bytes());
+    note.extend_from_slice(&elf::NT_FILE.to_le_bytes());
+    note.extend_from_slice(b"CORE\0\0\0\0");
+    note.extend_from_slice(&desc);
+    push(&mut data, 0x100, &note);
+
+    // PT_NOTE segment.
+    push(&mut data, 64, &elf::PT_NOTE.to_le_bytes());
+    push(&mut data, 64 + 8, &0x100u64.to_le_bytes());
+    push(&mut data, 64 + 32, &(note.len() as u64).to_le_bytes());
+    push(&mut data, 64 + 48, &4u64.to_le_bytes());
+    // PT_LOAD segment with the process memory.
+    push(&mut data, 120, &elf::PT_LOAD.to_le_bytes());
+    push(&mut data, 120 + 8, &0x180u64.to_le_bytes());
+    push(&mut data, 120 + 16, &0x40_0000u64.to_le_bytes());
+    push(&mut data, 120 + 32, &16u64.to_le_bytes());
+    push(&mut data, 120 + 40, &0x1000u64.to_le_bytes());
+    push(&mut data, 0x180, b"process memory\0");
+
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*data).unwrap();
+    assert_eq!(
+        object.address_map().cstr_at(0x40_0008),
+        Some(&b"memory"[..])
+    );
+    assert_eq!(object.cstr_at(0x40_0008), Some(&b"memory"[..]));
+    let notes = object.note_segments().unwrap();
+    assert_eq!(notes.len(), 1);
+    let mappings = notes[0].file_mappings(Endianness::Little).unwrap().unwrap();
+    assert_eq!(
+        mappings,
+        [read::elf::CoreFileMapping {
+            start: 0x40_0000,
+            end: 0x40_1000,
+            file_offset: 0x2000,
+            path: b"/bin/true",
+        }]
+    );
+
+    let object = read::File::parse(&*data).unwrap();
+    assert_eq!(object.address_map().data_at(0x40_000e), Some(&[0, 0][..]));
+}
+
+#[test]
+fn version_requirements() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+    let dynstr = object.add_section(
+        Vec::new(),
+        b".dynstr".to_vec(),
+        SectionKind::Elf(elf::SHT_STRTAB),
+    );
+    object.append_section_data(
+        dynstr,
+        b"\0libc.so.6\0GLIBC_2.2.5\0GLIBC_2.34\0libm.so.6\0GLIBC_2.29\0",
+        1,
+    );
+
+    let mut verneed = Vec::new();
+    // (vn_cnt, vn_file, vn_next), followed by the vna_name of each Vernaux.
+    for &(cnt, file, next, names) in &[(2u16, 1u32, 48u32, &[11u32, 23][..]), (1, 34, 0, &[44])] {
+        verneed.extend_from_slice(&elf::VER_NEED_CURRENT.to_le_bytes());
+        verneed.extend_from_slice(&cnt.to_le_bytes());
+        verneed.extend_from_slice(&file.to_le_bytes());
+        verneed.extend_from_slice(&16u32.to_le_bytes());
+        verneed.extend_from_slice(&next.to_le_bytes());
+        for (i, name) in names.iter().enumerate() {
+            let next = if i + 1 == names.len() { 0u32 } else { 16 };
+            verneed.extend_from_slice(&0u32.to_le_bytes());
+            verneed.extend_from_slice(&0u16.to_le_bytes());
+            verneed.extend_from_slice(&0u16.to_le_bytes());
+            verneed.extend_from_slice(&name.to_le_bytes());
+            verneed.extend_from_slice(&next.to_le_bytes());
+        }
+    }
+    let version_r = object.add_section(
+        Vec::new(),
+        b".gnu.version_r".to_vec(),
+        SectionKind::Elf(elf::SHT_GNU_VERNEED),
+    );
+    object.append_section_data(version_r, &verneed, 8);
+    let mut bytes = object.write().unwrap();
+
+    // Set `sh_link` and `sh_info`, which can't be set by the writer.
+    let (dynstr_index, version_r_index) = {
+        let object = read::File::parse(&*bytes).unwrap();
+        (
+            object.section_by_name(".dynstr").unwrap().index().0,
+            object.section_by_name(".gnu.version_r").unwrap().index().0,
+        )
+    };
+    let header = elf::FileHeader64::<LittleEndian>::parse(&*bytes).unwrap();
+    let section_header = header.e_shoff(LittleEndian) as usize
+        + version_r_index * std::mem::size_of::<elf::SectionHeader64<LittleEndian>>();
+    bytes[section_header + 40..][..4].copy_from_slice(&(dynstr_index as u32).to_le_bytes());
+    bytes[section_header + 44..][..4].copy_from_slice(&2u32.to_le_bytes());
+
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert_eq!(
+        object.version_requirements().unwrap(),
+        [
+            read::elf::VersionNeed {
+                library: b"libc.so.6",
+                versions: vec![&b"GLIBC_2.2.5"[..], b"GLIBC_2.34"],
+            },
+            read::elf::VersionNeed {
+                library: b"libm.so.6",
+                versions: vec![&b"GLIBC_2.29"[..]],
+            },
+        ]
+    );
+}
+
+#[test]
+fn newer_machines() {
+    for &(class, machine, arch) in &[
+        (elf::ELFCLASS32, elf::EM_CSKY, Architecture::Csky),
+        (
+            elf::ELFCLASS32,
+            elf::EM_LOONGARCH,
+            Architecture::LoongArch32,
+        ),
+        (
+            elf::ELFCLASS64,
+            elf::EM_LOONGARCH,
+            Architecture::LoongArch64,
+        ),
+        (elf::ELFCLASS64, 0xffff, Architecture::Unknown),
+    ] {
+        let write_arch = if class == elf::ELFCLASS32 {
+            Architecture::I386
+        } else {
+            Architecture::X86_64
+        };
+        let object = write::Object::new(BinaryFormat::Elf, write_arch, Endianness::Little);
+        let mut bytes = object.write().unwrap();
+        // Offset of `e_machine` in `elf::FileHeader*`.
+        bytes[18..20].copy_from_slice(&machine.to_le_bytes());
+        let file = read::File::parse(&*bytes).unwrap();
+        assert_eq!(file.architecture(), arch);
+    }
+}
+
+#[test]
+fn relocation_addend() {
+    for &(arch, implicit) in &[(Architecture::I386, true), (Architecture::X86_64, false)] {
+        let mut object = write::Object::new(BinaryFormat::Elf, arch, Endianness::Little);
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0xcc; 16], 16);
+        let data = object.section_id(write::StandardSection::Data);
+        object.append_section_data(data, &[0; 8], 4);
+        let symbol = object.section_symbol(text);
+        object
+            .add_relocation(
+                data,
+                write::Relocation {
+                    offset: 4,
+                    size: 32,
+                    kind: object::RelocationKind::Absolute,
+                    encoding: object::RelocationEncoding::Generic,
+                    symbol,
+                    addend: -4,
+                },
+            )
+            .unwrap();
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        let data = object.section_by_name(".data").unwrap();
+        let (offset, relocation) = data.relocations().next().unwrap();
+        assert_eq!(offset, 4);
+        assert_eq!(relocation.has_implicit_addend(), implicit);
+        if implicit {
+            assert_eq!(relocation.addend(), 0);
+        } else {
+            assert_eq!(relocation.addend(), -4);
+        }
+        assert_eq!(object.relocation_addend(&data, offset, &relocation), Ok(-4));
+    }
+}
+
+#[test]
+fn min_kernel_version() {
+    for &(os, expected) in &[
+        (elf::ELF_NOTE_OS_LINUX, Some((3, 2, 0))),
+        (elf::ELF_NOTE_OS_GNU, None),
+    ] {
+        let endian = Endianness::Little;
+        let mut object = write::Object::new(BinaryFormat::Elf, Architecture::X86_64, endian);
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(object::bytes_of(&elf::NoteHeader32 {
+            n_namesz: U32::new(endian, 4),
+            n_descsz: U32::new(endian, 16),
+            n_type: U32::new(endian, elf::NT_GNU_ABI_TAG),
+        }));
+        buffer.extend_from_slice(b"GNU\0");
+        for &word in &[os, 3, 2, 0] {
+            buffer.extend_from_slice(&word.to_le_bytes());
+        }
+        let section = object.add_section(Vec::new(), b".note.ABI-tag".to_vec(), SectionKind::Note);
+        object.section_mut(section).set_data(buffer, 4);
+        let bytes = object.write().unwrap();
+
+        let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+        assert_eq!(file.min_kernel_version().unwrap(), expected);
+    }
+
+    let object = write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let bytes = object.write().unwrap();
+    let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert_eq!(file.min_kernel_version().unwrap(), None);
+}
+
+#[test]
+fn prelink() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let mut dynamic = Vec::new();
+    for &(tag, value) in &[(elf::DT_GNU_PRELINKED, 0x1234_5678u64), (elf::DT_NULL, 0)] {
+        dynamic.extend_from_slice(&u64::from(tag).to_le_bytes());
+        dynamic.extend_from_slice(&value.to_le_bytes());
+    }
+    let section = object.add_section(
+        Vec::new(),
+        b".dynamic".to_vec(),
+        SectionKind::Elf(elf::SHT_DYNAMIC),
+    );
+    object.section_mut(section).set_data(dynamic, 8);
+    let bytes = object.write().unwrap();
+    let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(file.is_prelinked());
+    assert_eq!(file.prelink_timestamp().unwrap(), Some(0x1234_5678));
+
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let section = object.add_section(
+        Vec::new(),
+        b".gnu.prelink_undo".to_vec(),
+        SectionKind::Elf(elf::SHT_PROGBITS),
+    );
+    object.section_mut(section).set_data(vec![0; 8], 8);
+    let bytes = object.write().unwrap();
+    let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(file.is_prelinked());
+    assert_eq!(file.prelink_timestamp().unwrap(), None);
+
+    let object = write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let bytes = object.write().unwrap();
+    let file = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(!file.is_prelinked());
+}
+
+#[cfg(feature = "xz")]
+#[test]
+fn gnu_debugdata_symbols() {
+    // An ELF file containing a `mini_debug_info` symbol, compressed with `xz -9e`.
+    let xz = [
+        0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00, 0x00, 0x04, 0xe6, 0xd6, 0xb4, 0x46, 0x04, 0xc0, 0x77,
+        0xa0, 0x03, 0x21, 0x01, 0x1c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xcf, 0xa3,
+        0xfa, 0xb5, 0xe0, 0x01, 0x9f, 0x00, 0x6f, 0x5d, 0x00, 0x3f, 0x91, 0x45, 0x84, 0x68, 0x3d,
+        0x89, 0xa6, 0xda, 0x9c, 0xa6, 0xac, 0x2e, 0x2b, 0xcb, 0xcd, 0xa3, 0xdb, 0xc1, 0xf1, 0xea,
+        0x44, 0xa1, 0xb9, 0x1a, 0xd7, 0x7b, 0xe0, 0x6b, 0xaa, 0x4d, 0x13, 0x3d, 0x48, 0x04, 0x83,
+        0x5f, 0x94, 0x99, 0x34, 0xae, 0x13, 0x1f, 0x52, 0x8d, 0x71, 0xab, 0xc3, 0xad, 0x31, 0x13,
+        0xdd, 0xc4, 0x40, 0x29, 0x46, 0x33, 0x24, 0x5e, 0x5d, 0xfd, 0x41, 0x56, 0x8c, 0xba, 0xb4,
+        0x5d, 0x59, 0x18, 0x4a, 0x83, 0xb8, 0x73, 0xe0, 0xec, 0xae, 0xb9, 0xba, 0x20, 0xaa, 0x76,
+        0x28, 0x50, 0x30, 0xce, 0x30, 0x1e, 0x8a, 0xa9, 0x4b, 0xe6, 0xd9, 0x2e, 0xdd, 0x30, 0x6f,
+        0x0e, 0x86, 0x1d, 0xf9, 0x02, 0xce, 0x64, 0xe2, 0xfb, 0xe8, 0x2a, 0xae, 0x40, 0x71, 0x00,
+        0x00, 0x00, 0x13, 0x03, 0x38, 0x12, 0x18, 0xc6, 0x90, 0x62, 0x00, 0x01, 0x93, 0x01, 0xa0,
+        0x03, 0x00, 0x00, 0xdc, 0x3e, 0xea, 0x2a, 0xb1, 0xc4, 0x67, 0xfb, 0x02, 0x00, 0x00, 0x00,
+        0x00, 0x04, 0x59, 0x5a,
+    ];
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let section = object.add_section(Vec::new(), b".gnu_debugdata".to_vec(), SectionKind::Other);
+    object.append_section_data(section, &xz, 1);
+    let bytes = object.write().unwrap();
+
+    let object = read::elf::ElfFile64::<Endianness>::parse(&*bytes).unwrap();
+    let data = object.gnu_debugdata().unwrap().unwrap();
+    let inner = read::elf::ElfFile64::<Endianness>::parse(&*data).unwrap();
+    let symbol = inner
+        .symbols()
+        .find(|symbol| symbol.name() == Ok("mini_debug_info"))
+        .unwrap();
+    assert_eq!(symbol.address(), 0x1000);
+    assert_eq!(symbol.size(), 16);
+}
@@ -1,5 +1,10 @@
 use object::read::macho::MachHeader;
-use object::{macho, write, Architecture, BinaryFormat, Endianness};
+use object::read::{Object, ObjectSection, ObjectSegment, ObjectSymbol};
+use object::{
+    macho, read, write, Architecture, BigEndian, BinaryFormat, Endianness, SectionFlags,
+    SectionKind, SymbolFlags, SymbolKind, SymbolScope, U32, U64,
+};
+use std::mem;
 
 #[test]
 // Test that segment size is valid when the first section needs alignment.
@@ -22,3 +27,839 @@ fn issue_286_segment_file_size() {
     assert_eq!(segment.vmsize.get(endian), 30);
     assert_eq!(segment.filesize.get(endian), 30);
 }
+
+#[test]
+fn segment_sections() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1; 30], 4);
+    let data = object.section_id(write::StandardSection::Data);
+    object.append_section_data(data, &[2; 30], 4);
+
+    let bytes = &*object.write().unwrap();
+    let object = read::File::parse(bytes).unwrap();
+    let mut segments = object.segments();
+    let segment = segments.next().unwrap();
+    assert!(segments.next().is_none());
+
+    let names = segment
+        .sections()
+        .map(|section| section.name().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["__text", "__data"]);
+}
+
+#[test]
+fn segment_by_name() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1; 30], 4);
+
+    let mut bytes = object.write().unwrap();
+    // The segment name of the first load command, which follows the 64-bit header.
+    let segname = mem::size_of::<macho::MachHeader64<Endianness>>() + 8;
+    bytes[segname..][..6].copy_from_slice(b"__TEXT");
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let segment = object.segment_by_name("__TEXT").unwrap();
+    assert_eq!(segment.name(), Ok(Some("__TEXT")));
+    assert!(object.segment_by_name("__DATA").is_none());
+    assert!(object.has_segment("__TEXT"));
+    assert!(!object.has_segment("__DATA"));
+}
+
+#[test]
+fn preferred_load_address() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1; 30], 4);
+
+    let mut bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.preferred_load_address(), 0);
+
+    // The segment name and address of the first load command.
+    let segname = mem::size_of::<macho::MachHeader64<Endianness>>() + 8;
+    bytes[segname..][..6].copy_from_slice(b"__TEXT");
+    bytes[segname + 16..][..8].copy_from_slice(&0x1_0000_0000u64.to_le_bytes());
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.preferred_load_address(), 0x1_0000_0000);
+}
+
+#[test]
+fn address_range() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1; 30], 4);
+
+    let mut bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.address_range(), (0, 0));
+    let size = object.segments().next().unwrap().size();
+    assert_ne!(size, 0);
+
+    // Change the file type and the address of the first segment.
+    bytes[12..16].copy_from_slice(&macho::MH_EXECUTE.to_le_bytes());
+    let vmaddr = mem::size_of::<macho::MachHeader64<Endianness>>() + 24;
+    bytes[vmaddr..][..8].copy_from_slice(&0x1000u64.to_le_bytes());
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.address_range(), (0x1000, 0x1000 + size));
+
+    // `__PAGEZERO` is not included.
+    let bytes = pagezero_executable();
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.segments().count(), 2);
+    assert_eq!(object.address_range(), (0x1_0000_0000, 0x1_0000_4000));
+}
+
+/// Create an executable containing a `__PAGEZERO` segment and a `__TEXT` segment.
+fn pagezero_executable() -> Vec<u8> {
+    let endian = Endianness::Little;
+    let segment = |name: &[u8], vmaddr: u64, vmsize: u64, filesize: u64, prot: u32| {
+        let mut segname = [0; 16];
+        segname[..name.len()].copy_from_slice(name);
+        macho::SegmentCommand64 {
+            cmd: U32::new(endian, macho::LC_SEGMENT_64),
+            cmdsize: U32::new(
+                endian,
+                mem::size_of::<macho::SegmentCommand64<Endianness>>() as u32,
+            ),
+            segname,
+            vmaddr: U64::new(endian, vmaddr),
+            vmsize: U64::new(endian, vmsize),
+            fileoff: U64::new(endian, 0),
+            filesize: U64::new(endian, filesize),
+            maxprot: U32::new(endian, prot),
+            initprot: U32::new(endian, prot),
+            nsects: U32::new(endian, 0),
+            flags: U32::new(endian, 0),
+        }
+    };
+    let commands_size = 2 * mem::size_of::<macho::SegmentCommand64<Endianness>>();
+    let file_size = mem::size_of::<macho::MachHeader64<Endianness>>() + commands_size;
+    let header = macho::MachHeader64 {
+        magic: U32::new(BigEndian, macho::MH_CIGAM_64),
+        cputype: U32::new(endian, macho::CPU_TYPE_X86_64),
+        cpusubtype: U32::new(endian, macho::CPU_SUBTYPE_X86_64_ALL),
+        filetype: U32::new(endian, macho::MH_EXECUTE),
+        ncmds: U32::new(endian, 2),
+        sizeofcmds: U32::new(endian, commands_size as u32),
+        flags: U32::new(endian, 0),
+        reserved: U32::new(endian, 0),
+    };
+    let pagezero = segment(b"__PAGEZERO", 0, 0x1_0000_0000, 0, 0);
+    let text = segment(
+        b"__TEXT",
+        0x1_0000_0000,
+        0x4000,
+        file_size as u64,
+        macho::VM_PROT_READ | macho::VM_PROT_EXECUTE,
+    );
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(object::bytes_of(&header));
+    bytes.extend_from_slice(object::bytes_of(&pagezero));
+    bytes.extend_from_slice(object::bytes_of(&text));
+    bytes
+}
+
+#[test]
+fn contains_address() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1; 30], 4);
+
+    let mut bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+    assert!(!object.contains_address(0));
+    let size = object.segments().next().unwrap().size();
+
+    // Change the file type and the address of the first segment.
+    bytes[12..16].copy_from_slice(&macho::MH_EXECUTE.to_le_bytes());
+    let vmaddr = mem::size_of::<macho::MachHeader64<Endianness>>() + 24;
+    bytes[vmaddr..][..8].copy_from_slice(&0x1000u64.to_le_bytes());
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert!(!object.contains_address(0xfff));
+    assert!(object.contains_address(0x1000));
+    assert!(object.contains_address(0x1000 + size - 1));
+    assert!(!object.contains_address(0x1000 + size));
+
+    // Addresses in `__PAGEZERO` are not included.
+    let bytes = pagezero_executable();
+    let object = read::File::parse(&*bytes).unwrap();
+    assert!(!object.contains_address(0));
+    assert!(!object.contains_address(0xffff_ffff));
+    assert!(object.contains_address(0x1_0000_0000));
+    assert!(object.contains_address(0x1_0000_3fff));
+    assert!(!object.contains_address(0x1_0000_4000));
+}
+
+#[test]
+fn stub_section_kinds() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let stubs = object.add_section(b"__TEXT".to_vec(), b"__stubs".to_vec(), SectionKind::Text);
+    object.append_section_data(stubs, &[0xcc; 6], 1);
+    object.add_symbol(write::Symbol {
+        name: b"stub".to_vec(),
+        value: 0,
+        size: 0,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Compilation,
+        weak: false,
+        section: write::SymbolSection::Section(stubs),
+        flags: SymbolFlags::None,
+    });
+    let pointers = object.add_section(b"__DATA".to_vec(), b"__my_ptrs".to_vec(), SectionKind::Data);
+    object.append_section_data(pointers, &[0; 8], 8);
+    object.section_mut(pointers).flags = SectionFlags::MachO {
+        flags: macho::S_LAZY_SYMBOL_POINTERS,
+    };
+
+    let bytes = &*object.write().unwrap();
+    let object = read::File::parse(bytes).unwrap();
+
+    let section = object.section_by_name("__stubs").unwrap();
+    assert_eq!(section.kind(), SectionKind::Text);
+    let section = object.section_by_name("__my_ptrs").unwrap();
+    assert_eq!(section.kind(), SectionKind::Data);
+
+    let symbol = object.symbols().find(|s| s.name() == Ok("_stub")).unwrap();
+    assert_eq!(symbol.kind(), SymbolKind::Text);
+}
+
+#[test]
+fn cstr_at() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 16], 16);
+    let strings = object.section_id(write::StandardSection::ReadOnlyString);
+    object.append_section_data(strings, b"hello\0world\0", 1);
+    let data = object.section_id(write::StandardSection::Data);
+    object.append_section_data(data, b"abc", 1);
+
+    let mut bytes = object.write().unwrap();
+    check_cstr_at(&bytes);
+
+    // Executables use a cached index of the section addresses instead.
+    bytes[12..16].copy_from_slice(&macho::MH_EXECUTE.to_le_bytes());
+    check_cstr_at(&bytes);
+}
+
+fn check_cstr_at(bytes: &[u8]) {
+    let object = read::File::parse(bytes).unwrap();
+
+    let address = object.section_by_name("__cstring").unwrap().address();
+    assert_eq!(object.cstr_at(address), Some(&b"hello"[..]));
+    assert_eq!(object.cstr_at(address + 6), Some(&b"world"[..]));
+    assert_eq!(object.cstr_at(address + 11), Some(&b""[..]));
+
+    // Strings must be terminated within the section.
+    let address = object.section_by_name("__data").unwrap().address();
+    assert_eq!(object.cstr_at(address), None);
+    assert_eq!(object.cstr_at(0x1000_0000), None);
+}
+
+#[test]
+fn objc_sections() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let data = object.section_id(write::StandardSection::Data);
+    object.append_section_data(data, &[1; 8], 8);
+    for name in &[&b"__objc_classlist"[..], &b"__objc_selrefs"[..]] {
+        let section = object.add_section(b"__DATA".to_vec(), name.to_vec(), SectionKind::Data);
+        object.append_section_data(section, &[0; 8], 8);
+    }
+
+    let bytes = object.write().unwrap();
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    let names = object
+        .objc_sections()
+        .iter()
+        .map(|section| {
+            assert_eq!(section.kind(), SectionKind::Metadata);
+            section.name().unwrap().to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["__objc_classlist", "__objc_selrefs"]);
+    assert_eq!(
+        object.section_by_name("__data").unwrap().kind(),
+        SectionKind::Data
+    );
+}
+
+#[test]
+fn atoms() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+    object.flags = object::FileFlags::MachO {
+        flags: macho::MH_SUBSECTIONS_VIA_SYMBOLS,
+    };
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1, 2, 3, 4, 5, 6, 7, 8], 1);
+    for (name, value) in &[(&b"b"[..], 6), (&b"a"[..], 2)] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: *value,
+            size: 0,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+    }
+
+    let bytes = object.write().unwrap();
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    match object.flags() {
+        object::FileFlags::MachO { flags } => {
+            assert_ne!(flags & macho::MH_SUBSECTIONS_VIA_SYMBOLS, 0)
+        }
+        _ => panic!("unexpected flags"),
+    }
+    let section = object.section_by_name("__text").unwrap();
+    let atoms = section
+        .atoms()
+        .unwrap()
+        .iter()
+        .map(|atom| (atom.symbol, atom.address, atom.data))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        atoms,
+        [
+            ("", 0, &[1, 2][..]),
+            ("_a", 2, &[3, 4, 5, 6][..]),
+            ("_b", 6, &[7, 8][..]),
+        ]
+    );
+}
+
+#[test]
+fn fileset_entries() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 16], 16);
+    let entry = object.write().unwrap();
+
+    let name = b"com.example.kext\0\0\0\0\0\0\0\0";
+    let cmdsize = 32 + name.len() as u32;
+    let fileoff = 0x100u64;
+    let mut bytes = Vec::new();
+    for value in &[
+        macho::MH_MAGIC_64,
+        macho::CPU_TYPE_X86_64,
+        macho::CPU_SUBTYPE_X86_64_ALL,
+        macho::MH_FILESET,
+        1,
+        cmdsize,
+        0,
+        0,
+        macho::LC_FILESET_ENTRY,
+        cmdsize,
+    ] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&0xffff_ff80_0000_4000u64.to_le_bytes());
+    bytes.extend_from_slice(&fileoff.to_le_bytes());
+    bytes.extend_from_slice(&32u32.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(name);
+    bytes.resize(fileoff as usize, 0);
+    bytes.extend_from_slice(&entry);
+
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    let entries = object.fileset_entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name(), b"com.example.kext");
+    assert_eq!(entries[0].address(), 0xffff_ff80_0000_4000);
+    assert_eq!(entries[0].file_offset(), fileoff);
+    let entry = entries[0].parse_object().unwrap();
+    assert_eq!(entry.architecture(), Architecture::X86_64);
+    assert!(entry.section_by_name("__text").is_some());
+}
+
+#[test]
+fn section_data_by_name() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1; 4], 4);
+    let data_const = object.section_id(write::StandardSection::ReadOnlyDataWithRel);
+    object.append_section_data(data_const, &[2; 4], 4);
+    let rodata = object.section_id(write::StandardSection::ReadOnlyData);
+    object.append_section_data(rodata, &[3; 4], 4);
+
+    let bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+    assert_eq!(object.section_data_by_name(".text"), Ok(Some(&[1; 4][..])));
+    assert_eq!(object.section_data_by_name("__text"), Ok(Some(&[1; 4][..])));
+    assert_eq!(
+        object.section_data_by_name(".rodata"),
+        Ok(Some(&[3; 4][..]))
+    );
+    assert_eq!(object.section_data_by_name(".missing"), Ok(None));
+    assert!(object.has_section(".text"));
+    assert!(object.has_section("__const"));
+    assert!(!object.has_section(".missing"));
+}
+
+#[test]
+fn stabs() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[1; 16], 4);
+    for (name, value) in &[(&b"foo"[..], 0), (&b"bar"[..], 8)] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: *value,
+            size: 8,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+    }
+    let mut bytes = object.write().unwrap();
+
+    // Change `_foo` into an `N_FUN` stab entry.
+    let index = {
+        let object = read::File::parse(&*bytes).unwrap();
+        object
+            .symbols()
+            .find(|symbol| symbol.name() == Ok("_foo"))
+            .unwrap()
+            .index()
+            .0
+    };
+    let header = macho::MachHeader64::<Endianness>::parse(&*bytes, 0).unwrap();
+    let endian = header.endian().unwrap();
+    let mut commands = header.load_commands(endian, &*bytes, 0).unwrap();
+    let mut symoff = None;
+    while let Some(command) = commands.next().unwrap() {
+        if let Some(symtab) = command.symtab().unwrap() {
+            symoff = Some(symtab.symoff.get(endian) as usize);
+        }
+    }
+    let offset = symoff.unwrap() + index * mem::size_of::<macho::Nlist64<Endianness>>();
+    bytes[offset + 4] = macho::N_FUN;
+
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    let names: Vec<_> = object.symbols().map(|s| s.name().unwrap()).collect();
+    assert_eq!(names, ["_bar"]);
+    let stabs = object.stabs().unwrap();
+    assert_eq!(stabs.len(), 1);
+    assert_eq!(stabs[0].n_type, macho::N_FUN);
+    assert_eq!(stabs[0].name, b"_foo");
+}
+
+#[test]
+fn symbol_stubs() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xc3], 1);
+    let stubs = object.add_section(b"__TEXT".to_vec(), b"__stubs".to_vec(), SectionKind::Text);
+    object.section_mut(stubs).flags = SectionFlags::MachO {
+        flags: macho::S_SYMBOL_STUBS | macho::S_ATTR_PURE_INSTRUCTIONS,
+    };
+    object.append_section_data(stubs, &[0xcc; 12], 2);
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    assert!(!object.section_by_name("__text").unwrap().is_stub());
+    let stubs = object.section_by_name("__stubs").unwrap();
+    assert_eq!(stubs.kind(), SectionKind::Text);
+    assert!(stubs.is_stub());
+}
+
+#[test]
+fn data_in_code() {
+    let dataoff = 0x40u32;
+    let mut bytes = Vec::new();
+    for value in &[
+        macho::MH_MAGIC_64,
+        macho::CPU_TYPE_X86_64,
+        macho::CPU_SUBTYPE_X86_64_ALL,
+        macho::MH_EXECUTE,
+        1,
+        16,
+        0,
+        0,
+        macho::LC_DATA_IN_CODE,
+        16,
+        dataoff,
+        16,
+    ] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.resize(dataoff as usize, 0);
+    for &(offset, length, kind) in &[
+        (0x1010u32, 8u16, macho::DICE_KIND_JUMP_TABLE32 as u16),
+        (0x1040, 2, macho::DICE_KIND_DATA as u16),
+    ] {
+        bytes.extend_from_slice(&offset.to_le_bytes());
+        bytes.extend_from_slice(&length.to_le_bytes());
+        bytes.extend_from_slice(&kind.to_le_bytes());
+    }
+
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    let entries: Vec<_> = object
+        .data_in_code()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry.offset.get(Endianness::Little),
+                entry.length.get(Endianness::Little),
+                entry.kind.get(Endianness::Little),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            (0x1010, 8, macho::DICE_KIND_JUMP_TABLE32 as u16),
+            (0x1040, 2, macho::DICE_KIND_DATA as u16),
+        ]
+    );
+
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 16], 16);
+    let bytes = object.write().unwrap();
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(object.data_in_code().unwrap().is_empty());
+}
+
+#[test]
+fn exported_symbols() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xc3; 3], 1);
+    for &(name, value, scope, weak) in &[
+        (&b"global"[..], 0, SymbolScope::Dynamic, false),
+        (&b"weak"[..], 1, SymbolScope::Dynamic, true),
+        (&b"local"[..], 2, SymbolScope::Compilation, false),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value,
+            size: 1,
+            kind: SymbolKind::Text,
+            scope,
+            weak,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let exports = object.exported_symbols().unwrap();
+    let exports = exports
+        .iter()
+        .map(|export| (export.name(), export.address(), export.is_weak()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exports,
+        [(&b"_global"[..], 0, false), (&b"_weak"[..], 1, true)]
+    );
+}
+
+#[test]
+fn mach_uuid_matches() {
+    fn macho_with_uuid(filetype: u32, uuid: [u8; 16]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for value in &[
+            macho::MH_MAGIC_64,
+            macho::CPU_TYPE_X86_64,
+            macho::CPU_SUBTYPE_X86_64_ALL,
+            filetype,
+            1,
+            24,
+            0,
+            0,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&macho::LC_UUID.to_le_bytes());
+        data.extend_from_slice(&24u32.to_le_bytes());
+        data.extend_from_slice(&uuid);
+        data
+    }
+
+    let binary = macho_with_uuid(macho::MH_EXECUTE, [1; 16]);
+    let dsym = macho_with_uuid(macho::MH_DSYM, [1; 16]);
+    let other = macho_with_uuid(macho::MH_DSYM, [2; 16]);
+
+    let object = read::File::parse(&*binary).unwrap();
+    assert_eq!(object.mach_uuid().unwrap(), Some([1; 16]));
+    assert!(object.mach_uuid_matches(&dsym).unwrap());
+    assert!(!object.mach_uuid_matches(&other).unwrap());
+
+    // A fat file containing both debug files.
+    let mut fat = Vec::new();
+    fat.extend_from_slice(&macho::FAT_MAGIC.to_be_bytes());
+    fat.extend_from_slice(&2u32.to_be_bytes());
+    for &offset in &[0x40u32, 0x80] {
+        for value in &[
+            macho::CPU_TYPE_X86_64,
+            macho::CPU_SUBTYPE_X86_64_ALL,
+            offset,
+            dsym.len() as u32,
+            0,
+        ] {
+            fat.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+    fat.resize(0x40, 0);
+    fat.extend_from_slice(&other);
+    fat.resize(0x80, 0);
+    fat.extend_from_slice(&dsym);
+    assert!(object.mach_uuid_matches(&fat).unwrap());
+
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+    object.section_id(write::StandardSection::Text);
+    let bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+    assert!(!object.mach_uuid_matches(&dsym).unwrap());
+}
+
+#[test]
+fn dyld_cache_image() {
+    let dataoff = 0x40u32;
+    let mut bytes = Vec::new();
+    for value in &[
+        macho::MH_MAGIC_64,
+        macho::CPU_TYPE_X86_64,
+        macho::CPU_SUBTYPE_X86_64_ALL,
+        macho::MH_DYLIB,
+        1,
+        16,
+        macho::MH_DYLIB_IN_CACHE,
+        0,
+        macho::LC_SEGMENT_SPLIT_INFO,
+        16,
+        dataoff,
+        4,
+    ] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.resize(dataoff as usize, 0);
+    bytes.extend_from_slice(&[0x7f, 1, 2, 3]);
+
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(object.is_dyld_cache_image());
+    assert_eq!(
+        object.segment_split_info().unwrap(),
+        Some(&[0x7f, 1, 2, 3][..])
+    );
+
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 16], 16);
+    let bytes = object.write().unwrap();
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(!object.is_dyld_cache_image());
+    assert_eq!(object.segment_split_info().unwrap(), None);
+}
+
+#[test]
+fn relocated_data_external_branch() {
+    let mut object = write::Object::new(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        Endianness::Little,
+    );
+    let text = object.section_id(write::StandardSection::Text);
+    // nop; jmp _g
+    object.append_section_data(text, &[0x90, 0xe9, 0, 0, 0, 0], 1);
+    let g = object.add_symbol(write::Symbol {
+        name: b"g".to_vec(),
+        value: 0,
+        size: 0,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Undefined,
+        flags: SymbolFlags::None,
+    });
+    object
+        .add_relocation(
+            text,
+            write::Relocation {
+                offset: 2,
+                size: 32,
+                kind: object::RelocationKind::Relative,
+                encoding: object::RelocationEncoding::X86Branch,
+                symbol: g,
+                addend: -4,
+            },
+        )
+        .unwrap();
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let section = object.section_by_name("__text").unwrap();
+    let (_, relocation) = section.relocations().next().unwrap();
+    match relocation.flags() {
+        object::RelocationFlags::MachO { r_type, .. } => {
+            assert_eq!(r_type, macho::X86_64_RELOC_BRANCH)
+        }
+        flags => panic!("unexpected flags {:?}", flags),
+    }
+    // The undefined symbol is resolved to address 0.
+    let address = section.address();
+    let relocated = section.relocated_data().unwrap();
+    let expected = 0u64.wrapping_sub(address + 6) as u32;
+    assert_eq!(&relocated[2..6], &expected.to_le_bytes());
+}
+
+#[test]
+fn init_functions_unsupported_chained_fixups() {
+    let section_offset = 0x100u32;
+    let fixups_offset = 0x110u32;
+    let mut bytes = Vec::new();
+    for value in &[
+        macho::MH_MAGIC_64,
+        macho::CPU_TYPE_ARM64,
+        macho::CPU_SUBTYPE_ARM64E,
+        macho::MH_EXECUTE,
+        2,
+        72 + 80 + 16,
+        0,
+        0,
+        macho::LC_SEGMENT_64,
+        72 + 80,
+    ] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(b"__DATA\0\0\0\0\0\0\0\0\0\0");
+    for value in &[0x1000u64, 0x1000, 0, 0x200] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    for value in &[3u32, 3, 1, 0] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(b"__mod_init_func\0__DATA\0\0\0\0\0\0\0\0\0\0");
+    for value in &[0x1000u64 + u64::from(section_offset), 16] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    for value in &[
+        section_offset,
+        3,
+        0,
+        0,
+        macho::S_MOD_INIT_FUNC_POINTERS,
+        0,
+        0,
+        0,
+        macho::LC_DYLD_CHAINED_FIXUPS,
+        16,
+        fixups_offset,
+        60,
+    ] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.resize(section_offset as usize, 0);
+    for value in &[0x1234u64, 0x5678] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    // Chained fixups header, starts in image, and starts in segment.
+    for value in &[0u32, 28, 60, 60, 0, macho::DYLD_CHAINED_IMPORT, 0, 1, 8, 24] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&0x1000u16.to_le_bytes());
+    bytes.extend_from_slice(&macho::DYLD_CHAINED_PTR_ARM64E.to_le_bytes());
+    bytes.extend_from_slice(&0u64.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&section_offset.to_le_bytes()[..2]);
+    assert_eq!(bytes.len(), fixups_offset as usize + 60);
+
+    let object = read::macho::MachOFile64::<Endianness>::parse(&*bytes).unwrap();
+    assert!(object.chained_fixups().is_err());
+    // The raw section values are used instead of the fixups.
+    assert_eq!(object.init_functions().unwrap(), [0x1234, 0x5678]);
+}
@@ -6,6 +6,7 @@ use object::{
     Architecture, BinaryFormat, Endianness, RelocationEncoding, RelocationKind, SectionKind,
     SymbolFlags, SymbolKind, SymbolScope, SymbolSection,
 };
+use std::convert::TryInto;
 
 mod bss;
 mod comdat;
@@ -53,6 +54,7 @@ fn coff_x86_64() {
     let bytes = object.write().unwrap();
     let object = read::File::parse(&*bytes).unwrap();
     assert_eq!(object.format(), BinaryFormat::Coff);
+    assert!(object.is_relocatable());
     assert_eq!(object.architecture(), Architecture::X86_64);
     assert_eq!(object.endianness(), Endianness::Little);
 
@@ -90,6 +92,8 @@ fn coff_x86_64() {
     assert_eq!(symbol.is_weak(), false);
     assert_eq!(symbol.is_undefined(), false);
 
+    assert_eq!(object.relocation_sections(), [(text_index, text_index)]);
+
     let mut relocations = text.relocations();
 
     let (offset, relocation) = relocations.next().unwrap();
@@ -98,6 +102,12 @@ fn coff_x86_64() {
     assert_eq!(relocation.kind(), RelocationKind::Absolute);
     assert_eq!(relocation.encoding(), RelocationEncoding::Generic);
     assert_eq!(relocation.size(), 64);
+    assert_eq!(
+        relocation.flags(),
+        object::RelocationFlags::Coff {
+            typ: object::pe::IMAGE_REL_AMD64_ADDR64
+        }
+    );
     assert_eq!(
         relocation.target(),
         read::RelocationTarget::Symbol(func1_symbol)
@@ -111,6 +121,220 @@ fn coff_x86_64() {
     assert_eq!(map.get(func1_offset - 1), None);
 }
 
+#[test]
+fn coff_symbol_kinds() {
+    let mut object =
+        write::Object::new(BinaryFormat::Coff, Architecture::X86_64, Endianness::Little);
+
+    object.add_file_symbol(b"file.c".to_vec());
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xc3; 16], 16);
+    let data = object.section_id(write::StandardSection::Data);
+    object.append_section_data(data, &[1; 8], 8);
+    for (name, kind, scope, section) in &[
+        (
+            &b"func"[..],
+            SymbolKind::Text,
+            SymbolScope::Compilation,
+            text,
+        ),
+        (
+            &b"pad"[..],
+            SymbolKind::Data,
+            SymbolScope::Compilation,
+            data,
+        ),
+        (&b"var"[..], SymbolKind::Data, SymbolScope::Linkage, data),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.to_vec(),
+            value: 0,
+            size: 0,
+            kind: *kind,
+            scope: *scope,
+            weak: false,
+            section: write::SymbolSection::Section(*section),
+            flags: SymbolFlags::None,
+        });
+    }
+    object.section_symbol(text);
+
+    let mut bytes = object.write().unwrap();
+
+    // Give `func` an auxiliary function definition record (consuming `pad`), which
+    // must not cause it to be mistaken for a section symbol.
+    let symtab = u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize;
+    let func = (symtab..bytes.len())
+        .step_by(18)
+        .find(|&offset| &bytes[offset..offset + 8] == b"func\0\0\0\0")
+        .unwrap();
+    bytes[func + 14..func + 16].copy_from_slice(&0x20u16.to_le_bytes());
+    bytes[func + 17] = 1;
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let kinds = object
+        .symbols()
+        .map(|symbol| {
+            (
+                symbol.name().unwrap().to_string(),
+                symbol.kind(),
+                symbol.is_global(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert!(kinds.contains(&("file.c".into(), SymbolKind::File, false)));
+    assert!(kinds.contains(&("func".into(), SymbolKind::Text, false)));
+    assert!(kinds.contains(&("var".into(), SymbolKind::Data, true)));
+    assert!(kinds.contains(&(".text".into(), SymbolKind::Section, false)));
+    assert!(!kinds.iter().any(|(name, _, _)| name == "pad"));
+}
+
+#[test]
+fn coff_section_by_name() {
+    let mut object =
+        write::Object::new(BinaryFormat::Coff, Architecture::X86_64, Endianness::Little);
+    for name in &[&b".text"[..], b".data", b".debug_long_name", b".data"] {
+        let section = object.add_section(Vec::new(), name.to_vec(), SectionKind::Data);
+        object.append_section_data(section, &[0; 4], 4);
+    }
+    let bytes = object.write().unwrap();
+
+    // `section_by_name` uses a cached name index, so look up each name twice.
+    // If multiple sections have the same name, then the first is used.
+    let file = read::File::parse(&*bytes).unwrap();
+    for _ in 0..2 {
+        for &(name, index) in &[(".text", 1), (".data", 2), (".debug_long_name", 3)] {
+            let section = file.section_by_name(name).unwrap();
+            assert_eq!(section.index(), read::SectionIndex(index));
+            assert_eq!(section.name(), Ok(name));
+        }
+        assert!(file.section_by_name(".bss").is_none());
+        assert!(file.section_by_name(".dat").is_none());
+        assert!(file.section_by_name(".debug_long_name2").is_none());
+    }
+}
+
+#[test]
+fn coff_long_section_name() {
+    let mut object =
+        write::Object::new(BinaryFormat::Coff, Architecture::X86_64, Endianness::Little);
+    let section = object.add_section(Vec::new(), b".debug_info".to_vec(), SectionKind::Debug);
+    object.append_section_data(section, &[1; 4], 1);
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let section = object.section_by_name(".debug_info").unwrap();
+    assert_eq!(section.name(), Ok(".debug_info"));
+    assert_eq!(section.segment_name(), Ok(None));
+    assert_eq!(section.data(), Ok(&[1; 4][..]));
+
+    // The name in the section header is an offset into the string table.
+    let mut offset = 0;
+    let header = object::pe::ImageFileHeader::parse(&*bytes, &mut offset).unwrap();
+    let sections = header.sections(&*bytes, offset).unwrap();
+    let symbols = header.symbols(&*bytes).unwrap();
+    let section = sections.section(1).unwrap();
+    assert_eq!(section.raw_name()[0], b'/');
+    assert_eq!(section.name(symbols.strings()), Ok(&b".debug_info"[..]));
+}
+
+#[test]
+fn coff_grouped_sections() {
+    let mut object =
+        write::Object::new(BinaryFormat::Coff, Architecture::X86_64, Endianness::Little);
+    for &(name, kind, data, align) in &[
+        (&b".text$mn"[..], SectionKind::Text, &[1, 2, 3][..], 1),
+        (b".data", SectionKind::Data, &[4], 4),
+        (b".text$x", SectionKind::Text, &[5], 4),
+        (b".text$a", SectionKind::Text, &[6, 7], 1),
+    ] {
+        let section = object.add_section(Vec::new(), name.to_vec(), kind);
+        object.append_section_data(section, data, align);
+    }
+    let bytes = object.write().unwrap();
+
+    let object = read::File::parse(&*bytes).unwrap();
+    let groups = object.grouped_sections().unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].name(), ".text");
+    assert_eq!(
+        groups[0].sections(),
+        [
+            object.section_by_name(".text$a").unwrap().index(),
+            object.section_by_name(".text$mn").unwrap().index(),
+            object.section_by_name(".text$x").unwrap().index(),
+        ]
+    );
+    assert_eq!(groups[0].data(), [6, 7, 1, 2, 3, 0, 0, 0, 5]);
+    assert_eq!(groups[1].name(), ".data");
+    assert_eq!(groups[1].data(), [4]);
+
+    // The raw sections are unchanged.
+    assert!(object.section_by_name(".text").is_none());
+}
+
+#[test]
+fn parse_complete() {
+    for &format in &[BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0xc3; 16], 1);
+        let bytes = object.write().unwrap();
+
+        let file = read::File::parse_complete(&*bytes).unwrap();
+        assert!(file.required_file_size() <= bytes.len() as u64);
+    }
+}
+
+#[test]
+fn section_data_out_of_bounds() {
+    // ELF is tested in `elf::parse_complete`.
+    for &format in &[BinaryFormat::Coff, BinaryFormat::MachO] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0xc3; 16], 1);
+        let bss = object.section_id(write::StandardSection::UninitializedData);
+        object.append_section_bss(bss, 16, 1);
+        let mut bytes = object.write().unwrap();
+
+        let offset = {
+            let object = read::File::parse(&*bytes).unwrap();
+            for section in object.sections() {
+                if section.kind() == SectionKind::UninitializedData {
+                    assert_eq!(section.data(), Ok(&[][..]), "{:?}", format);
+                }
+            }
+            let section = object
+                .sections()
+                .find(|section| section.kind() == SectionKind::Text)
+                .unwrap();
+            section.file_range().unwrap().0
+        };
+
+        // Find the field in the section header that contains the file offset,
+        // and point it past the end of the file. The section headers are before
+        // the section data for these formats.
+        let len = bytes.len() as u32;
+        let header_len = bytes
+            .windows(16)
+            .position(|window| window == [0xc3; 16])
+            .unwrap();
+        let field = bytes[..header_len]
+            .windows(4)
+            .rposition(|window| window == (offset as u32).to_le_bytes())
+            .unwrap();
+        bytes[field..][..4].copy_from_slice(&len.to_le_bytes());
+
+        let object = read::File::parse(&*bytes).unwrap();
+        let section = object
+            .sections()
+            .find(|section| section.kind() == SectionKind::Text)
+            .unwrap();
+        assert!(section.data().is_err(), "{:?}", format);
+        assert!(section.uncompressed_data().is_err(), "{:?}", format);
+    }
+}
+
 #[test]
 fn elf_x86_64() {
     let mut object =
@@ -150,6 +374,7 @@ fn elf_x86_64() {
     let bytes = object.write().unwrap();
     let object = read::File::parse(&*bytes).unwrap();
     assert_eq!(object.format(), BinaryFormat::Elf);
+    assert!(object.is_relocatable());
     assert_eq!(object.architecture(), Architecture::X86_64);
     assert_eq!(object.endianness(), Endianness::Little);
 
@@ -204,6 +429,12 @@ fn elf_x86_64() {
     assert_eq!(symbol.is_weak(), false);
     assert_eq!(symbol.is_undefined(), false);
 
+    let relocation_sections = object.relocation_sections();
+    assert_eq!(relocation_sections.len(), 1);
+    assert_eq!(relocation_sections[0].1, text_index);
+    let rela_text = object.section_by_index(relocation_sections[0].0).unwrap();
+    assert_eq!(rela_text.name(), Ok(".rela.text"));
+
     let mut relocations = text.relocations();
 
     let (offset, relocation) = relocations.next().unwrap();
@@ -212,6 +443,12 @@ fn elf_x86_64() {
     assert_eq!(relocation.kind(), RelocationKind::Absolute);
     assert_eq!(relocation.encoding(), RelocationEncoding::Generic);
     assert_eq!(relocation.size(), 64);
+    assert_eq!(
+        relocation.flags(),
+        object::RelocationFlags::Elf {
+            r_type: object::elf::R_X86_64_64
+        }
+    );
     assert_eq!(
         relocation.target(),
         read::RelocationTarget::Symbol(func1_symbol)
@@ -244,6 +481,7 @@ fn elf_any() {
         (Architecture::Riscv32, Endianness::Little),
         (Architecture::Riscv64, Endianness::Little),
         (Architecture::S390x, Endianness::Big),
+        (Architecture::Sparc, Endianness::Big),
         (Architecture::Sparc64, Endianness::Big),
     ]
     .iter()
@@ -382,6 +620,7 @@ fn macho_x86_64() {
     let bytes = object.write().unwrap();
     let object = read::File::parse(&*bytes).unwrap();
     assert_eq!(object.format(), BinaryFormat::MachO);
+    assert!(object.is_relocatable());
     assert_eq!(object.architecture(), Architecture::X86_64);
     assert_eq!(object.endianness(), Endianness::Little);
 
@@ -411,6 +650,8 @@ fn macho_x86_64() {
     assert_eq!(symbol.is_weak(), false);
     assert_eq!(symbol.is_undefined(), false);
 
+    assert_eq!(object.relocation_sections(), [(text_index, text_index)]);
+
     let mut relocations = text.relocations();
 
     let (offset, relocation) = relocations.next().unwrap();
@@ -431,6 +672,14 @@ fn macho_x86_64() {
     assert_eq!(relocation.kind(), RelocationKind::Relative);
     assert_eq!(relocation.encoding(), RelocationEncoding::X86RipRelative);
     assert_eq!(relocation.size(), 32);
+    assert_eq!(
+        relocation.flags(),
+        object::RelocationFlags::MachO {
+            r_type: object::macho::X86_64_RELOC_SIGNED,
+            r_pcrel: true,
+            r_length: 2,
+        }
+    );
     assert_eq!(
         relocation.target(),
         read::RelocationTarget::Symbol(func1_symbol)
@@ -443,3 +692,630 @@ fn macho_x86_64() {
     assert_eq!(symbol.name(), "_func1");
     assert_eq!(map.get(func1_offset - 1), None);
 }
+
+#[test]
+fn stable_order() {
+    for format in [BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO]
+        .iter()
+        .copied()
+    {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        for i in 0..20 {
+            let name = format!("func{}", i).into_bytes();
+            let section = object.add_section(
+                Vec::new(),
+                format!(".text.{}", i).into_bytes(),
+                SectionKind::Text,
+            );
+            object.append_section_data(section, &[0xcc], 1);
+            object.add_symbol(write::Symbol {
+                name,
+                value: 0,
+                size: 1,
+                kind: SymbolKind::Text,
+                scope: SymbolScope::Linkage,
+                weak: false,
+                section: write::SymbolSection::Section(section),
+                flags: SymbolFlags::None,
+            });
+        }
+        let bytes = object.write().unwrap();
+        let object = read::File::parse(&*bytes).unwrap();
+
+        let sections = object.sections().map(|s| s.index().0).collect::<Vec<_>>();
+        assert!(sections.windows(2).all(|w| w[0] < w[1]), "{:?}", format);
+        let symbols = object.symbols().map(|s| s.index().0).collect::<Vec<_>>();
+        assert!(symbols.windows(2).all(|w| w[0] < w[1]), "{:?}", format);
+
+        let names = object
+            .symbols()
+            .filter_map(|s| s.name().ok())
+            .filter(|name| name.contains("func"))
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let expected = (0..20)
+            .map(|i| {
+                let prefix = if format == BinaryFormat::MachO {
+                    "_"
+                } else {
+                    ""
+                };
+                format!("{}func{}", prefix, i)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected, "{:?}", format);
+    }
+}
+
+#[test]
+fn parse_as() {
+    for format in [BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO]
+        .iter()
+        .copied()
+    {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0xcc; 4], 1);
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse_as(&*bytes, format).unwrap();
+        assert_eq!(object.format(), format);
+        assert_eq!(object.architecture(), Architecture::X86_64);
+        assert!(read::File::parse_as(&*bytes, BinaryFormat::Pe).is_err());
+    }
+
+    let object = write::Object::new(BinaryFormat::Elf, Architecture::I386, Endianness::Little);
+    let bytes = object.write().unwrap();
+    let object = read::File::parse_as(&*bytes, BinaryFormat::Elf).unwrap();
+    assert!(!object.is_64());
+    assert!(read::File::parse_as(&*bytes, BinaryFormat::MachO).is_err());
+}
+
+#[test]
+fn dwarf_section() {
+    use object::read::DwarfSectionId;
+
+    for &(format, info, str_offsets) in &[
+        (
+            BinaryFormat::Coff,
+            &b".debug_info"[..],
+            &b".debug_str_offsets"[..],
+        ),
+        (
+            BinaryFormat::Elf,
+            &b".debug_info"[..],
+            &b".debug_str_offsets"[..],
+        ),
+        (
+            BinaryFormat::MachO,
+            &b"__debug_info"[..],
+            &b"__debug_str_offs"[..],
+        ),
+    ] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        let segment = object.segment_name(write::StandardSegment::Debug).to_vec();
+        let section = object.add_section(segment.clone(), info.to_vec(), SectionKind::Debug);
+        object.append_section_data(section, &[1, 2, 3, 4], 1);
+        let section = object.add_section(segment, str_offsets.to_vec(), SectionKind::Debug);
+        object.append_section_data(section, &[5, 6], 1);
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        let data = object.dwarf_section(DwarfSectionId::DebugInfo).unwrap();
+        assert_eq!(data.as_deref(), Some(&[1, 2, 3, 4][..]), "{:?}", format);
+        let data = object
+            .dwarf_section(DwarfSectionId::DebugStrOffsets)
+            .unwrap();
+        assert_eq!(data.as_deref(), Some(&[5, 6][..]), "{:?}", format);
+        let data = object.dwarf_section(DwarfSectionId::DebugLine).unwrap();
+        assert_eq!(data, None);
+    }
+}
+
+#[test]
+fn owned_symbols() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xc3; 16], 1);
+    object.add_symbol(write::Symbol {
+        name: b"func".to_vec(),
+        value: 4,
+        size: 8,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: true,
+        section: write::SymbolSection::Section(text),
+        flags: SymbolFlags::None,
+    });
+    object.add_symbol(write::Symbol {
+        name: b"bad\xff".to_vec(),
+        value: 0,
+        size: 0,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Linkage,
+        weak: false,
+        section: write::SymbolSection::Undefined,
+        flags: SymbolFlags::None,
+    });
+    let bytes = object.write().unwrap();
+
+    let symbols = {
+        let object = read::File::parse(&*bytes).unwrap();
+        let symbol = object
+            .symbols()
+            .find(|symbol| symbol.name_bytes() == Ok(&b"bad\xff"[..]))
+            .unwrap();
+        assert!(symbol.name().is_err());
+        let symbols = object.owned_symbols();
+        assert_eq!(symbols.len(), object.symbols().count());
+        symbols
+    };
+    drop(bytes);
+    assert!(symbols.iter().any(|symbol| symbol.name() == "bad\u{fffd}"));
+    let symbol = symbols
+        .iter()
+        .find(|symbol| symbol.name() == "func")
+        .unwrap();
+    assert_eq!(symbol.address(), 4);
+    assert_eq!(symbol.size(), 8);
+    assert_eq!(symbol.kind(), SymbolKind::Text);
+    assert_eq!(symbol.scope(), SymbolScope::Linkage);
+    assert!(symbol.is_weak());
+    assert!(symbol.section().index().is_some());
+}
+
+#[test]
+fn section_is_zero() {
+    for &format in &[BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0, 0, 0xc3, 0], 1);
+        let data = object.section_id(write::StandardSection::Data);
+        object.append_section_data(data, &[0; 16], 1);
+        let bss = object.section_id(write::StandardSection::UninitializedData);
+        object.append_section_bss(bss, 16, 1);
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        for section in object.sections() {
+            match section.kind() {
+                SectionKind::Text => assert!(!section.is_zero(), "{:?}", format),
+                SectionKind::Data | SectionKind::UninitializedData => {
+                    assert!(section.is_zero(), "{:?}", format)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[test]
+fn symbols_in_section() {
+    for &format in &[BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        object.mangling = write::Mangling::None;
+
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0xc3; 16], 1);
+        let data = object.section_id(write::StandardSection::Data);
+        object.append_section_data(data, &[0; 16], 1);
+        for &(name, section, value) in &[
+            (&b"text2"[..], text, 8),
+            (&b"data1"[..], data, 0),
+            (&b"text1"[..], text, 4),
+            (&b"text0"[..], text, 0),
+        ] {
+            object.add_symbol(write::Symbol {
+                name: name.to_vec(),
+                value,
+                size: 0,
+                kind: SymbolKind::Data,
+                scope: SymbolScope::Linkage,
+                weak: false,
+                section: write::SymbolSection::Section(section),
+                flags: SymbolFlags::None,
+            });
+        }
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        let text = object
+            .sections()
+            .find(|section| section.kind() == SectionKind::Text)
+            .unwrap();
+        let names: Vec<_> = object
+            .symbols_in_section(text.index())
+            .iter()
+            .map(|symbol| symbol.name().unwrap().to_string())
+            .collect();
+        assert_eq!(names, ["text0", "text1", "text2"], "{:?}", format);
+    }
+}
+
+#[test]
+fn init_functions() {
+    use object::SectionFlags;
+
+    for &format in &[BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        object.mangling = write::Mangling::None;
+
+        let text = object.section_id(write::StandardSection::Text);
+        object.append_section_data(text, &[0xc3; 16], 16);
+        let (segment, name, kind, flags) = match format {
+            BinaryFormat::Coff => (&b""[..], &b".CRT$XCU"[..], SectionKind::Data, None),
+            BinaryFormat::Elf => (
+                &b""[..],
+                &b".init_array"[..],
+                SectionKind::Elf(object::elf::SHT_INIT_ARRAY),
+                Some(SectionFlags::Elf {
+                    sh_flags: u64::from(object::elf::SHF_ALLOC | object::elf::SHF_WRITE),
+                }),
+            ),
+            BinaryFormat::MachO => (
+                &b"__DATA"[..],
+                &b"__mod_init_func"[..],
+                SectionKind::Data,
+                Some(SectionFlags::MachO {
+                    flags: object::macho::S_MOD_INIT_FUNC_POINTERS,
+                }),
+            ),
+            _ => unreachable!(),
+        };
+        let init = object.add_section(segment.to_vec(), name.to_vec(), kind);
+        if let Some(flags) = flags {
+            object.section_mut(init).flags = flags;
+        }
+        object.append_section_data(init, &[0; 16], 8);
+
+        for &(name, value) in &[(&b"ctor1"[..], 8), (&b"ctor0"[..], 0)] {
+            let symbol = object.add_symbol(write::Symbol {
+                name: name.to_vec(),
+                value,
+                size: 8,
+                kind: SymbolKind::Text,
+                scope: SymbolScope::Compilation,
+                weak: false,
+                section: write::SymbolSection::Section(text),
+                flags: SymbolFlags::None,
+            });
+            object
+                .add_relocation(
+                    init,
+                    write::Relocation {
+                        offset: 8 - value,
+                        size: 64,
+                        kind: RelocationKind::Absolute,
+                        encoding: RelocationEncoding::Generic,
+                        symbol,
+                        addend: 0,
+                    },
+                )
+                .unwrap();
+        }
+        let bytes = object.write().unwrap();
+
+        let object = read::File::parse(&*bytes).unwrap();
+        let text = object.section_by_name(".text").unwrap();
+        assert_eq!(
+            object.init_functions().unwrap(),
+            [text.address() + 8, text.address()],
+            "{:?}",
+            format
+        );
+    }
+}
+
+#[test]
+fn write_to() {
+    for &(format, arch) in &[
+        (BinaryFormat::Coff, Architecture::X86_64),
+        (BinaryFormat::Elf, Architecture::X86_64),
+        (BinaryFormat::MachO, Architecture::X86_64),
+    ] {
+        let mut object = write::Object::new(format, arch, Endianness::Little);
+        object.mangling = write::Mangling::None;
+        let text = object.section_id(write::StandardSection::Text);
+        let data = object.section_id(write::StandardSection::Data);
+        let func_offset = object.append_section_data(text, &[0xc3; 16], 16);
+        let func = object.add_symbol(write::Symbol {
+            name: b"func".to_vec(),
+            value: func_offset,
+            size: 16,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+        object.append_section_data(data, &[0; 8], 8);
+        object
+            .add_relocation(
+                data,
+                write::Relocation {
+                    offset: 0,
+                    size: 64,
+                    kind: RelocationKind::Absolute,
+                    encoding: RelocationEncoding::Generic,
+                    symbol: func,
+                    addend: 0,
+                },
+            )
+            .unwrap();
+        let bytes = object.write().unwrap();
+        let object = read::File::parse(&*bytes).unwrap();
+
+        let mut copy = Vec::new();
+        object.write_to(&mut copy).unwrap();
+        let copy_object = read::File::parse(&*copy).unwrap();
+        assert_eq!(copy_object.format(), format);
+        assert_eq!(copy_object.architecture(), arch);
+        for section in object.sections() {
+            if section.kind() == SectionKind::Metadata {
+                continue;
+            }
+            let name = section.name().unwrap();
+            let copy_section = copy_object.section_by_name(name).unwrap();
+            assert_eq!(copy_section.kind(), section.kind(), "{}", name);
+            assert_eq!(copy_section.data(), section.data(), "{}", name);
+            assert_eq!(
+                copy_section.relocations().count(),
+                section.relocations().count(),
+                "{}",
+                name
+            );
+        }
+        let symbol = copy_object
+            .symbols()
+            .find(|symbol| symbol.name() == Ok("func"))
+            .unwrap();
+        assert_eq!(symbol.kind(), SymbolKind::Text);
+        assert_eq!(symbol.scope(), SymbolScope::Linkage);
+
+        // Writing the copy again doesn't change it.
+        let mut copy2 = Vec::new();
+        copy_object.write_to(&mut copy2).unwrap();
+        assert_eq!(copy, copy2);
+
+        if format == BinaryFormat::Elf {
+            // Executables are not supported.
+            let mut exe = bytes.clone();
+            exe[16..18].copy_from_slice(&object::elf::ET_EXEC.to_le_bytes());
+            let exe = read::File::parse(&*exe).unwrap();
+            assert!(exe.write_to(&mut Vec::new()).is_err());
+        }
+    }
+}
+
+#[test]
+fn relocation_count() {
+    for &format in &[BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        let text = object.section_id(write::StandardSection::Text);
+        let data = object.section_id(write::StandardSection::Data);
+        object.append_section_data(text, &[0xc3; 16], 16);
+        object.append_section_data(data, &[0; 16], 8);
+        let symbol = object.section_symbol(text);
+        for &offset in &[0, 8] {
+            object
+                .add_relocation(
+                    data,
+                    write::Relocation {
+                        offset,
+                        size: 64,
+                        kind: RelocationKind::Absolute,
+                        encoding: RelocationEncoding::Generic,
+                        symbol,
+                        addend: 0,
+                    },
+                )
+                .unwrap();
+        }
+        let mut bytes = object.write().unwrap();
+
+        let file = read::File::parse(&*bytes).unwrap();
+        let text = file.section_by_name(".text").unwrap();
+        assert_eq!(text.relocation_count(), 0);
+        let data = file.section_by_name(".data").unwrap();
+        assert_eq!(data.relocation_count(), 2);
+        assert_eq!(data.relocations().count(), 2);
+
+        if format == BinaryFormat::Coff {
+            // Use the relocation overflow encoding. The first relocation contains
+            // the count, which includes itself.
+            // COFF section indices are 1-based.
+            let header = 20 + 40 * (data.index().0 - 1);
+            assert_eq!(&bytes[header..header + 5], b".data");
+            let pointer =
+                u32::from_le_bytes(bytes[header + 24..header + 28].try_into().unwrap()) as usize;
+            bytes[header + 32..header + 34].copy_from_slice(&0xffffu16.to_le_bytes());
+            let characteristics =
+                u32::from_le_bytes(bytes[header + 36..header + 40].try_into().unwrap())
+                    | object::pe::IMAGE_SCN_LNK_NRELOC_OVFL;
+            bytes[header + 36..header + 40].copy_from_slice(&characteristics.to_le_bytes());
+            bytes[pointer..pointer + 4].copy_from_slice(&2u32.to_le_bytes());
+
+            let file = read::File::parse(&*bytes).unwrap();
+            let data = file.section_by_name(".data").unwrap();
+            assert_eq!(data.relocation_count(), 1);
+            let relocations = data.relocations().collect::<Vec<_>>();
+            assert_eq!(relocations.len(), 1);
+            assert_eq!(relocations[0].0, 8);
+        }
+    }
+}
+
+#[test]
+fn symbol_for_address() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 0x60], 16);
+    for &(name, value, size, kind, scope) in &[
+        ("start", 0, 0, SymbolKind::Label, SymbolScope::Compilation),
+        (
+            "alias",
+            0x10,
+            0x20,
+            SymbolKind::Text,
+            SymbolScope::Compilation,
+        ),
+        ("func", 0x10, 0x20, SymbolKind::Text, SymbolScope::Linkage),
+        (
+            ".Lcfi",
+            0x18,
+            0,
+            SymbolKind::Label,
+            SymbolScope::Compilation,
+        ),
+        ("b", 0x40, 0x10, SymbolKind::Text, SymbolScope::Linkage),
+        ("a", 0x40, 0x10, SymbolKind::Text, SymbolScope::Linkage),
+    ] {
+        object.add_symbol(write::Symbol {
+            name: name.as_bytes().to_vec(),
+            value,
+            size,
+            kind,
+            scope,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+    }
+    let bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+
+    let name = |address| {
+        object
+            .symbol_for_address(address)
+            .map(|symbol| symbol.name().unwrap())
+    };
+    assert_eq!(name(0x8), Some("start"));
+    // The function is preferred over the label, and global over local.
+    assert_eq!(name(0x10), Some("func"));
+    assert_eq!(name(0x18), Some("func"));
+    assert_eq!(name(0x2f), Some("func"));
+    // After the end of the function, only the closest label remains.
+    assert_eq!(name(0x30), Some(".Lcfi"));
+    // Name order is used for symbols that are otherwise equal.
+    assert_eq!(name(0x48), Some("a"));
+}
+
+#[test]
+fn symbols_sorted_by_address() {
+    let mut object =
+        write::Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+    let text = object.section_id(write::StandardSection::Text);
+    object.append_section_data(text, &[0xcc; 0x30], 16);
+    for &(name, value) in &[("c", 0x20), ("b", 0x10), ("a", 0x20), ("d", 0)] {
+        object.add_symbol(write::Symbol {
+            name: name.as_bytes().to_vec(),
+            value,
+            size: 0x10,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: write::SymbolSection::Section(text),
+            flags: SymbolFlags::None,
+        });
+    }
+    object.add_symbol(write::Symbol {
+        name: b"undefined".to_vec(),
+        value: 0,
+        size: 0,
+        kind: SymbolKind::Text,
+        scope: SymbolScope::Dynamic,
+        weak: false,
+        section: write::SymbolSection::Undefined,
+        flags: SymbolFlags::None,
+    });
+    let bytes = object.write().unwrap();
+    let object = read::File::parse(&*bytes).unwrap();
+
+    let names: Vec<_> = object
+        .symbols_sorted_by_address()
+        .iter()
+        .map(|symbol| symbol.name().unwrap())
+        .collect();
+    assert_eq!(names, ["d", "b", "a", "c"]);
+
+    let map = object.symbol_map();
+    let names: Vec<_> = map.symbols().iter().map(|symbol| symbol.name()).collect();
+    assert_eq!(names, ["d", "b", "a", "c"]);
+}
+
+#[test]
+fn sections_by_name() {
+    for &format in &[BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        for &byte in &[1, 2] {
+            let section =
+                object.add_section(b"__TEXT".to_vec(), b"dup".to_vec(), SectionKind::Text);
+            object.append_section_data(section, &[byte; 4], 4);
+        }
+        let bytes = object.write().unwrap();
+        let object = read::File::parse(&*bytes).unwrap();
+
+        let sections = object.sections_by_name("dup");
+        assert_eq!(sections.len(), 2, "{:?}", format);
+        assert_eq!(sections[0].data().unwrap(), &[1; 4]);
+        assert_eq!(sections[1].data().unwrap(), &[2; 4]);
+        assert_eq!(
+            object.section_by_name("dup").unwrap().index(),
+            sections[0].index()
+        );
+        assert!(object.sections_by_name("missing").is_empty());
+    }
+}
+
+#[test]
+fn sections_by_name_translation() {
+    for &format in &[BinaryFormat::Coff, BinaryFormat::Elf, BinaryFormat::MachO] {
+        let mut object = write::Object::new(format, Architecture::X86_64, Endianness::Little);
+        let rodata = object.section_id(write::StandardSection::ReadOnlyData);
+        object.append_section_data(rodata, &[1; 4], 4);
+        // Mach-O also uses `__const` in other segments.
+        let section =
+            object.add_section(b"__DATA".to_vec(), b"__const".to_vec(), SectionKind::Data);
+        object.append_section_data(section, &[2; 4], 4);
+        let (segment, name) = match format {
+            BinaryFormat::MachO => (&b"__DWARF"[..], &b"__debug_info"[..]),
+            _ => (&[][..], &b".debug_info"[..]),
+        };
+        let section = object.add_section(segment.to_vec(), name.to_vec(), SectionKind::Debug);
+        object.append_section_data(section, &[3; 4], 1);
+        let bytes = object.write().unwrap();
+        let object = read::File::parse(&*bytes).unwrap();
+
+        for &name in &[
+            ".rodata",
+            ".rdata",
+            "__const",
+            ".debug_info",
+            "__debug_info",
+            "missing",
+        ] {
+            let sections = object.sections_by_name(name);
+            assert_eq!(
+                sections.first().map(|section| section.index()),
+                object.section_by_name(name).map(|section| section.index()),
+                "{:?} {}",
+                format,
+                name
+            );
+        }
+        assert_eq!(
+            object.sections_by_name(".debug_info").len(),
+            1,
+            "{:?}",
+            format
+        );
+        if format == BinaryFormat::MachO {
+            let sections = object.sections_by_name(".rodata");
+            assert_eq!(sections.len(), 1);
+            assert_eq!(sections[0].data().unwrap(), &[1; 4]);
+            assert_eq!(object.sections_by_name("__const").len(), 2);
+        }
+    }
+}
@@ -131,6 +131,8 @@ fn elf_x86_64_tls() {
     assert_eq!(symbol.is_weak(), false);
     assert_eq!(symbol.is_undefined(), false);
     assert_eq!(symbol.size(), 30);
+    assert_eq!(symbol.tls_offset(), Some(0));
+    assert_eq!(symbol.common_alignment(), None);
 
     let symbol = symbols.next().unwrap();
     println!("{:?}", symbol);
@@ -141,6 +143,7 @@ fn elf_x86_64_tls() {
     assert_eq!(symbol.is_weak(), false);
     assert_eq!(symbol.is_undefined(), false);
     assert_eq!(symbol.size(), 31);
+    assert_eq!(symbol.tls_offset(), Some(0));
 }
 
 #[test]